@@ -0,0 +1,58 @@
+// Audible alarm for new critical faults. Drives the PC speaker / buzzer through the Linux
+// console tone ioctl, so no audio stack is needed on the car; when the console can't be
+// opened (no permission, no speaker) the terminal bell is rung instead.
+use log::warn;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::os::unix::io::AsRawFd;
+use std::time::Duration;
+
+const KIOCSOUND: libc::c_ulong = 0x4B2F;
+// Input clock of the PC speaker timer; KIOCSOUND takes clock / frequency
+const SPEAKER_CLOCK_HZ: u32 = 1_193_180;
+
+const ALARM_TONE_HZ: u32 = 2000;
+const ALARM_BEEP: Duration = Duration::from_millis(150);
+const ALARM_GAP: Duration = Duration::from_millis(100);
+const ALARM_BEEPS: usize = 3;
+
+// Sound the alarm on a background thread so the GUI never waits on it
+pub fn sound_alarm() {
+    let spawned = std::thread::Builder::new()
+        .name("alarm".to_string())
+        .spawn(|| {
+            for beep in 0..ALARM_BEEPS {
+                if beep > 0 {
+                    std::thread::sleep(ALARM_GAP);
+                }
+                if console_tone(ALARM_TONE_HZ, ALARM_BEEP).is_err() {
+                    terminal_bell();
+                    return;
+                }
+            }
+        });
+    if let Err(e) = spawned {
+        warn!("Failed to start alarm thread: {}", e);
+    }
+}
+
+fn console_tone(frequency_hz: u32, duration: Duration) -> std::io::Result<()> {
+    let console = OpenOptions::new().write(true).open("/dev/console")?;
+    let fd = console.as_raw_fd();
+    let divisor = (SPEAKER_CLOCK_HZ / frequency_hz.max(1)) as libc::c_int;
+
+    // SAFETY: fd is an open console descriptor; KIOCSOUND takes its argument by value
+    if unsafe { libc::ioctl(fd, KIOCSOUND as _, divisor) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    std::thread::sleep(duration);
+    // SAFETY: as above; 0 turns the tone off
+    unsafe { libc::ioctl(fd, KIOCSOUND as _, 0) };
+    Ok(())
+}
+
+fn terminal_bell() {
+    let mut stdout = std::io::stdout();
+    let _ = stdout.write_all(b"\x07");
+    let _ = stdout.flush();
+}
@@ -0,0 +1,81 @@
+// Estimated CAN bus load: on-wire bits of the frames received over a sliding window, as a
+// percentage of the nominal bitrate. Frames are sized with can::frame_bits (SOF, arbitration,
+// control, CRC, ACK, EOF, interframe space and worst-case stuffing), so the estimate errs high.
+// Only frames the socket delivers are counted; with the default DBC filter, use --promiscuous
+// to measure the whole bus.
+use crate::can::frame_bits;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+pub const BUS_LOAD_WINDOW: Duration = Duration::from_secs(1);
+
+pub struct BusLoadMeter {
+    window: Duration,
+    frames: VecDeque<(Instant, u32)>, // Arrival time and on-wire bits, oldest first
+}
+
+impl BusLoadMeter {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            frames: VecDeque::new(),
+        }
+    }
+
+    pub fn record(&mut self, data_len: usize, extended: bool) {
+        self.record_at(Instant::now(), frame_bits(data_len, extended));
+    }
+
+    fn record_at(&mut self, now: Instant, bits: u32) {
+        while let Some((arrived, _)) = self.frames.front() {
+            if now.duration_since(*arrived) <= self.window {
+                break;
+            }
+            self.frames.pop_front();
+        }
+        self.frames.push_back((now, bits));
+    }
+
+    // Load over the last window at `bitrate`, in percent (may exceed 100 with worst-case stuffing)
+    pub fn load_percent(&self, bitrate: u32) -> f64 {
+        self.load_percent_at(Instant::now(), bitrate)
+    }
+
+    fn load_percent_at(&self, now: Instant, bitrate: u32) -> f64 {
+        let bits: u64 = self
+            .frames
+            .iter()
+            .filter(|(arrived, _)| now.duration_since(*arrived) <= self.window)
+            .map(|(_, bits)| *bits as u64)
+            .sum();
+        bits as f64 * 100.0 / (self.window.as_secs_f64() * bitrate.max(1) as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_over_sliding_window() {
+        let start = Instant::now();
+        let mut meter = BusLoadMeter::new(BUS_LOAD_WINDOW);
+        assert_eq!(meter.load_percent_at(start, 500_000), 0.0);
+
+        // 1000 extended 8-byte frames in one second: 160 bits each at worst-case stuffing
+        let bits = frame_bits(8, true);
+        assert_eq!(bits, 160);
+        for i in 0..1000 {
+            meter.record_at(start + Duration::from_millis(i), bits);
+        }
+        let now = start + Duration::from_millis(999);
+        assert!((meter.load_percent_at(now, 500_000) - 32.0).abs() < 1e-9);
+        assert!((meter.load_percent_at(now, 250_000) - 64.0).abs() < 1e-9);
+
+        // Frames older than the window stop counting, even before new ones arrive
+        let later = start + Duration::from_millis(1500);
+        assert!((meter.load_percent_at(later, 500_000) - 16.0).abs() < 1e-9);
+        meter.record_at(start + Duration::from_secs(5), bits);
+        assert_eq!(meter.frames.len(), 1);
+    }
+}
@@ -1,241 +1,794 @@
 // Fixed src/can.rs - Updated CAN signal extraction with proper signed/unsigned handling
 
-use crate::gui_modules::{DTC_FLAGS_1_FAULTS, DTC_FLAGS_2_FAULTS};
-use can_dbc::{Signal, DBC};
-use socketcan::{CanFrame, EmbeddedFrame};
+use crate::gui_modules::{builtin_bit_faults, BitFault, BMS_DTC_ID};
+use can_dbc::{
+    AttributeValue, AttributeValuedForObjectType, MultiplexIndicator, Signal,
+    SignalExtendedValueType, ValueType, DBC,
+};
+use log::warn;
+use serde::Serialize;
+use socketcan::{
+    CanFilter, CanFrame, CanInterface, CanSocket, EmbeddedFrame, ExtendedId, Socket, StandardId,
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
 use std::fs;
+use std::sync::{Arc, Mutex, PoisonError};
+
+const CAN_EFF_FLAG: u32 = 0x8000_0000;
+const CAN_EFF_MASK: u32 = 0x1FFF_FFFF;
+const CAN_SFF_MASK: u32 = 0x7FF;
+
+pub const DEFAULT_CAN_BITRATE: u32 = 500_000;
+
+// Read the bitrate the kernel has configured for `iface` (None for vcan or if unavailable)
+pub fn detect_can_bitrate(iface: &str) -> Option<u32> {
+    let interface = CanInterface::open(iface).ok()?;
+    interface.bit_rate().ok().flatten()
+}
+
+// Worst-case on-wire bits for a classic CAN data frame, including stuff bits and interframe space
+pub fn frame_bits(data_len: usize, extended: bool) -> u32 {
+    let data_bits = 8 * data_len.min(8) as u32;
+    // Bits from SOF through the CRC are subject to stuffing
+    let (stuffable_bits, fixed_bits) = if extended {
+        (54 + data_bits, 67 + data_bits)
+    } else {
+        (34 + data_bits, 47 + data_bits)
+    };
+    fixed_bits + (stuffable_bits - 1) / 4
+}
+
+// Time one frame occupies the bus at `bitrate`, in microseconds
+pub fn frame_time_us(data_len: usize, extended: bool, bitrate: u32) -> f64 {
+    frame_bits(data_len, extended) as f64 * 1_000_000.0 / bitrate.max(1) as f64
+}
+
+// Seconds per unit for DBC time units, so durations display correctly whatever the DBC's scaling
+pub fn seconds_per_unit(unit: &str) -> Option<f64> {
+    match unit.trim().to_lowercase().as_str() {
+        "s" | "sec" | "secs" | "second" | "seconds" => Some(1.0),
+        "ds" | "decisecond" | "deciseconds" => Some(0.1),
+        "cs" | "centisecond" | "centiseconds" => Some(0.01),
+        "ms" | "millisecond" | "milliseconds" => Some(0.001),
+        "min" | "minute" | "minutes" => Some(60.0),
+        _ => None,
+    }
+}
+
+// Kernel-level filters matching exactly the given IDs (IDs above 0x7FF are treated as extended)
+pub fn build_id_filters(ids: &[u32]) -> Vec<CanFilter> {
+    ids.iter()
+        .map(|&id| {
+            if id > CAN_SFF_MASK {
+                CanFilter::new(id | CAN_EFF_FLAG, CAN_EFF_MASK | CAN_EFF_FLAG)
+            } else {
+                CanFilter::new(id, CAN_SFF_MASK | CAN_EFF_FLAG)
+            }
+        })
+        .collect()
+}
+
+// Build a data frame, e.g. for injection; None if the ID doesn't fit its format or the
+// data is over 8 bytes
+pub fn build_frame(id: u32, is_extended: bool, data: &[u8]) -> Option<CanFrame> {
+    if is_extended {
+        CanFrame::new(ExtendedId::new(id)?, data)
+    } else {
+        CanFrame::new(StandardId::new(id.try_into().ok()?)?, data)
+    }
+}
+
+// Transmit side of a CAN interface, for frames the car is asked to put on its bus
+pub struct CanWriter {
+    socket: CanSocket,
+    iface: String,
+}
+
+impl CanWriter {
+    pub fn open(iface: &str) -> Result<Self, String> {
+        let socket = CanSocket::open(iface)
+            .map_err(|e| format!("Failed to open CAN socket {}: {}", iface, e))?;
+        Ok(Self {
+            socket,
+            iface: iface.to_string(),
+        })
+    }
+
+    pub fn send(&self, frame: &CanFrame) -> Result<(), String> {
+        self.socket
+            .write_frame(frame)
+            .map_err(|e| format!("Failed to write frame to {}: {}", self.iface, e))
+    }
+}
+
+// Compact signal schema shared with the ground station so both sides decode identically
+#[derive(Serialize)]
+struct SignalSchema<'a> {
+    name: &'a str,
+    start_bit: u64,
+    size: u64,
+    byte_order: &'static str, // "intel" (little-endian) or "motorola" (big-endian)
+    signed: bool,
+    factor: f64,
+    offset: f64,
+    min: f64,
+    max: f64,
+    unit: &'a str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    values: Vec<ValueSchema<'a>>,
+}
+
+#[derive(Serialize)]
+struct ValueSchema<'a> {
+    value: i64,
+    description: &'a str,
+}
+
+#[derive(Serialize)]
+struct SchemaDocument<'a> {
+    messages: Vec<MessageSchema<'a>>,
+}
+
+#[derive(Serialize)]
+struct MessageSchema<'a> {
+    id: u32,
+    name: &'a str,
+    size: u64,
+    signals: Vec<SignalSchema<'a>>,
+}
+
+// One decoded signal: the raw integer from the frame, its scaled engineering value, the
+// DBC value-table label when the raw value has one, and the DBC unit ("" if none).
+// `out_of_range` marks a value outside the DBC [min|max] (then clipped into it when the
+// decoder clamps); signals with a value table are never flagged.
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodedSignal {
+    pub name: String,
+    pub raw: i64,
+    pub value: f64,
+    pub label: Option<String>,
+    pub unit: String,
+    pub out_of_range: bool,
+}
+
+impl DecodedSignal {
+    // Text for display and fault records: the label if there is one, otherwise the value
+    pub fn display_value(&self) -> String {
+        self.label.clone().unwrap_or_else(|| self.value.to_string())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DecodedMessage {
+    pub message_name: String, // Name the GUI mappings are keyed by (see message_name_for_id)
+    pub decoded: bool,        // False for the unknown() placeholder
+    // The DBC message's own name; None for IDs only the bit-fault table covers
+    pub dbc_name: Option<String>,
+    pub signals: Vec<DecodedSignal>,
+}
+
+impl DecodedMessage {
+    // Placeholder for frames the DBC can't decode; they are still logged and forwarded
+    pub fn unknown() -> Self {
+        Self {
+            message_name: UNMAPPED_MESSAGE.to_string(),
+            decoded: false,
+            dbc_name: None,
+            signals: Vec::new(),
+        }
+    }
+
+    // Name for outside consumers: the GUI name, or the DBC name for messages the GUI doesn't map
+    pub fn export_name(&self) -> &str {
+        match &self.dbc_name {
+            Some(dbc_name) if self.message_name == UNMAPPED_MESSAGE => dbc_name,
+            _ => &self.message_name,
+        }
+    }
+}
+
+// One "name: value unit" line per signal, for text logs and debugging. Signals with a value
+// description keep the raw number in front of it ("BPS_State: 2 (Active)").
+impl fmt::Display for DecodedMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, signal) in self.signals.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            match &signal.label {
+                Some(label) => write!(f, "{}: {} ({})", signal.name, signal.raw, label)?,
+                None if signal.unit.is_empty() => write!(f, "{}: {}", signal.name, signal.value)?,
+                None => write!(f, "{}: {} {}", signal.name, signal.value, signal.unit)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// GUI message name of IDs the mappings don't cover, and of undecodable frames
+pub const UNMAPPED_MESSAGE: &str = "Unknown";
+
+// Map a received ID to the message name used by the GUI value/fault mappings
+pub fn message_name_for_id(raw_id: u32) -> &'static str {
+    match raw_id {
+        BMS_DTC_ID => "BMS_DTC",
+        0x310 => "BMS_Limits",
+        0x320 => "BMS_Power",
+        0x330 => "BMS_State",
+        0x340 => "BMS_Capacity",
+        0x360 => "BMS_Temperature",
+        0x776 => "BPS_System",
+        0x777 => "BPS_Thing",
+        0x200 | 0x201 => "MPPT1",
+        0x202 | 0x203 => "MPPT2",
+        // Motor Controller 1 (ID ending in 05)
+        id if id == 0x0CF11E05
+            || id == 0x0CF11F05
+            || (id & 0xFFFFFF0F) == 0x0CF11E05
+            || (id & 0xFFFFFF0F) == 0x0CF11F05 =>
+        {
+            "MotorController_1"
+        }
+        // Motor Controller 2 (ID ending in 06)
+        id if id == 0x0CF11E06
+            || id == 0x8CF11F06
+            || (id & 0xFFFFFF0F) == 0x0CF11E06
+            || (id & 0xFFFFFF0F) == 0x0CF11F06 =>
+        {
+            "MotorController_2"
+        }
+        _ => UNMAPPED_MESSAGE,
+    }
+}
 
 #[derive(Clone)]
 pub struct CanDecoder {
     dbc: DBC,
+    path: Option<String>, // File the DBC was loaded from, for reload()
+    // DBC message ID -> index into dbc.messages() (first definition wins, like a linear scan)
+    message_index: HashMap<u32, usize>,
+    // Clip decoded values into the DBC [min|max] (set_clamp_to_range)
+    clamp_to_range: bool,
+    // Bit-packed fault words, decoded whether or not the DBC has their message (set_bit_faults)
+    bit_faults: Vec<BitFault>,
+    // "MESSAGE.Signal" names already reported as not fitting their frame (logged once each,
+    // shared with clones)
+    truncated_signals_logged: Arc<Mutex<BTreeSet<String>>>,
 }
 
 impl CanDecoder {
     pub fn new(dbc_path: &str) -> Self {
         let dbc_content = fs::read_to_string(dbc_path).expect("Failed to read DBC file");
-        let dbc = DBC::from_slice(dbc_content.as_bytes()).expect("Failed to parse DBC");
-        Self { dbc }
+        let mut decoder = Self::from_dbc_str(&dbc_content).expect("Failed to parse DBC");
+        decoder.path = Some(dbc_path.to_string());
+        decoder
+    }
+
+    // Re-read the DBC file this decoder was created from. On any error the current
+    // definitions are kept.
+    pub fn reload(&mut self) -> Result<(), String> {
+        let path = self
+            .path
+            .clone()
+            .ok_or_else(|| "decoder was not loaded from a file".to_string())?;
+        let dbc_content =
+            fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        let reloaded = Self::from_dbc_str(&dbc_content)?;
+
+        self.dbc = reloaded.dbc;
+        self.message_index = reloaded.message_index;
+        self.truncated_signals_logged = reloaded.truncated_signals_logged;
+        Ok(())
+    }
+
+    // Build a decoder from DBC text already in memory (inline fixtures, embedded DBCs)
+    pub fn from_dbc_str(dbc_content: &str) -> Result<Self, String> {
+        let dbc = DBC::from_slice(dbc_content.as_bytes()).map_err(|e| match e {
+            // Report where parsing stopped rather than dumping the partially parsed DBC
+            can_dbc::Error::Incomplete(_, remaining) => format!(
+                "Failed to parse DBC near: {}",
+                remaining.lines().next().unwrap_or("").trim()
+            ),
+            other => format!("Failed to parse DBC: {:?}", other),
+        })?;
+
+        let mut message_index = HashMap::new();
+        for (index, message) in dbc.messages().iter().enumerate() {
+            message_index
+                .entry(message.message_id().raw())
+                .or_insert(index);
+        }
+        Ok(Self {
+            dbc,
+            path: None,
+            message_index,
+            clamp_to_range: false,
+            bit_faults: builtin_bit_faults(),
+            truncated_signals_logged: Arc::new(Mutex::new(BTreeSet::new())),
+        })
+    }
+
+    // Clip values into the signal's [min|max] so one glitchy frame can't put e.g. -2000°C on
+    // the dashboard. Signals without a usable range (min == max) or with a value table keep
+    // their decoded value.
+    pub fn set_clamp_to_range(&mut self, clamp: bool) {
+        self.clamp_to_range = clamp;
+    }
+
+    // Replace the built-in fault words (the BMS DTC flags). A word's `replaces` signal (e.g. the
+    // raw DTC_Flags_1) is dropped from its message; other DBC signals are kept.
+    pub fn set_bit_faults(&mut self, bit_faults: Vec<BitFault>) {
+        self.bit_faults = bit_faults;
     }
 
-    pub fn decode(&self, frame: CanFrame) -> Option<String> {
+    pub fn decode(&self, frame: CanFrame) -> Option<DecodedMessage> {
         // Get the raw ID without any modification first
         let raw_id = match frame.id() {
             socketcan::Id::Standard(std_id) => std_id.as_raw() as u32,
             socketcan::Id::Extended(ext_id) => ext_id.as_raw(),
         };
 
-        // Special handling for DTC flags message (ID 0x300)
-        if raw_id == 0x300 && frame.data().len() >= 4 {
-            return Some(self.decode_dtc_flags(frame.data()));
+        let message = self.find_message(raw_id);
+        let bit_faults: Vec<&BitFault> = self
+            .bit_faults
+            .iter()
+            .filter(|fault| fault.id == raw_id)
+            .collect();
+        if message.is_none() && bit_faults.is_empty() {
+            return None;
         }
 
-        // First, check for exact match with raw_id
-        let exact_match = self.dbc.messages().iter().find(|m| {
-            let msg_id = m.message_id().raw();
-            msg_id == raw_id
-        });
-
-        if let Some(message) = exact_match {
-            return self.decode_message(message, frame);
+        let mut signals = message
+            .map(|message| self.decode_message(message, frame))
+            .unwrap_or_default();
+        if !bit_faults.is_empty() {
+            let replaced: HashSet<&str> = bit_faults
+                .iter()
+                .filter_map(|fault| fault.replaces.as_deref())
+                .collect();
+            signals.retain(|signal| !replaced.contains(signal.name.as_str()));
+            // Only set flags are reported, so a clear word adds no signals
+            signals.extend(Self::decode_bit_faults(&bit_faults, frame.data()));
         }
 
-        // Try with 11-bit ID (masked with 0x7FF for standard IDs)
-        let standard_id = raw_id & 0x7FF;
-        let standard_match = self.dbc.messages().iter().find(|m| {
-            let msg_id = m.message_id().raw();
-            msg_id == standard_id
-        });
+        Some(DecodedMessage {
+            message_name: message_name_for_id(raw_id).to_string(),
+            decoded: true,
+            dbc_name: message.map(|message| message.message_name().clone()),
+            signals,
+        })
+    }
 
-        if let Some(message) = standard_match {
-            return self.decode_message(message, frame);
-        }
+    // Build the frame for DBC message `raw_id` from engineering values, the inverse of decode.
+    // Signals `value_of` returns None for are left at raw 0; values are clamped to what fits.
+    pub fn encode(&self, raw_id: u32, value_of: impl Fn(&str) -> Option<f64>) -> Option<CanFrame> {
+        let message = self.find_message(raw_id)?;
+        let mut data = vec![0u8; (*message.message_size() as usize).min(8)];
 
-        // Try with 29-bit ID (masked with 0x1FFFFFFF for extended IDs)
-        let extended_id = raw_id & 0x1FFFFFFF;
-        let extended_match = self.dbc.messages().iter().find(|m| {
-            let msg_id = m.message_id().raw();
-            msg_id == extended_id
-        });
+        let raw_of = |signal: &Signal| {
+            value_of(signal.name()).map(|v| match self.float_type(message, signal) {
+                Some(float_type) => Self::float_raw_from_value(signal, float_type, v),
+                None => self.raw_from_value(signal, v),
+            })
+        };
+        let mux_value = message
+            .signals()
+            .iter()
+            .find(|signal| *signal.multiplexer_indicator() == MultiplexIndicator::Multiplexor)
+            .map(|switch| raw_of(switch).unwrap_or(0));
+
+        for signal in message.signals() {
+            if !Self::mux_selected(signal, mux_value) || !Self::signal_fits(signal, data.len()) {
+                continue;
+            }
+            if let Some(raw) = raw_of(signal) {
+                Self::insert_signal_value(&mut data, signal, raw);
+            }
+        }
 
-        if let Some(message) = extended_match {
-            return self.decode_message(message, frame);
+        match message.message_id() {
+            can_dbc::MessageId::Standard(id) => CanFrame::new(StandardId::new(*id)?, &data),
+            can_dbc::MessageId::Extended(id) => CanFrame::new(ExtendedId::new(*id)?, &data),
         }
+    }
+
+    // Every DBC message ID (as written in the DBC) with its GenMsgCycleTime in ms, if set
+    pub fn message_cycle_times(&self) -> Vec<(u32, Option<u64>)> {
+        self.dbc
+            .messages()
+            .iter()
+            .map(|message| {
+                let cycle_ms = self.dbc.attribute_values().iter().find_map(|attribute| {
+                    match attribute.attribute_value() {
+                        AttributeValuedForObjectType::MessageDefinitionAttributeValue(
+                            id,
+                            Some(value),
+                        ) if id == message.message_id()
+                            && attribute.attribute_name() == "GenMsgCycleTime" =>
+                        {
+                            match value {
+                                AttributeValue::AttributeValueU64(ms) => Some(*ms),
+                                AttributeValue::AttributeValueI64(ms) => u64::try_from(*ms).ok(),
+                                AttributeValue::AttributeValueF64(ms) => Some(*ms as u64),
+                                AttributeValue::AttributeValueCharString(_) => None,
+                            }
+                        }
+                        _ => None,
+                    }
+                });
+                (message.message_id().raw(), cycle_ms.filter(|ms| *ms > 0))
+            })
+            .collect()
+    }
+
+    // IDs worth receiving from the bus: every DBC message (as the bus reports it, without the
+    // DBC's extended flag) plus the bit fault IDs, which are decoded without the DBC
+    pub fn receive_ids(&self) -> Vec<u32> {
+        let mut ids: Vec<u32> = self
+            .dbc
+            .messages()
+            .iter()
+            .map(|message| message.message_id().raw() & 0x1FFF_FFFF)
+            .chain(self.bit_faults.iter().map(|fault| fault.id))
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
 
-        // Check if the DBC might have extended flag set (0x80000000) that we need to add
-        let flag_extended_id = raw_id | 0x80000000;
-        let flag_match = self.dbc.messages().iter().find(|m| {
-            let msg_id = m.message_id().raw();
-            msg_id == flag_extended_id
-        });
+    // Range-check a decoded engineering value against the DBC's declared [min|max].
+    // Returns None when the signal is unknown or has no usable range (min == max, e.g. [0|0]).
+    pub fn is_value_out_of_range(
+        &self,
+        raw_id: u32,
+        signal_name: &str,
+        value: f64,
+    ) -> Option<bool> {
+        let signal = self
+            .find_message(raw_id)?
+            .signals()
+            .iter()
+            .find(|signal| signal.name() == signal_name)?;
 
-        if let Some(message) = flag_match {
-            return self.decode_message(message, frame);
+        let (min, max) = (*signal.min(), *signal.max());
+        if min >= max {
+            return None;
         }
 
+        Some(value < min || value > max)
+    }
+
+    // Unit string the DBC declares for a signal (empty units are reported as None)
+    pub fn signal_unit(&self, raw_id: u32, signal_name: &str) -> Option<&str> {
+        self.find_message(raw_id)?
+            .signals()
+            .iter()
+            .find(|signal| signal.name() == signal_name)
+            .map(|signal| signal.unit().as_str())
+            .filter(|unit| !unit.is_empty())
+    }
+
+    // Resolve a received ID to its DBC message, tolerating the extended-flag and masking
+    // differences between what the bus reports and what the DBC declares
+    fn find_message(&self, raw_id: u32) -> Option<&can_dbc::Message> {
+        let by_id = |id: u32| self.message_index.get(&id).copied();
+        // Whichever of two IDs is defined first in the DBC
+        let first_of = |a: u32, b: u32| match (by_id(a), by_id(b)) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+
+        // Exact match first, then the 11-bit and 29-bit masked IDs, then with the
+        // extended flag (0x80000000) the DBC may have set
+        let mut index = by_id(raw_id)
+            .or_else(|| by_id(raw_id & 0x7FF))
+            .or_else(|| by_id(raw_id & 0x1FFFFFFF))
+            .or_else(|| by_id(raw_id | 0x80000000));
+
         // One more try for MotorController messages specifically - look for the right message pattern
-        if (raw_id & 0xFFFFFF00) == 0x8CF11E00 || (raw_id & 0x1FFFFFFF) == 0x0CF11E05 {
-            let mc_match = self.dbc.messages().iter().find(|m| {
-                let msg_id = m.message_id().raw();
-                msg_id == 0x8CF11E05 || msg_id == 217128453
-            });
-
-            if let Some(message) = mc_match {
-                return self.decode_message(message, frame);
-            }
+        if index.is_none()
+            && ((raw_id & 0xFFFFFF00) == 0x8CF11E00 || (raw_id & 0x1FFFFFFF) == 0x0CF11E05)
+        {
+            index = first_of(0x8CF11E05, 217128453);
+        }
+        if index.is_none()
+            && ((raw_id & 0xFFFFFF00) == 0x8CF11F00 || (raw_id & 0x1FFFFFFF) == 0x0CF11F05)
+        {
+            index = first_of(0x8CF11F05, 217128709);
         }
 
-        if (raw_id & 0xFFFFFF00) == 0x8CF11F00 || (raw_id & 0x1FFFFFFF) == 0x0CF11F05 {
-            let mc_match = self.dbc.messages().iter().find(|m| {
-                let msg_id = m.message_id().raw();
-                msg_id == 0x8CF11F05 || msg_id == 217128709
-            });
+        self.dbc.messages().get(index?)
+    }
 
-            if let Some(message) = mc_match {
-                return self.decode_message(message, frame);
-            }
-        }
+    // Export the decoded view of the DBC (messages, signals, value tables) as compact JSON
+    pub fn export_schema_json(&self) -> Result<String, serde_json::Error> {
+        let messages: Vec<MessageSchema> = self
+            .dbc
+            .messages()
+            .iter()
+            .map(|message| MessageSchema {
+                id: message.message_id().raw(),
+                name: message.message_name(),
+                size: *message.message_size(),
+                signals: message
+                    .signals()
+                    .iter()
+                    .map(|signal| SignalSchema {
+                        name: signal.name(),
+                        start_bit: *signal.start_bit(),
+                        size: *signal.signal_size(),
+                        byte_order: match signal.byte_order() {
+                            can_dbc::ByteOrder::LittleEndian => "intel",
+                            can_dbc::ByteOrder::BigEndian => "motorola",
+                        },
+                        // Use the decoder's own signedness so the receiver matches our output
+                        signed: self.is_signal_signed(signal),
+                        factor: *signal.factor(),
+                        offset: *signal.offset(),
+                        min: *signal.min(),
+                        max: *signal.max(),
+                        unit: signal.unit(),
+                        values: self
+                            .dbc
+                            .value_descriptions_for_signal(*message.message_id(), signal.name())
+                            .map(|descs| {
+                                descs
+                                    .iter()
+                                    .map(|desc| ValueSchema {
+                                        value: *desc.a() as i64,
+                                        description: desc.b(),
+                                    })
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                    })
+                    .collect(),
+            })
+            .collect();
 
-        None
+        serde_json::to_string(&SchemaDocument { messages })
     }
 
-    fn decode_dtc_flags(&self, data: &[u8]) -> String {
-        let mut result = String::new();
+    // A signal per set flag, for fault words read little-endian from `data` (zero past its end)
+    fn decode_bit_faults(bit_faults: &[&BitFault], data: &[u8]) -> Vec<DecodedSignal> {
+        bit_faults
+            .iter()
+            .filter(|fault| {
+                let mut word = [0u8; 4];
+                for (slot, byte) in word.iter_mut().zip(data.iter().skip(fault.byte as usize)) {
+                    *slot = *byte;
+                }
+                u32::from_le_bytes(word) & fault.mask != 0
+            })
+            .map(|fault| DecodedSignal {
+                name: fault.signal_name(),
+                raw: 1,
+                value: 1.0,
+                label: Some(fault.name.clone()),
+                unit: String::new(),
+                out_of_range: false,
+            })
+            .collect()
+    }
 
-        // Extract DTC_Flags_1 (first 2 bytes, little endian)
-        if data.len() >= 2 {
-            let flags1 = u16::from_le_bytes([data[0], data[1]]);
+    fn decode_message(&self, message: &can_dbc::Message, frame: CanFrame) -> Vec<DecodedSignal> {
+        // Raw value of the multiplexor switch (M), for messages that have one
+        let mux_value = message
+            .signals()
+            .iter()
+            .find(|signal| *signal.multiplexer_indicator() == MultiplexIndicator::Multiplexor)
+            .filter(|switch| Self::signal_fits(switch, frame.data().len()))
+            .map(|switch| self.raw_signal_value(switch, frame.data()));
 
-            for (mask, fault_name, _severity) in DTC_FLAGS_1_FAULTS {
-                if flags1 & mask != 0 {
-                    result.push_str(&format!(
-                        "Fault_DTC1_{}: {}\n",
-                        fault_name.split(':').next().unwrap(),
-                        fault_name
-                    ));
+        message
+            .signals()
+            .iter()
+            .filter(|signal| Self::mux_selected(signal, mux_value))
+            .filter(|signal| {
+                // A short frame would decode the missing bits as 0; omit the signal instead
+                let fits = Self::signal_fits(signal, frame.data().len());
+                if !fits {
+                    let key = format!("{}.{}", message.message_name(), signal.name());
+                    let mut logged = self
+                        .truncated_signals_logged
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner);
+                    if logged.insert(key.clone()) {
+                        warn!(
+                            "Signal {} ({}|{}) doesn't fit in a {}-byte frame, skipping it",
+                            key,
+                            signal.start_bit(),
+                            signal.signal_size(),
+                            frame.data().len()
+                        );
+                    }
                 }
-            }
-        }
+                fits
+            })
+            .map(|signal| {
+                let raw_value = self.raw_signal_value(signal, frame.data());
+
+                // IEEE-754 signals (SIG_VALTYPE_) carry the float's bit pattern, not an integer
+                let base_value = match self.float_type(message, signal) {
+                    Some(SignalExtendedValueType::IEEEfloat32Bit) => {
+                        f32::from_bits(raw_value as u32) as f64
+                    }
+                    Some(SignalExtendedValueType::IEEEdouble64bit) => {
+                        f64::from_bits(raw_value as u64)
+                    }
+                    _ => raw_value as f64,
+                };
+
+                // Scale raw value to engineering value
+                let mut signal_value = (*signal.factor() * base_value) + *signal.offset();
 
-        // Extract DTC_Flags_2 (next 2 bytes, little endian)
-        if data.len() >= 4 {
-            let flags2 = u16::from_le_bytes([data[2], data[3]]);
-
-            for (mask, fault_name, _severity) in DTC_FLAGS_2_FAULTS {
-                if flags2 & mask != 0 {
-                    result.push_str(&format!(
-                        "Fault_DTC2_{}: {}\n",
-                        fault_name.split(':').next().unwrap(),
-                        fault_name
-                    ));
+                // Lookup value descriptions via DBC
+                let value_descs = self
+                    .dbc
+                    .value_descriptions_for_signal(*message.message_id(), signal.name());
+                let value_desc = value_descs.and_then(|descs| {
+                    descs
+                        .iter()
+                        .find(|desc| (*desc.a()) as i64 == raw_value)
+                        .map(|d| d.b().clone())
+                });
+
+                let (min, max) = (*signal.min(), *signal.max());
+                let out_of_range = value_descs.is_none()
+                    && min < max
+                    && (signal_value < min || signal_value > max);
+                if out_of_range && self.clamp_to_range {
+                    signal_value = signal_value.clamp(min, max);
                 }
-            }
-        }
 
-        // Don't output zero-value DTC signals when no faults are active
-        // This prevents false positive fault detection from "DTC_Flags_1: 0" signals
-        
-        result
+                DecodedSignal {
+                    name: signal.name().clone(),
+                    raw: raw_value,
+                    value: signal_value,
+                    label: value_desc,
+                    unit: signal.unit().clone(),
+                    out_of_range,
+                }
+            })
+            .collect()
     }
 
-    fn decode_message(&self, message: &can_dbc::Message, frame: CanFrame) -> Option<String> {
-        Some(
-            message
-                .signals()
-                .iter()
-                .fold(String::new(), |mut acc, signal| {
-                    let raw_value = {
-                        let data_array = frame.data().to_vec();
-
-                        let start_bit = *signal.start_bit() as usize;
-                        let size = *signal.signal_size() as usize;
-
-                        // Determine endianness from the DBC signal
-                        let is_intel = match signal.byte_order() {
-                            can_dbc::ByteOrder::LittleEndian => true,
-                            can_dbc::ByteOrder::BigEndian => false,
-                        };
-
-                        // Check if signal is signed based on value type
-                        // The can-dbc library should parse the @1- notation
-                        let is_signed = self.is_signal_signed(signal);
-
-                        self.extract_signal_value(&data_array, start_bit, size, is_intel, is_signed)
-                    };
-
-                    // Scale raw value to engineering value
-                    let signal_value = (*signal.factor() * raw_value as f64) + *signal.offset();
-
-                    // Lookup value descriptions via DBC
-                    let value_desc = self
-                        .dbc
-                        .value_descriptions_for_signal(*message.message_id(), signal.name())
-                        .and_then(|descs| {
-                            descs
-                                .iter()
-                                .find(|desc| (*desc.a()) as i64 == raw_value)
-                                .map(|d| d.b())
-                        });
-
-                    if let Some(desc) = value_desc {
-                        acc.push_str(&format!("{}: {}\n", signal.name(), desc));
-                    } else {
-                        acc.push_str(&format!("{}: {}\n", signal.name(), signal_value));
-                    }
-                    acc
-                }),
-        )
+    fn raw_signal_value(&self, signal: &Signal, data: &[u8]) -> i64 {
+        let start_bit = *signal.start_bit() as usize;
+        let size = *signal.signal_size() as usize;
+
+        // Determine endianness from the DBC signal
+        let is_intel = match signal.byte_order() {
+            can_dbc::ByteOrder::LittleEndian => true,
+            can_dbc::ByteOrder::BigEndian => false,
+        };
+
+        // Check if signal is signed based on value type
+        // The can-dbc library should parse the @1- notation
+        let is_signed = self.is_signal_signed(signal);
+
+        self.extract_signal_value(data, start_bit, size, is_intel, is_signed)
     }
 
-    // Helper function to determine if a signal is signed
-    // Since can-dbc doesn't directly expose the signed flag, we need to infer it
-    fn is_signal_signed(&self, signal: &Signal) -> bool {
-        // Method 1: Check if min value is negative (most reliable when min/max are set correctly)
-        if *signal.min() < 0.0 {
-            return true;
+    // Engineering value -> raw integer, clamped to the signal's signed/unsigned range
+    fn raw_from_value(&self, signal: &Signal, value: f64) -> i64 {
+        let size = (*signal.signal_size()).clamp(1, 63) as u32;
+        let (min, max) = if self.is_signal_signed(signal) {
+            (-(1i64 << (size - 1)), (1i64 << (size - 1)) - 1)
+        } else {
+            (0, (1i64 << size) - 1)
+        };
+
+        let factor = match *signal.factor() {
+            0.0 => 1.0,
+            factor => factor,
+        };
+        let raw = ((value - *signal.offset()) / factor).round();
+        (raw as i64).clamp(min, max)
+    }
+
+    // SIG_VALTYPE_ float type of a signal, if it has one whose size matches (32 or 64 bits)
+    fn float_type(
+        &self,
+        message: &can_dbc::Message,
+        signal: &Signal,
+    ) -> Option<SignalExtendedValueType> {
+        let float_type = self
+            .dbc
+            .extended_value_type_for_signal(*message.message_id(), signal.name())?;
+        match (float_type, *signal.signal_size()) {
+            (SignalExtendedValueType::IEEEfloat32Bit, 32)
+            | (SignalExtendedValueType::IEEEdouble64bit, 64) => Some(*float_type),
+            _ => None,
         }
+    }
+
+    // Engineering value -> bit pattern of the IEEE-754 float the signal carries
+    fn float_raw_from_value(
+        signal: &Signal,
+        float_type: SignalExtendedValueType,
+        value: f64,
+    ) -> i64 {
+        let factor = match *signal.factor() {
+            0.0 => 1.0,
+            factor => factor,
+        };
+        let value = (value - *signal.offset()) / factor;
+        match float_type {
+            SignalExtendedValueType::IEEEfloat32Bit => (value as f32).to_bits() as i64,
+            _ => value.to_bits() as i64,
+        }
+    }
 
-        // Method 2: Check if max value suggests a signed interpretation
-        // For a 16-bit signal, if max > 32767, it's likely unsigned
-        // But if max <= 32767 and we see values > 32767 in practice, it's signed
-        let signal_size = *signal.signal_size() as u32;
-        if signal_size <= 32 {
-            let _max_signed = (1i64 << (signal_size - 1)) - 1;
-            let max_unsigned = (1u64 << signal_size) - 1;
-
-            // If the max value is set and it's less than half the unsigned range,
-            // it might be signed (this is a heuristic)
-            if *signal.max() > 0.0 && (*signal.max() as u64) < (max_unsigned / 2) {
-                // Additional check: some known signed signals
-                if signal.name().contains("Current")
-                    || signal.name().contains("Temperature")
-                    || signal.name().contains("Torque")
-                {
-                    return true;
+    // Write `raw` into `data` with the same bit layout extract_signal_value reads
+    fn insert_signal_value(data: &mut [u8], signal: &Signal, raw: i64) {
+        let start_bit = *signal.start_bit() as usize;
+        let size = (*signal.signal_size() as usize).min(64);
+        let raw = raw as u64;
+
+        for i in 0..size {
+            let (byte_index, bit_index, value_bit) = match signal.byte_order() {
+                can_dbc::ByteOrder::LittleEndian => ((start_bit + i) / 8, (start_bit + i) % 8, i),
+                can_dbc::ByteOrder::BigEndian => {
+                    let (byte_index, bit_index) = Self::motorola_bit(start_bit, i);
+                    (byte_index, bit_index, size - 1 - i)
+                }
+            };
+
+            if byte_index < data.len() {
+                if raw & (1 << value_bit) != 0 {
+                    data[byte_index] |= 1 << bit_index;
+                } else {
+                    data[byte_index] &= !(1 << bit_index);
                 }
             }
         }
+    }
+
+    // Multiplexed signals (m<N>) are only present when the switch reads N; plain signals and
+    // the switch itself always are. Nested (extended) multiplexing is keyed on the main switch.
+    fn mux_selected(signal: &Signal, mux_value: Option<i64>) -> bool {
+        match signal.multiplexer_indicator() {
+            MultiplexIndicator::MultiplexedSignal(selector)
+            | MultiplexIndicator::MultiplexorAndMultiplexedSignal(selector) => {
+                mux_value == Some(*selector as i64)
+            }
+            MultiplexIndicator::Multiplexor | MultiplexIndicator::Plain => true,
+        }
+    }
+
+    // (byte, bit) of the i-th bit of a Motorola signal counting down from its MSB. DBC start
+    // bits use sawtooth numbering (byte * 8 + bit, bit 0 = LSB): the signal runs down to bit 0
+    // of the start byte, then continues from bit 7 of the next byte.
+    fn motorola_bit(start_bit: usize, i: usize) -> (usize, usize) {
+        let offset = (7 - start_bit % 8) + i;
+        (start_bit / 8 + offset / 8, 7 - offset % 8)
+    }
+
+    // Whether the bytes a signal occupies (per its DBC layout) are all present in the frame
+    fn signal_fits(signal: &Signal, data_len: usize) -> bool {
+        let start_bit = *signal.start_bit() as usize;
+        let size = *signal.signal_size() as usize;
 
-        // Method 3: Check specific signals we know are signed
-        // This is a workaround for incorrect DBC files
-        match signal.name().as_str() {
-            "Pack_Current"
-            | "Average_Current"
-            | "Low_Voltage_Current"
-            | "Actual_Current_A"
-            | "Controller_Temperature_C"
-            | "Motor_Temperature_C"
-            | "Motor_Temperature_Data"
-            | "Ambient_Temperature_C"
-            | "Heatsink_Temperature_C"
-            | "Input_Current_A"
-            | "Output_Current_A"
-            | "Input_Voltage_V"
-            | "Output_Voltage_V"
-            | "BPS_Voltage" => true,
-            _ => false,
+        match signal.byte_order() {
+            can_dbc::ByteOrder::LittleEndian => start_bit + size <= data_len * 8,
+            // Motorola start_bit is the MSB; the signal runs down to bit 0 of that byte,
+            // then continues from bit 7 of each following byte
+            can_dbc::ByteOrder::BigEndian => {
+                let bits_in_first_byte = start_bit % 8 + 1;
+                let extra_bytes = size.saturating_sub(bits_in_first_byte).div_ceil(8);
+                start_bit / 8 + extra_bytes < data_len
+            }
         }
     }
 
+    // Helper function to determine if a signal is signed
+    // Signedness is the DBC's value type (@1- signed, @1+ unsigned)
+    fn is_signal_signed(&self, signal: &Signal) -> bool {
+        *signal.value_type() == ValueType::Signed
+    }
+
     fn extract_signal_value(
         &self,
         data: &[u8],
@@ -267,9 +820,7 @@ impl CanDecoder {
         } else {
             // Motorola format (big-endian)
             for i in 0..size {
-                let bit_pos = if start_bit >= i { start_bit - i } else { 0 };
-                let byte_index = bit_pos / 8;
-                let bit_index = 7 - (bit_pos % 8);
+                let (byte_index, bit_index) = Self::motorola_bit(start_bit, i);
 
                 if byte_index < data.len() {
                     let bit_value = (data[byte_index] & (1 << bit_index)) != 0;
@@ -299,3 +850,372 @@ impl CanDecoder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gui_modules::FaultSeverity;
+    use socketcan::StandardId;
+
+    const TEST_DBC: &str = r#"VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU
+
+BO_ 513 TEST_MSG: 8 ECU
+ SG_ Coolant_Level : 0|8@1+ (1,0) [0|100] "Percent" Vector__XXX
+ SG_ Unranged : 8|8@1+ (1,0) [0|0] "" Vector__XXX
+ SG_ On_Time : 16|16@1+ (0.1,0) [0|6553.5] "seconds" Vector__XXX
+ SG_ Trim : 32|8@1- (1,0) [0|0] "" Vector__XXX
+
+BO_ 1024 MUX_MSG: 8 ECU
+ SG_ Page M : 0|8@1+ (1,0) [0|255] "" Vector__XXX
+ SG_ Heatsink_Temp m0 : 8|8@1+ (1,0) [0|255] "C" Vector__XXX
+ SG_ Bus_Voltage m1 : 8|8@1+ (0.5,0) [0|127.5] "V" Vector__XXX
+ SG_ Counter : 56|8@1+ (1,0) [0|255] "" Vector__XXX
+
+BO_ 1536 FLOAT_MSG: 8 ECU
+ SG_ Cell_Temp : 0|32@1- (2,1) [0|0] "C" Vector__XXX
+ SG_ Raw_Word : 32|32@1+ (1,0) [0|0] "" Vector__XXX
+
+BO_ 1537 DOUBLE_MSG: 8 ECU
+ SG_ Energy : 0|64@1- (1,0) [0|0] "Wh" Vector__XXX
+
+BO_ 1538 MOTOROLA_MSG: 8 ECU
+ SG_ Phase_Angle : 11|12@0+ (1,0) [0|0] "" Vector__XXX
+ SG_ Offset_Word : 37|10@0- (1,0) [0|0] "" Vector__XXX
+
+SIG_VALTYPE_ 1536 Cell_Temp : 1;
+SIG_VALTYPE_ 1537 Energy : 2;
+"#;
+
+    fn decoded_signal<'a>(decoded: &'a DecodedMessage, signal_name: &str) -> &'a DecodedSignal {
+        decoded
+            .signals
+            .iter()
+            .find(|signal| signal.name == signal_name)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_out_of_range_decode() {
+        let decoder = CanDecoder::from_dbc_str(TEST_DBC).unwrap();
+        let frame =
+            CanFrame::new(StandardId::new(0x201).unwrap(), &[150, 7, 0, 0, 0, 0, 0, 0]).unwrap();
+
+        let decoded = decoder.decode(frame).unwrap();
+        assert_eq!(decoded.message_name, "MPPT1");
+        let level = decoded_signal(&decoded, "Coolant_Level").value;
+        assert_eq!(level, 150.0);
+        assert_eq!(
+            decoder.is_value_out_of_range(0x201, "Coolant_Level", level),
+            Some(true)
+        );
+        assert_eq!(
+            decoder.is_value_out_of_range(0x201, "Coolant_Level", 42.0),
+            Some(false)
+        );
+
+        // Placeholder [0|0] ranges are never treated as violations
+        let unranged = decoded_signal(&decoded, "Unranged").value;
+        assert_eq!(
+            decoder.is_value_out_of_range(0x201, "Unranged", unranged),
+            None
+        );
+    }
+
+    #[test]
+    fn test_signedness_follows_dbc_value_type() {
+        let decoder = CanDecoder::from_dbc_str(TEST_DBC).unwrap();
+        let frame = CanFrame::new(
+            StandardId::new(0x201).unwrap(),
+            &[0xFF, 0xFF, 0, 0, 0xFF, 0, 0, 0],
+        )
+        .unwrap();
+
+        // Same raw byte, @1+ vs @1- (Trim has no name or range hinting it's signed)
+        let decoded = decoder.decode(frame).unwrap();
+        assert_eq!(decoded_signal(&decoded, "Coolant_Level").raw, 255);
+        assert_eq!(decoded_signal(&decoded, "Trim").raw, -1);
+    }
+
+    #[test]
+    fn test_ieee_float_signals() {
+        let decoder = CanDecoder::from_dbc_str(TEST_DBC).unwrap();
+        let mut data = [0u8; 8];
+        data[..4].copy_from_slice(&(-12.25f32).to_bits().to_le_bytes());
+        data[4..].copy_from_slice(&(-12.25f32).to_bits().to_le_bytes());
+        let frame = CanFrame::new(StandardId::new(0x600).unwrap(), &data).unwrap();
+
+        // Float bits, then factor/offset; the integer signal beside it is unaffected
+        let decoded = decoder.decode(frame).unwrap();
+        assert_eq!(decoded_signal(&decoded, "Cell_Temp").value, -23.5);
+        assert_eq!(
+            decoded_signal(&decoded, "Raw_Word").value,
+            (-12.25f32).to_bits() as f64
+        );
+
+        let frame = CanFrame::new(
+            StandardId::new(0x601).unwrap(),
+            &1234.5678f64.to_bits().to_le_bytes(),
+        )
+        .unwrap();
+        let decoded = decoder.decode(frame).unwrap();
+        assert_eq!(decoded_signal(&decoded, "Energy").value, 1234.5678);
+
+        // Encoding writes the same bit patterns back
+        let encoded = decoder.encode(0x601, |_| Some(1234.5678)).unwrap();
+        assert_eq!(encoded.data(), frame.data());
+        let encoded = decoder.encode(0x600, |_| Some(-23.5)).unwrap();
+        assert_eq!(&encoded.data()[..4], &data[..4]);
+    }
+
+    #[test]
+    fn test_motorola_byte_aligned_signals() {
+        // BMS_State (0x330): Pack_SOC 7|8@0+, Adaptive_SOC 15|8@0+, Pack_Health 23|8@0+,
+        // Pack_DOD 31|8@0+ each fill one byte MSB-first
+        let decoder = CanDecoder::new("telemetry.dbc");
+        let frame = CanFrame::new(
+            StandardId::new(0x330).unwrap(),
+            &[0xB4, 0x01, 0x62, 0x80, 0, 0, 0, 0],
+        )
+        .unwrap();
+
+        let decoded = decoder.decode(frame).unwrap();
+        assert_eq!(decoded_signal(&decoded, "Pack_SOC").raw, 0xB4);
+        assert_eq!(decoded_signal(&decoded, "Adaptive_SOC").raw, 0x01);
+        assert_eq!(decoded_signal(&decoded, "Pack_Health").raw, 98);
+        assert_eq!(decoded_signal(&decoded, "Pack_DOD").raw, 0x80);
+    }
+
+    #[test]
+    fn test_motorola_signals_across_byte_boundaries() {
+        let decoder = CanDecoder::from_dbc_str(TEST_DBC).unwrap();
+        // Phase_Angle 11|12@0+: low nibble of byte 1, then all of byte 2.
+        // Offset_Word 37|10@0-: bits 5..0 of byte 4, then bits 7..4 of byte 5.
+        let data = [0, 0x0A, 0xBC, 0, 0x3F, 0xD0, 0, 0];
+        let frame = CanFrame::new(StandardId::new(0x602).unwrap(), &data).unwrap();
+
+        let decoded = decoder.decode(frame).unwrap();
+        assert_eq!(decoded_signal(&decoded, "Phase_Angle").raw, 0xABC);
+        assert_eq!(decoded_signal(&decoded, "Offset_Word").raw, -3);
+
+        let encoded = decoder
+            .encode(0x602, |signal| match signal {
+                "Phase_Angle" => Some(0xABC as f64),
+                _ => Some(-3.0),
+            })
+            .unwrap();
+        assert_eq!(encoded.data(), &data);
+    }
+
+    #[test]
+    #[ignore = "needs vcan0"]
+    fn test_can_writer_on_vcan0() {
+        // Needs `ip link add dev vcan0 type vcan && ip link set up vcan0`
+        let writer = CanWriter::open("vcan0").unwrap();
+        let reader = CanSocket::open("vcan0").unwrap();
+        reader
+            .set_read_timeout(std::time::Duration::from_secs(1))
+            .unwrap();
+
+        let frame = build_frame(0x0CF11E05, true, &[1, 2, 3]).unwrap();
+        writer.send(&frame).unwrap();
+        let received = reader.read_frame().unwrap();
+        assert_eq!(received.id(), frame.id());
+        assert_eq!(received.data(), &[1, 2, 3]);
+
+        assert!(build_frame(0x800, false, &[]).is_none());
+    }
+
+    #[test]
+    fn test_clamp_to_range() {
+        let mut decoder = CanDecoder::from_dbc_str(TEST_DBC).unwrap();
+        decoder.set_clamp_to_range(true);
+        let frame =
+            CanFrame::new(StandardId::new(0x201).unwrap(), &[150, 7, 0, 0, 0, 0, 0, 0]).unwrap();
+
+        let decoded = decoder.decode(frame).unwrap();
+        let level = decoded_signal(&decoded, "Coolant_Level");
+        assert_eq!((level.raw, level.value), (150, 100.0));
+        assert!(level.out_of_range);
+
+        // [0|0] means no range: left alone and not flagged
+        let unranged = decoded_signal(&decoded, "Unranged");
+        assert_eq!(unranged.value, 7.0);
+        assert!(!unranged.out_of_range);
+    }
+
+    #[test]
+    fn test_fractional_on_time_decode() {
+        let decoder = CanDecoder::from_dbc_str(TEST_DBC).unwrap();
+        // Raw 1234 at factor 0.1 -> 123.4 seconds
+        let frame = CanFrame::new(
+            StandardId::new(0x201).unwrap(),
+            &[0, 0, 0xD2, 0x04, 0, 0, 0, 0],
+        )
+        .unwrap();
+
+        let decoded = decoder.decode(frame).unwrap();
+        let on_time = decoded_signal(&decoded, "On_Time");
+        assert_eq!(on_time.raw, 1234);
+        assert!(on_time.label.is_none());
+        assert!((on_time.value - 123.4).abs() < 1e-9);
+
+        // A 2-byte frame can't hold On_Time (bits 16..32), so it is omitted, not decoded as 0
+        let short = CanFrame::new(StandardId::new(0x201).unwrap(), &[42, 0]).unwrap();
+        let decoded = decoder.decode(short).unwrap();
+        assert_eq!(decoded_signal(&decoded, "Coolant_Level").value, 42.0);
+        assert!(!decoded.signals.iter().any(|s| s.name == "On_Time"));
+        decoder.decode(short).unwrap();
+        assert_eq!(
+            *decoder.truncated_signals_logged.lock().unwrap(),
+            BTreeSet::from(["TEST_MSG.On_Time".to_string(), "TEST_MSG.Trim".to_string()]),
+            "each truncated signal is reported once"
+        );
+        assert_eq!(
+            decoded.to_string(),
+            "Coolant_Level: 42 Percent\nUnranged: 0"
+        );
+
+        let unit = decoder.signal_unit(0x201, "On_Time").unwrap();
+        assert_eq!(seconds_per_unit(unit), Some(1.0));
+        assert_eq!(seconds_per_unit("ds"), Some(0.1));
+    }
+
+    #[test]
+    fn test_unmapped_messages_still_decode() {
+        // 0x350 is in the DBC but has no GUI mapping
+        let decoder = CanDecoder::new("telemetry.dbc");
+        let frame = build_frame(0x350, false, &[0; 8]).unwrap();
+        let decoded = decoder.decode(frame).unwrap();
+        assert!(decoded.decoded);
+        assert_eq!(decoded.message_name, UNMAPPED_MESSAGE);
+        assert_eq!(decoded.export_name(), "MSGID_0X350");
+
+        let mapped = decoder
+            .decode(build_frame(0x320, false, &[0; 8]).unwrap())
+            .unwrap();
+        assert_eq!(mapped.export_name(), "BMS_Power");
+        assert!(!DecodedMessage::unknown().decoded);
+    }
+
+    #[test]
+    fn test_labelled_signal_keeps_raw() {
+        // MC_ERR6 has the value descriptions 0 "OK" / 1 "Over temperature"
+        let decoder = CanDecoder::new("telemetry.dbc");
+        let frame = build_frame(0x0CF11E05, true, &[0, 0, 0, 0, 0, 0, 0x40, 0]).unwrap();
+        let decoded = decoder.decode(frame).unwrap();
+        let error = decoded_signal(&decoded, "MC_ERR6");
+        assert_eq!(error.raw, 1);
+        assert_eq!(error.display_value(), "Over temperature");
+        assert!(decoded
+            .to_string()
+            .lines()
+            .any(|line| line == "MC_ERR6: 1 (Over temperature)"));
+    }
+
+    #[test]
+    fn test_multiplexed_decode() {
+        let decoder = CanDecoder::from_dbc_str(TEST_DBC).unwrap();
+        let decode_page = |data: &[u8]| {
+            let frame = CanFrame::new(StandardId::new(0x400).unwrap(), data).unwrap();
+            let decoded = decoder.decode(frame).unwrap();
+            let names: Vec<String> = decoded.signals.iter().map(|s| s.name.clone()).collect();
+            (decoded, names)
+        };
+
+        // Page 0 carries the heatsink temperature in byte 1
+        let (decoded, page0) = decode_page(&[0, 80, 0, 0, 0, 0, 0, 9]);
+        assert_eq!(page0, ["Page", "Heatsink_Temp", "Counter"]);
+        assert_eq!(decoded_signal(&decoded, "Heatsink_Temp").value, 80.0);
+
+        // Page 1 reuses byte 1 for the bus voltage
+        let (decoded, page1) = decode_page(&[1, 200, 0, 0, 0, 0, 0, 9]);
+        assert_eq!(page1, ["Page", "Bus_Voltage", "Counter"]);
+        assert_eq!(decoded_signal(&decoded, "Bus_Voltage").value, 100.0);
+
+        // Unknown pages decode only the switch and the plain signals
+        let (_, page7) = decode_page(&[7, 200, 0, 0, 0, 0, 0, 9]);
+        assert_eq!(page7, ["Page", "Counter"]);
+    }
+
+    #[test]
+    fn test_receive_ids_cover_dbc_and_dtc() {
+        let decoder = CanDecoder::new("telemetry.dbc");
+        let ids = decoder.receive_ids();
+
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(ids.contains(&0x300));
+        assert!(ids.contains(&0x776));
+        // Extended DBC IDs are reported without the 0x80000000 flag
+        assert!(ids.contains(&0x0CF11E05));
+        assert!(ids.iter().all(|&id| id <= 0x1FFF_FFFF));
+
+        // The DTC message is received even when the DBC doesn't define it
+        let no_dtc = CanDecoder::from_dbc_str(TEST_DBC).unwrap();
+        assert!(no_dtc.receive_ids().contains(&0x300));
+    }
+
+    #[test]
+    fn test_bit_faults_decode() {
+        // Built-in BMS DTC words: Pack Too Hot (DTC1 0x0080) and Weak Cell (DTC2 0x0004)
+        let decoder = CanDecoder::new("telemetry.dbc");
+        let frame = build_frame(BMS_DTC_ID, false, &[0x80, 0x00, 0x04, 0x00]).unwrap();
+        let names: Vec<String> = decoder
+            .decode(frame)
+            .unwrap()
+            .signals
+            .into_iter()
+            .map(|signal| signal.name)
+            .collect();
+        assert_eq!(names, ["Fault_DTC1_Pack Too Hot", "Fault_DTC2_Weak Cell"]);
+
+        // A configured word adds its flags and keeps the DBC signals it overlaps
+        let mut decoder = CanDecoder::from_dbc_str(TEST_DBC).unwrap();
+        decoder.set_bit_faults(vec![BitFault {
+            id: 0x201,
+            byte: 1,
+            mask: 0x02,
+            prefix: "TEST_ERR".to_string(),
+            name: "Low Coolant".to_string(),
+            severity: FaultSeverity::Warning,
+            replaces: None,
+        }]);
+        let frame = build_frame(0x201, false, &[40, 0x02, 0, 0, 0, 0, 0, 0]).unwrap();
+        let decoded = decoder.decode(frame).unwrap();
+        assert_eq!(decoded_signal(&decoded, "Coolant_Level").value, 40.0);
+        assert_eq!(decoded_signal(&decoded, "Unranged").value, 2.0);
+        assert_eq!(
+            decoded_signal(&decoded, "TEST_ERR_Low Coolant")
+                .label
+                .as_deref(),
+            Some("Low Coolant")
+        );
+        assert_eq!(decoder.receive_ids(), [0x201, 0x400, 0x600, 0x601, 0x602]);
+    }
+
+    #[test]
+    fn test_reload_keeps_old_dbc_on_error() {
+        let path = std::env::temp_dir().join(format!("reload_test_{}.dbc", std::process::id()));
+        fs::write(&path, TEST_DBC).unwrap();
+        let mut decoder = CanDecoder::new(path.to_str().unwrap());
+
+        fs::write(&path, "BO_ not a dbc").unwrap();
+        assert!(decoder.reload().is_err());
+        assert!(decoder.signal_unit(0x201, "On_Time").is_some());
+
+        fs::write(&path, TEST_DBC.replace("\"seconds\"", "\"ds\"")).unwrap();
+        decoder.reload().unwrap();
+        assert_eq!(decoder.signal_unit(0x201, "On_Time"), Some("ds"));
+
+        let _ = fs::remove_file(&path);
+
+        // In-memory decoders have no file to reload from
+        let mut in_memory = CanDecoder::from_dbc_str(TEST_DBC).unwrap();
+        assert!(in_memory.reload().is_err());
+    }
+}
@@ -0,0 +1,330 @@
+// Runtime configuration assembled from the command line and passed to the GUI as iced flags
+use crate::can::DEFAULT_CAN_BITRATE;
+use crate::gui_modules::FaultPalette;
+use crate::influx::InfluxConfig;
+use crate::logger::LogFormat;
+use crate::mqtt::DEFAULT_MQTT_PORT;
+use crate::serial::{
+    BatchConfig, ChecksumMode, OverflowPolicy, DEFAULT_PRESERVED_PER_ID, DEFAULT_RFD_BAUD_RATE,
+};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+pub const DEFAULT_FULLSCREEN_DELAY_MS: u64 = 500;
+pub const DEFAULT_LOW_DISK_MB: u64 = 500;
+pub const DEFAULT_CAN_STALE_MS: u64 = 2000;
+pub const DEFAULT_CAN_RETRY_MS: u64 = 1000;
+pub const DEFAULT_CAN_ONLINE_FRAMES: u32 = 3;
+pub const DEFAULT_WHEEL_DIAMETER_IN: f64 = 23.5;
+pub const DEFAULT_CURRENT_WARN_A: f64 = 70.0;
+pub const DEFAULT_DATA_STALE_MS: u64 = 2000;
+pub const DEFAULT_FAULT_CYCLE_MS: u64 = 2000;
+pub const DEFAULT_FAULTS_PER_PAGE: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    pub rfd_enabled: bool,
+
+    // Serial baud rate of the RFD modem (57600 unless the radio was reconfigured)
+    pub rfd_baud_rate: u32,
+
+    // Serial port of the RFD (--rfd-port); skips scanning, which can grab the wrong USB
+    // device. With rfd_auto_scan off and no port, the RFD never connects.
+    pub rfd_port: Option<String>,
+    pub rfd_auto_scan: bool,
+
+    // Scan every serial port for modems, not only known FTDI/SiLabs/CH340 USB adapters
+    pub scan_all_ports: bool,
+
+    // Also run a LoRa modem as a backup link, transmitting alongside the RFD
+    pub lora_enabled: bool,
+
+    // Routine-lane batch limits for the RF links (--batch-size/--batch-bytes/--batch-timeout-ms,
+    // and --batch-timeout-max-ms for how far the timeout stretches on a failing link)
+    pub batch_config: BatchConfig,
+    // What a lane sheds when the radio can't keep up and its batch stays full (--overflow-policy)
+    pub overflow_policy: OverflowPolicy,
+
+    // Frame and batch checksum width on the RF links (--crc16 trades integrity for bandwidth)
+    pub checksum_mode: ChecksumMode,
+
+    // Transmit CAN frames requested by the base station over the RF link onto can0
+    // (--allow-can-inject); off by default so the link can't write to the car's bus
+    pub can_inject: bool,
+
+    // CAN IDs accepted at the socket via kernel filters; None filters to the DBC's IDs (plus
+    // known_unknown_ids) unless can_promiscuous is set. Frames outside the allowlist never
+    // reach userspace, so they are not logged either.
+    pub can_id_allowlist: Option<Vec<u32>>,
+
+    // Receive every frame on the bus (--promiscuous), e.g. to find IDs missing from the DBC
+    pub can_promiscuous: bool,
+
+    // CAN IDs forwarded over RFD; None forwards everything received. Unlisted frames are
+    // still displayed and logged, they just never reach the batcher.
+    pub tx_id_allowlist: Option<Vec<u32>>,
+
+    // Non-DBC IDs expected on the bus; they pass through without being reported as undecoded
+    pub known_unknown_ids: Vec<u32>,
+
+    // Nominal CAN bitrate used for bus load and latency estimates
+    pub can_bitrate: u32,
+
+    // Re-open the CAN socket after this long without frames (e.g. an `ip link` flap),
+    // retrying every can_retry_ms while the interface can't be opened
+    pub can_stale_ms: u64,
+    pub can_retry_ms: u64,
+
+    // Consecutive frames needed before the indicator goes back to ONLINE. Going OFFLINE
+    // already requires the bus to be quiet for the full can_stale_ms window.
+    pub can_online_frames: u32,
+
+    // Battery, MPPT and BPS values not updated for this long are grayed out as stale
+    pub data_stale_ms: u64,
+
+    // Wheel diameter used to convert motor RPM to road speed (and distance)
+    pub wheel_diameter_inches: f64,
+
+    // Pack current above which the dashboard shows the slow-down warning, and above which
+    // it turns critical (--current-warn/--current-critical; None = no critical level)
+    pub current_warn_amps: f64,
+    pub current_critical_amps: Option<f64>,
+
+    // Beep once when each critical fault is raised (--no-audio turns it off)
+    pub audio_alarm: bool,
+
+    // Raise warnings when configured signals decode outside their DBC [min|max]
+    pub range_faults_enabled: bool,
+
+    // Clip decoded values into their DBC [min|max] (value-table signals are left alone)
+    pub clamp_signals: bool,
+
+    // Start windowed (never go fullscreen), otherwise go fullscreen after the delay so the
+    // window manager can place the window on the right monitor first
+    pub windowed: bool,
+    pub fullscreen_delay_ms: u64,
+
+    // Local-time hour window [start, end) in which the display dims automatically (may wrap midnight)
+    pub auto_dim_hours: Option<(u32, u32)>,
+
+    // GUI value and fault signal mappings (--mappings); None reads signal_mappings.toml
+    // if it exists, otherwise the built-in mappings are used
+    pub mappings_file: Option<PathBuf>,
+
+    // Per-severity fault colors (--palette default|colorblind)
+    pub fault_palette: FaultPalette,
+
+    // Faults shown per fault panel page, and how long each page shows when there are more
+    pub faults_per_page: usize,
+    pub fault_cycle_ms: u64,
+
+    // Keep at most this many log files (None = only the 10GB size cap applies)
+    pub max_log_files: Option<usize>,
+
+    // Line format of the CAN log (--log-format custom|candump|csv)
+    pub log_format: LogFormat,
+
+    // Directory for CAN logs and session summaries (None = working directory)
+    pub log_dir: Option<PathBuf>,
+
+    // Start a new log file every N minutes (None = only the size limit rotates)
+    pub log_rotate_minutes: Option<u64>,
+
+    // Simulated RFD link impairments for resilience testing (fractions 0.0-1.0)
+    pub sim_loss: f64,
+    pub sim_corrupt: f64,
+    pub sim_seed: u64,
+
+    // CAN IDs promoted to Critical transmit priority (express lane, sent first in batches)
+    pub critical_ids: Vec<u32>,
+    // CAN IDs whose every frame is sent over RF, rather than only the latest per batch
+    pub preserve_ids: Vec<u32>,
+    // Frames of each preserved ID queued at once before the oldest is dropped
+    pub preserve_depth: usize,
+
+    // Log only every Nth frame of these IDs (disk only; RF transmission is unaffected)
+    pub log_decimation: HashMap<u32, u32>,
+
+    // Warn when the log directory has less than this much free space (Critical at a tenth of it)
+    pub low_disk_mb: u64,
+
+    // Serve live telemetry JSON to WebSocket clients on this port (None = server off)
+    pub ws_port: Option<u16>,
+
+    // Publish decoded signals to this MQTT broker (None = publisher off)
+    pub mqtt_host: Option<String>,
+    pub mqtt_port: u16,
+
+    // Batch decoded frames into this InfluxDB database (None = sink off)
+    pub influx: Option<InfluxConfig>,
+
+    // Drive the GUI from this CAN log instead of can0, scaled by replay_speed (2.0 = twice as fast)
+    pub replay_log: Option<String>,
+    pub replay_speed: f64,
+
+    // Drive the GUI from simulated traffic for every known DBC message (no CAN hardware needed)
+    pub simulate: bool,
+
+    // Send every received frame to this (broadcast) address, and/or drive the GUI from such a
+    // feed on this UDP port instead of can0
+    pub udp_broadcast: Option<SocketAddr>,
+    pub udp_listen_port: Option<u16>,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            rfd_enabled: true,
+            rfd_baud_rate: DEFAULT_RFD_BAUD_RATE,
+            rfd_port: None,
+            rfd_auto_scan: true,
+            scan_all_ports: false,
+            lora_enabled: false,
+            batch_config: BatchConfig::default(),
+            overflow_policy: OverflowPolicy::default(),
+            checksum_mode: ChecksumMode::default(),
+            can_inject: false,
+            can_id_allowlist: None,
+            can_promiscuous: false,
+            tx_id_allowlist: None,
+            known_unknown_ids: Vec::new(),
+            can_bitrate: DEFAULT_CAN_BITRATE,
+            can_stale_ms: DEFAULT_CAN_STALE_MS,
+            can_retry_ms: DEFAULT_CAN_RETRY_MS,
+            can_online_frames: DEFAULT_CAN_ONLINE_FRAMES,
+            data_stale_ms: DEFAULT_DATA_STALE_MS,
+            wheel_diameter_inches: DEFAULT_WHEEL_DIAMETER_IN,
+            current_warn_amps: DEFAULT_CURRENT_WARN_A,
+            current_critical_amps: None,
+            audio_alarm: true,
+            range_faults_enabled: false,
+            clamp_signals: false,
+            windowed: false,
+            fullscreen_delay_ms: DEFAULT_FULLSCREEN_DELAY_MS,
+            auto_dim_hours: None,
+            mappings_file: None,
+            fault_palette: FaultPalette::default(),
+            faults_per_page: DEFAULT_FAULTS_PER_PAGE,
+            fault_cycle_ms: DEFAULT_FAULT_CYCLE_MS,
+            max_log_files: None,
+            log_format: LogFormat::default(),
+            log_dir: None,
+            log_rotate_minutes: None,
+            sim_loss: 0.0,
+            sim_corrupt: 0.0,
+            sim_seed: 0,
+            critical_ids: Vec::new(),
+            preserve_ids: Vec::new(),
+            preserve_depth: DEFAULT_PRESERVED_PER_ID,
+            log_decimation: HashMap::new(),
+            low_disk_mb: DEFAULT_LOW_DISK_MB,
+            ws_port: None,
+            mqtt_host: None,
+            mqtt_port: DEFAULT_MQTT_PORT,
+            influx: None,
+            replay_log: None,
+            replay_speed: 1.0,
+            simulate: false,
+            udp_broadcast: None,
+            udp_listen_port: None,
+        }
+    }
+}
+
+// Parse a single CAN ID written as hex (0x300) or decimal (768)
+pub fn parse_can_id(value: &str) -> Result<u32, String> {
+    let trimmed = value.trim();
+    let parsed = match trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+    {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => trimmed.parse::<u32>(),
+    };
+
+    match parsed {
+        Ok(id) if id <= 0x1FFF_FFFF => Ok(id),
+        Ok(id) => Err(format!("CAN ID 0x{:X} exceeds 29 bits", id)),
+        Err(_) => Err(format!("invalid CAN ID '{}'", trimmed)),
+    }
+}
+
+// Parse a comma-separated list of CAN IDs, e.g. "0x300,0x776,0x0CF11E05"
+pub fn parse_can_id_list(value: &str) -> Result<Vec<u32>, String> {
+    value
+        .split(',')
+        .filter(|part| !part.trim().is_empty())
+        .map(parse_can_id)
+        .collect()
+}
+
+// Parse a wheel diameter in inches (e.g. "23.5")
+pub fn parse_wheel_diameter(value: &str) -> Result<f64, String> {
+    match value.trim().parse::<f64>() {
+        Ok(inches) if inches > 0.0 && inches.is_finite() => Ok(inches),
+        _ => Err(format!("invalid wheel diameter '{}'", value.trim())),
+    }
+}
+
+// Parse a battery current threshold in amps (e.g. "70")
+pub fn parse_current_limit(value: &str) -> Result<f64, String> {
+    match value.trim().trim_end_matches('A').parse::<f64>() {
+        Ok(amps) if amps > 0.0 && amps.is_finite() => Ok(amps),
+        _ => Err(format!("invalid current threshold '{}'", value.trim())),
+    }
+}
+
+// Parse a replay speed multiplier (e.g. "1", "0.5", "10")
+pub fn parse_replay_speed(value: &str) -> Result<f64, String> {
+    match value.trim().trim_end_matches('x').parse::<f64>() {
+        Ok(speed) if speed > 0.0 && speed.is_finite() => Ok(speed),
+        _ => Err(format!("invalid replay speed '{}'", value.trim())),
+    }
+}
+
+// Parse a percentage (e.g. "5" or "2.5") into a 0.0-1.0 fraction
+pub fn parse_percent(value: &str) -> Result<f64, String> {
+    match value.trim().trim_end_matches('%').parse::<f64>() {
+        Ok(pct) if (0.0..=100.0).contains(&pct) => Ok(pct / 100.0),
+        Ok(pct) => Err(format!("percentage {} is outside 0-100", pct)),
+        Err(_) => Err(format!("invalid percentage '{}'", value.trim())),
+    }
+}
+
+// Parse an hour window "START-END" in 24h local time, e.g. "20-6" for 8pm to 6am
+pub fn parse_hour_window(value: &str) -> Result<(u32, u32), String> {
+    let parse_hour = |hour: &str| match hour.trim().parse::<u32>() {
+        Ok(h) if h < 24 => Ok(h),
+        _ => Err(format!("invalid hour '{}' (expected 0-23)", hour.trim())),
+    };
+
+    let (start, end) = value
+        .split_once('-')
+        .ok_or_else(|| format!("expected START-END, got '{}'", value.trim()))?;
+    let window = (parse_hour(start)?, parse_hour(end)?);
+    if window.0 == window.1 {
+        return Err("hour window is empty (start equals end)".to_string());
+    }
+    Ok(window)
+}
+
+// Parse "ID=N" pairs, e.g. "0x0CF11E05=4,0x320=2"
+pub fn parse_decimation_list(value: &str) -> Result<HashMap<u32, u32>, String> {
+    value
+        .split(',')
+        .filter(|part| !part.trim().is_empty())
+        .map(|part| {
+            let (id, every_nth) = part
+                .split_once('=')
+                .ok_or_else(|| format!("expected ID=N, got '{}'", part.trim()))?;
+            let every_nth = every_nth
+                .trim()
+                .parse::<u32>()
+                .ok()
+                .filter(|n| *n > 0)
+                .ok_or_else(|| format!("invalid decimation factor '{}'", every_nth.trim()))?;
+            Ok((parse_can_id(id)?, every_nth))
+        })
+        .collect()
+}
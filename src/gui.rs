@@ -1,20 +1,134 @@
 // Optimized src/gui.rs file with enhanced batching integration
 
-use crate::can::CanDecoder;
-use crate::logger::CanLogger;
-use crate::serial::SerialManager;
-use chrono::Local;
+use crate::alarm::sound_alarm;
+use crate::busload::{BusLoadMeter, BUS_LOAD_WINDOW};
+use crate::can::{
+    build_frame, build_id_filters, detect_can_bitrate, frame_time_us, seconds_per_unit, CanDecoder,
+    CanWriter, DecodedMessage, DecodedSignal,
+};
+use crate::config::AppConfig;
+use crate::health::{now_ms, Heartbeat, RateTracker};
+use crate::influx::InfluxWriter;
+use crate::logger::{free_disk_bytes, write_screenshot, CanLogger, FaultEvent, FaultLogger};
+use crate::mqtt::MqttPublisher;
+use crate::odometer::{Odometer, ODOMETER_STATE_FILE};
+use crate::replay::LogReplay;
+use crate::serial::{CanFrameData, LinkSimulator, MessagePriority, SerialError, SerialManager};
+use crate::session::{EnergyMeter, LinkSummary, SessionStats};
+use crate::simulate::FrameSimulator;
+use crate::udp::{UdpBroadcaster, UdpReceiver};
+use crate::ws::{severity_name, start_ws_server, TelemetryBroadcaster, WsEvent, WsFault};
+use chrono::{Local, Timelike};
+use iced::keyboard::{self, Key};
+use iced::{event, window};
 use iced::{subscription, time, Application, Command, Element, Subscription, Theme};
-use socketcan::{CanFrame, CanSocket, EmbeddedFrame, Socket, StandardId};
-use std::collections::HashMap;
+use log::{debug, error, info, warn};
+use socketcan::{CanSocket, EmbeddedFrame, Socket, SocketOptions};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use crate::gui_modules::*;
 use crate::gui_modules::get_fault_severity;
-use rand;
+
+const CAN_INTERFACE: &str = "can0";
+const BPS_THING_ID: u32 = 0x777;
+// Per-motor speeds further apart than this are highlighted (possible slip or sensor fault)
+const MOTOR_DIVERGENCE_MPH: f64 = 2.0;
+// Below this speed Wh/mile swings wildly (and is infinite at a standstill), so it isn't shown
+const MIN_EFFICIENCY_SPEED_MPH: f64 = 2.0;
+// Motor speed older than this is not used for speed (short, so a dropped motor reads 0 quickly)
+const MOTOR_DATA_MAX_AGE: Duration = Duration::from_millis(250);
+
+// How long each subsystem may go without a heartbeat before it is reported as STALLED
+const CAN_STALL_WINDOW: Duration = Duration::from_millis(2000);
+const SCAN_STALL_WINDOW: Duration = Duration::from_millis(15000); // Port probing can block for a while
+const BATCH_STALL_WINDOW: Duration = Duration::from_millis(1000);
+// CAN IDs not seen for this long are highlighted on the message rate page
+const MESSAGE_STALE_AFTER: Duration = Duration::from_secs(2);
+
+const TICK_INTERVAL: Duration = Duration::from_millis(100);
+const DISK_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+const WS_STATUS_INTERVAL: Duration = Duration::from_secs(1);
+const LOW_DISK_FAULT_KEY: &str = "Logger_LowDisk";
+const DBC_RELOAD_FAULT_KEY: &str = "Decoder_DbcReload";
+
+// Set by the Ctrl-C handler (see main.rs); the next tick closes the window like the close button
+pub static SHUTDOWN_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+// State threaded through the CAN subscription between frames
+struct CanSubscriptionState {
+    decoder: Arc<Mutex<CanDecoder>>,
+    heartbeat: Heartbeat,
+    frames_received: Arc<AtomicU64>,
+    id_allowlist: Option<Vec<u32>>,
+
+    // Kept open across frames; dropped and re-opened when the bus goes quiet or errors
+    socket: Option<CanSocket>,
+    last_frame: Instant,
+    stale_after: Duration,
+    retry_interval: Duration,
+}
+
+// Frame sources that stand in for can0 when there's no bus to read
+#[derive(Clone)]
+enum OfflineSource {
+    // --replay: a recorded log at `speed` times the recorded timing
+    Replay { path: String, speed: f64 },
+    // --simulate: generated traffic for every known DBC message
+    Simulate,
+    // --udp-listen: live frames from another instance's --udp-broadcast
+    Udp { port: u16 },
+}
+
+// An opened OfflineSource
+enum FrameFeed {
+    Replay(LogReplay),
+    Simulated(FrameSimulator),
+    Udp(UdpReceiver),
+}
+
+// State threaded through the offline subscription in place of the CAN socket
+struct OfflineState {
+    decoder: Arc<Mutex<CanDecoder>>,
+    heartbeat: Heartbeat,
+    frames_received: Arc<AtomicU64>,
+    id_allowlist: Option<Vec<u32>>,
+
+    // Opened on the first poll; feed is None once it ends (or fails to open)
+    pending: Option<OfflineSource>,
+    feed: Option<FrameFeed>,
+    started: Instant,
+}
+
+// Everything the dashboard shows from live values, captured when the display is paused
+struct DashboardSnapshot {
+    battery: BatteryData,
+    bps: BpsData,
+    mppt: MpptData,
+    mppt1_stale: bool,
+    mppt2_stale: bool,
+    display_units: DisplayUnits,
+    direction: String,
+    speed_mph: f64,
+    session_miles: f64,
+    trip_peak_mph: f64,
+    motor_detail: MotorSpeedDetail,
+    power: PowerData,
+}
 
 pub struct TelemetryGui {
     // CAN status
     can_connected: bool,
+    can_reconnecting: bool, // Was online, socket is being re-opened
+    // Hysteresis so a marginal bus doesn't strobe the indicator: frames seen since the
+    // link was last lost, and how many are needed to show ONLINE
+    can_frames_since_loss: u32,
+    can_bus_load: BusLoadMeter, // Estimated from the frames received over the last second
+    can_online_frames: u32,
+    can_stale_after: Duration,
+    can_retry_interval: Duration,
 
     // Motor data
     motor1_speed_rpm: f64,
@@ -25,11 +139,13 @@ pub struct TelemetryGui {
     speed_mph: f64, // This becomes the calculated result
     direction: String,
 
-    motor1_last_update: Option<std::time::Instant>,
-    motor2_last_update: Option<std::time::Instant>,
+    // When each group of values was last updated, and when displayed groups count as stale
+    data_last_update: HashMap<DataGroup, Instant>,
+    data_stale_after: Duration,
 
     // MPPT data
     mppt_data: MpptData,
+    display_units: DisplayUnits, // DBC units of the values above, as last decoded
 
     // Battery data
     battery_voltage: f64,
@@ -41,107 +157,432 @@ pub struct TelemetryGui {
 
     // BPS data
     bps_state: String,
-    bps_ontime: u64,
+    bps_ontime: f64,       // Seconds, converted from whatever unit the DBC declares
+    bps_ontime_scale: f64, // Seconds per decoded BPS_ON_Time unit
 
     // UI state
     fullscreen: bool,
+    show_diagnostics: bool,
+    show_message_rates: bool,
+    show_motor_detail: bool,
+    // Set while paused: the dashboard shows this instead of the values, which keep updating
+    frozen: Option<DashboardSnapshot>,
+    speed_unit: SpeedUnit,
+    dimmed: bool,
+    // Auto-dim hour window; the last evaluated state makes it edge-triggered so a manual
+    // toggle holds until the window next opens or closes
+    auto_dim_hours: Option<(u32, u32)>,
+    auto_dim_active: Option<bool>,
     current_time: String,
 
-    // Fault tracking
+    // Fault tracking; acknowledged faults stay latched but off the fault panel
     active_faults: HashMap<String, Fault>,
+    acknowledged_faults: HashMap<String, Fault>,
 
     // Fault cycling state
     fault_page_index: usize,   // Current fault page (0-based)
     fault_cycle_timer: u32,    // Timer for cycling (increments every update)
-    fault_cycle_interval: u32, // Number of ticks between cycles (3 seconds = 15 ticks at 200ms)
+    fault_cycle_interval: u32, // Number of ticks between cycles (--fault-cycle-ms / TICK_INTERVAL)
+    faults_per_page: usize,
 
     // System components
-    decoder: CanDecoder,
+    decoder: Arc<Mutex<CanDecoder>>, // Shared with the CAN subscription so reloads apply there
     logger: Option<CanLogger>,
-    _theme: Theme,
+    fault_logger: Option<FaultLogger>,
+    theme: Theme, // Dark or Light; the night dim mode overrides it
+    // Severity colors of the fault panel (--palette)
+    fault_palette: FaultPalette,
     serial_manager: SerialManager,
 
     // Radio status
     rfd_connected: bool,
+    lora_connected: bool,
+
+    // Subsystem liveness (CAN subscription bumps this on every frame read)
+    can_heartbeat: Heartbeat,
+
+    // Frames read off the bus by the CAN subscription (compared against frames sent over RF)
+    can_frames_received: Arc<AtomicU64>,
+
+    // Frames arriving over the RFD link other than probe echoes and inject requests; they are
+    // only counted, for the receive-side loss estimate next to them
+    rf_receiver: Option<mpsc::Receiver<CanFrameData>>,
+    rf_frames_received: u64,
+
+    // Free-space monitoring of the log directory
+    low_disk_bytes: u64,
+    last_disk_check: Option<std::time::Instant>,
+
+    // Distance integrated from speed (lifetime total persisted across restarts)
+    odometer: Odometer,
+
+    // Peaks, energy and fault counts for the end-of-session summary
+    session: SessionStats,
+    energy: EnergyMeter, // Dashboard energy counter (ResetEnergy zeroes it)
+    trip_peak_mph: f64,  // Since the last ResetTrip (the session summary keeps its own peak)
+
+    // Live JSON feed for WebSocket clients (--ws-port)
+    ws: Option<TelemetryBroadcaster>,
+    last_ws_status: Option<Instant>,
+
+    // Per-signal MQTT topics for strategy tooling (--mqtt-host)
+    mqtt: Option<MqttPublisher>,
+
+    // Raw frame feed for other instances on the LAN (--udp-broadcast), flushed every tick
+    udp: Option<UdpBroadcaster>,
+
+    // Batched line-protocol writes for long-term storage (--influx-url)
+    influx: Option<InfluxWriter>,
 
     // Enable/disable flags
     rfd_enabled: bool,
+    lora_enabled: bool,
+
+    // Kernel-level CAN ID allowlist (None = receive all frames, only with --promiscuous)
+    can_id_allowlist: Option<Vec<u32>>,
+
+    // Take frames from a log replay or the simulator instead of can0
+    offline_source: Option<OfflineSource>,
+
+    // IDs allowed onto the RFD link (None = transmit every frame)
+    tx_id_allowlist: Option<Vec<u32>>,
+
+    // Undecodable IDs: acknowledged by the operator, and everything else seen this session
+    known_unknown_ids: Vec<u32>,
+    undecoded_ids: BTreeSet<u32>,
+    // Receive rate of every CAN ID seen this session, for the message rate page
+    message_rates: HashMap<u32, RateTracker>,
+
+    // Converts motor RPM to road speed (--wheel-diameter)
+    wheel_diameter_inches: f64,
+
+    // Battery current warning levels (--current-warn/--current-critical)
+    current_warn_amps: f64,
+    current_critical_amps: Option<f64>,
+
+    // Critical faults that have already sounded the alarm (forgotten when they clear)
+    audio_alarm: bool,
+    alarmed_faults: HashSet<String>,
+
+    // CAN bitrate: configured nominal value and what the interface reports (if readable)
+    can_bitrate: u32,
+    detected_can_bitrate: Option<u32>,
 
     // Configuration mappings
-    gui_value_mappings: HashMap<(&'static str, &'static str), Vec<GuiValueType>>,
-    fault_signal_config: HashMap<&'static str, Vec<&'static str>>,
+    gui_value_mappings: GuiValueMappings,
+    fault_signal_config: FaultSignalConfig,
+    bit_fault_signals: HashMap<u32, HashMap<String, FaultSeverity>>, // CAN ID -> flag signals
+    range_check_config: RangeCheckConfig,
+    range_faults_enabled: bool,
 }
 
 impl Application for TelemetryGui {
     type Executor = iced::executor::Default;
     type Message = Message;
     type Theme = iced::Theme;
-    type Flags = bool; // rfd_enabled
+    type Flags = AppConfig;
 
     fn theme(&self) -> Self::Theme {
-        iced::Theme::Dark
+        if self.dimmed {
+            dimmed_theme()
+        } else {
+            self.theme.clone()
+        }
     }
 
     fn new(flags: Self::Flags) -> (Self, Command<Message>) {
-        let rfd_enabled = flags;
+        let rfd_enabled = flags.rfd_enabled;
 
         // Create enhanced serial manager with improved batching
-        let serial_manager = Self::create_enhanced_serial_manager(rfd_enabled);
+        let mut serial_manager = Self::create_enhanced_serial_manager(
+            rfd_enabled,
+            flags.rfd_baud_rate,
+            flags.lora_enabled,
+            flags.rfd_port.as_deref(),
+            flags.rfd_auto_scan,
+            flags.scan_all_ports,
+        );
+        if let Err(e) = serial_manager.set_batch_config(flags.batch_config) {
+            warn!("Failed to apply batch settings: {}", e);
+        }
+        serial_manager.set_overflow_policy(flags.overflow_policy);
+        serial_manager.set_checksum_mode(flags.checksum_mode);
+        serial_manager.set_preserve_ids(
+            flags.preserve_ids.iter().copied().collect(),
+            flags.preserve_depth,
+        );
+        serial_manager.set_priority_overrides(
+            flags
+                .critical_ids
+                .iter()
+                .map(|id| (*id, MessagePriority::Critical))
+                .collect(),
+        );
+        if flags.sim_loss > 0.0 || flags.sim_corrupt > 0.0 {
+            serial_manager.set_link_simulator(Some(LinkSimulator::new(
+                flags.sim_loss,
+                flags.sim_corrupt,
+                flags.sim_seed,
+            )));
+        }
+        if flags.can_inject {
+            Self::start_can_injector(&serial_manager);
+        }
+        let rf_receiver = serial_manager
+            .start_receiving()
+            .map_err(|e| warn!("Failed to start RFD receive thread: {}", e))
+            .ok();
+
+        // Compare the configured bitrate against what the interface is actually running
+        let detected_can_bitrate = detect_can_bitrate(CAN_INTERFACE);
+        if let Some(actual) = detected_can_bitrate {
+            if actual != flags.can_bitrate {
+                warn!(
+                    "{} reports {} bit/s but --can-bitrate is {} bit/s",
+                    CAN_INTERFACE, actual, flags.can_bitrate
+                );
+            }
+        }
+
+        // BPS on-time is displayed in seconds; honour the DBC's unit if it uses another time base
+        let mut decoder = CanDecoder::new("telemetry.dbc");
+        decoder.set_clamp_to_range(flags.clamp_signals);
+        let bps_ontime_scale = Self::bps_ontime_scale(&decoder);
+        let signal_mappings = SignalMappings::load_or_builtin(flags.mappings_file.as_deref());
+        decoder.set_bit_faults(signal_mappings.bit_faults.clone());
+        let mut bit_fault_signals: HashMap<u32, HashMap<String, FaultSeverity>> = HashMap::new();
+        for fault in &signal_mappings.bit_faults {
+            bit_fault_signals
+                .entry(fault.id)
+                .or_default()
+                .insert(fault.signal_name(), fault.severity);
+        }
+
+        // Without an explicit --can-ids, let the kernel drop IDs nothing would decode. The
+        // filter is fixed at startup, so messages added by a DBC reload need a restart.
+        let can_id_allowlist = flags.can_id_allowlist.clone().or_else(|| {
+            if flags.can_promiscuous {
+                return None;
+            }
+            let mut ids = decoder.receive_ids();
+            ids.extend(&flags.known_unknown_ids);
+            info!(
+                "CAN socket filter: {} DBC IDs (--promiscuous receives all)",
+                ids.len()
+            );
+            Some(ids)
+        });
 
         // Initialize logger
-        let logger = match CanLogger::new() {
+        let logger = match &flags.log_dir {
+            Some(dir) => CanLogger::with_dir(dir.clone(), flags.max_log_files, flags.log_format),
+            None => CanLogger::new(flags.max_log_files, flags.log_format),
+        };
+        let logger = logger.map(|logger| {
+            let logger = logger
+                .with_interface(CAN_INTERFACE)
+                .with_decimation(flags.log_decimation.clone());
+            match flags.log_rotate_minutes {
+                Some(minutes) => logger.with_rotation_interval(Duration::from_secs(minutes * 60)),
+                None => logger,
+            }
+        });
+        let logger = match logger {
             Ok(logger) => {
-                println!("CAN logging started: {:?}", logger.get_log_path());
+                info!("CAN logging started: {:?}", logger.get_log_path());
                 Some(logger)
             }
             Err(e) => {
-                eprintln!("Failed to initialize CAN logger: {}", e);
+                error!("Failed to initialize CAN logger: {}", e);
                 None
             }
         };
 
+        let fault_log_dir = flags
+            .log_dir
+            .clone()
+            .unwrap_or_else(|| std::path::PathBuf::from("."));
+        let fault_logger = match FaultLogger::with_dir(&fault_log_dir) {
+            Ok(fault_logger) => {
+                info!("Fault logging started: {:?}", fault_logger.get_log_path());
+                Some(fault_logger)
+            }
+            Err(e) => {
+                error!("Failed to initialize fault logger: {}", e);
+                None
+            }
+        };
+
+        let offline_source = if flags.simulate {
+            Some(OfflineSource::Simulate)
+        } else if let Some(port) = flags.udp_listen_port {
+            Some(OfflineSource::Udp { port })
+        } else {
+            flags.replay_log.clone().map(|path| OfflineSource::Replay {
+                path,
+                speed: flags.replay_speed,
+            })
+        };
+
+        let ws = flags.ws_port.and_then(|port| match start_ws_server(port) {
+            Ok(broadcaster) => {
+                info!("WebSocket telemetry server listening on port {}", port);
+                Some(broadcaster)
+            }
+            Err(e) => {
+                error!("{}", e);
+                None
+            }
+        });
+
+        let mqtt = flags.mqtt_host.as_deref().and_then(|host| {
+            match MqttPublisher::start(host, flags.mqtt_port) {
+                Ok(publisher) => {
+                    info!(
+                        "Publishing telemetry to MQTT broker {}:{}",
+                        host, flags.mqtt_port
+                    );
+                    Some(publisher)
+                }
+                Err(e) => {
+                    error!("{}", e);
+                    None
+                }
+            }
+        });
+
+        let udp = flags
+            .udp_broadcast
+            .and_then(|target| match UdpBroadcaster::new(target) {
+                Ok(broadcaster) => {
+                    info!("Broadcasting CAN frames over UDP to {}", target);
+                    Some(broadcaster)
+                }
+                Err(e) => {
+                    error!("{}", e);
+                    None
+                }
+            });
+
+        let influx = flags.influx.clone().and_then(|config| {
+            let url = config.url.clone();
+            match InfluxWriter::start(config) {
+                Ok(writer) => {
+                    info!("Writing telemetry to InfluxDB at {}", url);
+                    Some(writer)
+                }
+                Err(e) => {
+                    error!("{}", e);
+                    None
+                }
+            }
+        });
+
+        // Going fullscreen immediately grabs whichever display the window started on, so wait
+        // for the window manager to place it first (--windowed skips fullscreen entirely)
+        let startup_command = if flags.windowed {
+            Command::none()
+        } else {
+            let delay = Duration::from_millis(flags.fullscreen_delay_ms);
+            Command::perform(tokio::time::sleep(delay), |_| Message::EnterFullscreen)
+        };
+
         (
             Self {
                 can_connected: false,
+                can_reconnecting: false,
+                can_frames_since_loss: 0,
+                can_bus_load: BusLoadMeter::new(BUS_LOAD_WINDOW),
+                can_online_frames: flags.can_online_frames,
+                can_stale_after: Duration::from_millis(flags.can_stale_ms),
+                can_retry_interval: Duration::from_millis(flags.can_retry_ms),
                 direction: "Neutral".into(),
-                fullscreen: true,
+                fullscreen: false,
+                show_diagnostics: false,
+                show_message_rates: false,
+                show_motor_detail: false,
+                frozen: None,
+                speed_unit: SpeedUnit::Mph,
+                dimmed: false,
+                auto_dim_hours: flags.auto_dim_hours,
+                auto_dim_active: None,
 
                 motor1_speed_rpm: 0.0,
                 motor2_speed_rpm: 0.0,
                 motor1_direction: "Neutral".into(),
                 motor2_direction: "Neutral".into(),
                 speed_mph: 0.0,
-                motor1_last_update: None,
-                motor2_last_update: None,
+                data_last_update: HashMap::new(),
+                data_stale_after: Duration::from_millis(flags.data_stale_ms),
                 battery_voltage: 0.0,
                 battery_current: 0.0,
                 battery_charge: 0.0,
                 battery_temp: 0.0,
                 battery_temp_hi: 0.0,
                 battery_temp_lo: 0.0,
-                bps_ontime: 0,
+                bps_ontime: 0.0,
+                bps_ontime_scale,
                 bps_state: "Standby".into(),
                 active_faults: HashMap::new(),
+                acknowledged_faults: HashMap::new(),
 
                 // Initialize fault cycling state - faster cycling
                 fault_page_index: 0,
                 fault_cycle_timer: 0,
-                fault_cycle_interval: 20, // 2 seconds at 100ms per tick - faster cycling
+                fault_cycle_interval: (flags.fault_cycle_ms / TICK_INTERVAL.as_millis() as u64)
+                    .max(1) as u32,
+                faults_per_page: flags.faults_per_page,
 
-                _theme: iced::Theme::Dark,
-                decoder: CanDecoder::new("telemetry.dbc"),
+                theme: iced::Theme::Dark,
+                fault_palette: flags.fault_palette,
+                decoder: Arc::new(Mutex::new(decoder)),
                 logger,
+                fault_logger,
                 serial_manager,
                 rfd_connected: false,
+                lora_connected: false,
+                can_heartbeat: Heartbeat::new(),
+                can_frames_received: Arc::new(AtomicU64::new(0)),
+                rf_receiver,
+                rf_frames_received: 0,
+                odometer: Odometer::load(ODOMETER_STATE_FILE),
+                session: SessionStats::new(),
+                energy: EnergyMeter::new(),
+                trip_peak_mph: 0.0,
+                ws,
+                last_ws_status: None,
+                mqtt,
+                influx,
+                udp,
+                low_disk_bytes: flags.low_disk_mb * 1024 * 1024,
+                last_disk_check: None,
                 rfd_enabled,
+                lora_enabled: flags.lora_enabled,
+                can_id_allowlist,
+                offline_source,
+                tx_id_allowlist: flags.tx_id_allowlist,
+                known_unknown_ids: flags.known_unknown_ids,
+                undecoded_ids: BTreeSet::new(),
+                message_rates: HashMap::new(),
+                wheel_diameter_inches: flags.wheel_diameter_inches,
+                current_warn_amps: flags.current_warn_amps,
+                current_critical_amps: flags.current_critical_amps,
+                audio_alarm: flags.audio_alarm,
+                alarmed_faults: HashSet::new(),
+                can_bitrate: flags.can_bitrate,
+                detected_can_bitrate,
                 current_time: Local::now().format("%H:%M:%S").to_string(),
                 mppt_data: MpptData::default(),
+                display_units: DisplayUnits::default(),
 
                 // Initialize configuration mappings
-                gui_value_mappings: get_gui_value_mappings(),
-                fault_signal_config: get_fault_signal_config(),
+                gui_value_mappings: signal_mappings.values,
+                fault_signal_config: signal_mappings.faults,
+                bit_fault_signals,
+                range_check_config: signal_mappings.range_checks,
+                range_faults_enabled: flags.range_faults_enabled,
             },
-            iced::window::change_mode(iced::window::Id::MAIN, iced::window::Mode::Fullscreen),
+            startup_command,
         )
     }
 
@@ -154,18 +595,23 @@ impl Application for TelemetryGui {
 
     fn update(&mut self, message: Message) -> Command<Message> {
         match message {
-            Message::CanFrameReceived(decoded_str, frame) => {
-                // Mark CAN as connected
-                self.can_connected = true;
+            Message::CanFrameReceived(decoded, frame) => {
+                // Mark CAN as connected once enough consecutive frames have arrived
+                if !self.can_connected {
+                    self.can_frames_since_loss += 1;
+                    if self.can_frames_since_loss >= self.can_online_frames {
+                        self.can_connected = true;
+                        self.can_reconnecting = false;
+                    }
+                }
+                self.can_bus_load
+                    .record(frame.data().len(), frame.is_extended());
 
                 // Log the frame (non-blocking)
                 if let Some(logger) = &mut self.logger {
-                    if let Err(e) = logger.log_frame(&frame) {
-                        // Don't print every logging error to avoid console spam
-                        if rand::random::<u8>() < 10 {
-                            // Print ~4% of errors
-                            eprintln!("Failed to log CAN frame: {}", e);
-                        }
+                    if let Err(e) = logger.log_frame(&frame, &decoded) {
+                        // Per-frame, so only at debug level (the low-disk fault covers a full disk)
+                        debug!("Failed to log CAN frame: {}", e);
                     }
                 }
 
@@ -175,106 +621,148 @@ impl Application for TelemetryGui {
                     socketcan::Id::Extended(ext_id) => ext_id.as_raw(),
                 };
 
-                // Determine message name from ID
-                let message_name = match raw_id {
-                    0x300 => "BMS_DTC",
-                    0x310 => "BMS_Limits",
-                    0x320 => "BMS_Power",
-                    0x330 => "BMS_State",
-                    0x340 => "BMS_Capacity",
-                    0x360 => "BMS_Temperature",
-                    0x776 => "BPS_System",
-                    0x777 => "BPS_Thing",
-                    0x200 | 0x201 => "MPPT1",
-                    0x202 | 0x203 => "MPPT2",
-                    // Motor Controller 1 (ID ending in 05)
-                    id if id == 0x0CF11E05
-                        || id == 0x0CF11F05
-                        || (id & 0xFFFFFF0F) == 0x0CF11E05
-                        || (id & 0xFFFFFF0F) == 0x0CF11F05 =>
-                    {
-                        "MotorController_1"
-                    }
-                    // Motor Controller 2 (ID ending in 06)
-                    id if id == 0x0CF11E06
-                        || id == 0x8CF11F06
-                        || (id & 0xFFFFFF0F) == 0x0CF11E06
-                        || (id & 0xFFFFFF0F) == 0x0CF11F06 =>
-                    {
-                        "MotorController_2"
+                let message_name = decoded.message_name.as_str();
+                match self.message_rates.get_mut(&raw_id) {
+                    Some(tracker) => tracker.record(message_name),
+                    None => {
+                        self.message_rates
+                            .insert(raw_id, RateTracker::new(message_name));
                     }
-                    _ => "Unknown",
-                };
+                }
 
-                // Track DTC faults found in this message to clear stale ones
-                let mut dtc_faults_in_message = std::collections::HashSet::new();
+                // Surface each unexpected undecodable ID once; acknowledged ones stay quiet
+                if !decoded.decoded
+                    && !self.known_unknown_ids.contains(&raw_id)
+                    && self.undecoded_ids.insert(raw_id)
+                {
+                    warn!("Undecoded CAN ID 0x{:X} (not in DBC)", raw_id);
+                }
+
+                // Track bit faults found in this message to clear stale ones
+                let mut bit_faults_in_message = std::collections::HashSet::new();
 
                 // Process telemetry data using mapping system
-                for line in decoded_str.lines() {
-                    if let Some((signal, val)) = line.split_once(": ") {
-                        // Check if this signal updates a GUI value
-                        if let Some(gui_value_types) =
-                            self.gui_value_mappings.get(&(message_name, signal))
-                        {
-                            let gui_value_types_cloned = gui_value_types.clone();
-                            for gui_value_type in gui_value_types_cloned {
-                                self.update_gui_value(&gui_value_type, val);
-                            }
+                for decoded_signal in &decoded.signals {
+                    let signal = decoded_signal.name.as_str();
+
+                    // Check if this signal updates a GUI value
+                    if let Some(gui_value_types) = self
+                        .gui_value_mappings
+                        .get(message_name)
+                        .and_then(|signals| signals.get(signal))
+                    {
+                        let gui_value_types_cloned = gui_value_types.clone();
+                        for gui_value_type in gui_value_types_cloned {
+                            self.update_gui_value(&gui_value_type, decoded_signal);
                         }
+                    }
 
-                        // Check if this signal is configured as a fault signal
-                        if let Some(fault_signals) = self.fault_signal_config.get(message_name) {
-                            if fault_signals.contains(&signal) {
-                                self.process_regular_fault(message_name, signal, val);
-                            }
+                    // Check if this signal is configured as a fault signal
+                    if let Some(fault_signals) = self.fault_signal_config.get(message_name) {
+                        if fault_signals.iter().any(|name| name == signal) {
+                            self.process_regular_fault(message_name, decoded_signal);
                         }
+                    }
 
-                        // Handle special DTC fault processing
-                        if message_name == "BMS_DTC" {
-                            if signal.starts_with("Fault_DTC") {
-                                let fault_name = signal.to_string();
-                                dtc_faults_in_message.insert(fault_name.clone());
-                                
-                                if !val.trim().is_empty()
-                                    && val.trim() != "0"
-                                    && val.trim() != "0.0"
-                                {
-                                    // DTC fault is active
-                                    let new_fault = Fault {
-                                        name: fault_name.clone(),
-                                        timestamp: chrono::Utc::now(),
-                                        is_active: true,
-                                        value: val.to_owned(),
-                                        message_name: message_name.to_string(),
-                                        severity: get_fault_severity(message_name, signal),
-                                    };
-                                    self.active_faults.insert(fault_name.clone(), new_fault);
-                                } else {
-                                    // DTC fault is explicitly cleared (value is 0 or empty)
-                                    self.active_faults.remove(&fault_name);
-                                }
+                    // Check the decoded value against its DBC range (opt-in)
+                    if self.range_faults_enabled {
+                        if let Some(range_signals) = self.range_check_config.get(message_name) {
+                            if range_signals.iter().any(|name| name == signal) {
+                                self.process_range_fault(raw_id, message_name, decoded_signal);
                             }
                         }
                     }
+
+                    // Flags of bit-packed fault words (e.g. the BMS DTCs) arrive as signals
+                    if let Some(severity) = self
+                        .bit_fault_signals
+                        .get(&raw_id)
+                        .and_then(|signals| signals.get(signal))
+                        .copied()
+                    {
+                        let fault_name = signal.to_string();
+                        bit_faults_in_message.insert(fault_name.clone());
+
+                        if decoded_signal.value != 0.0 {
+                            // Bit fault is active
+                            let new_fault = Fault {
+                                name: fault_name.clone(),
+                                timestamp: chrono::Utc::now(),
+                                value: decoded_signal.display_value(),
+                                message_name: message_name.to_string(),
+                                severity,
+                            };
+                            self.raise_fault(fault_name.clone(), new_fault);
+                        } else {
+                            // Bit fault is explicitly cleared
+                            self.clear_fault(&fault_name);
+                        }
+                    }
                 }
 
-                // Clear any DTC faults that weren't present in this BMS_DTC message
-                if message_name == "BMS_DTC" {
-                    let dtc_fault_keys: Vec<String> = self.active_faults.keys()
-                        .filter(|key| key.starts_with("Fault_DTC"))
+                // Clear this ID's bit faults whose flag wasn't set in this message
+                if let Some(signals) = self.bit_fault_signals.get(&raw_id) {
+                    let cleared: Vec<String> = signals
+                        .keys()
+                        .filter(|key| !bit_faults_in_message.contains(*key))
+                        .filter(|key| {
+                            self.active_faults.contains_key(*key)
+                                || self.acknowledged_faults.contains_key(*key)
+                        })
                         .cloned()
                         .collect();
-                    
-                    for fault_key in dtc_fault_keys {
-                        if !dtc_faults_in_message.contains(&fault_key) {
-                            self.active_faults.remove(&fault_key);
-                        }
+
+                    for fault_key in cleared {
+                        self.clear_fault(&fault_key);
                     }
                 }
 
+                if message_name == "BMS_Power" {
+                    self.energy
+                        .update(self.battery_voltage, self.battery_current, true);
+                }
+
                 // UPDATED: Send the CAN frame using enhanced batching system
                 // This now includes automatic filtering and intelligent batching
-                self.send_can_frame_to_modems_enhanced(raw_id, frame.data());
+                self.send_can_frame_to_modems_enhanced(raw_id, frame.is_extended(), frame.data());
+
+                if let Some(ws) = &self.ws {
+                    if decoded.decoded {
+                        ws.publish(&WsEvent::Telemetry {
+                            timestamp_ms: now_ms(),
+                            can_id: raw_id,
+                            message: decoded.export_name(),
+                            signals: &decoded.signals,
+                        });
+                    }
+                }
+                if let Some(mqtt) = &self.mqtt {
+                    if decoded.decoded {
+                        mqtt.publish(decoded.export_name(), &decoded.signals);
+                    }
+                }
+                if let Some(udp) = &mut self.udp {
+                    udp.push(&frame);
+                }
+                if let Some(influx) = &self.influx {
+                    if decoded.decoded {
+                        influx.write(decoded.export_name(), raw_id, &decoded.signals);
+                    }
+                }
+            }
+
+            Message::CanLinkLost => {
+                self.can_frames_since_loss = 0;
+                if self.can_connected {
+                    warn!("CAN link lost, waiting for {} to come back", CAN_INTERFACE);
+                    self.can_connected = false;
+                    self.can_reconnecting = true;
+                }
+            }
+
+            Message::CloseRequested => {
+                self.finish_session();
+                return window::close(window::Id::MAIN);
             }
 
             Message::ToggleFullscreen => {
@@ -289,16 +777,162 @@ impl Application for TelemetryGui {
                 );
             }
 
+            Message::EnterFullscreen => {
+                self.fullscreen = true;
+                return iced::window::change_mode(
+                    iced::window::Id::MAIN,
+                    iced::window::Mode::Fullscreen,
+                );
+            }
+
+            Message::ResetTrip => {
+                self.odometer.reset_session();
+                self.trip_peak_mph = 0.0;
+            }
+
+            Message::ResetEnergy => {
+                self.energy.reset();
+            }
+
+            Message::ToggleMotorDetail => {
+                self.show_motor_detail = !self.show_motor_detail;
+            }
+
+            Message::ToggleSpeedUnit => {
+                self.speed_unit = self.speed_unit.toggled();
+            }
+
+            Message::ReloadDbc => {
+                self.reload_dbc();
+            }
+
+            Message::ToggleDim => {
+                self.dimmed = !self.dimmed;
+            }
+
+            Message::TogglePause => {
+                let pausing = self.frozen.is_none();
+                self.frozen = pausing.then(|| self.dashboard_snapshot());
+                info!("Display {}", if pausing { "paused" } else { "resumed" });
+            }
+
+            Message::Screenshot => {
+                return window::screenshot(window::Id::MAIN, Message::ScreenshotCaptured);
+            }
+
+            Message::ScreenshotCaptured(screenshot) => {
+                // Encoding a full-window PNG takes a moment; keep it off the GUI thread
+                let dir = self.output_dir();
+                std::thread::spawn(move || {
+                    let size = screenshot.size;
+                    match write_screenshot(&dir, size.width, size.height, &screenshot.bytes) {
+                        Ok(path) => info!("Screenshot saved to {:?}", path),
+                        Err(e) => error!("Screenshot failed: {}", e),
+                    }
+                });
+            }
+
+            Message::ToggleTheme => {
+                self.theme = match self.theme {
+                    Theme::Light => Theme::Dark,
+                    _ => Theme::Light,
+                };
+            }
+
+            Message::ToggleDiagnostics => {
+                // From the rate page, the top-row button goes back to the dashboard
+                if self.show_message_rates {
+                    self.show_message_rates = false;
+                } else {
+                    self.show_diagnostics = !self.show_diagnostics;
+                }
+            }
+
+            Message::ToggleMessageRates => {
+                self.show_message_rates = !self.show_message_rates;
+                self.show_diagnostics = !self.show_message_rates;
+            }
+
+            Message::AcknowledgeFault(fault_key) => {
+                if let Some(fault) = self.active_faults.remove(&fault_key) {
+                    self.acknowledged_faults.insert(fault_key, fault);
+                    // The panel may now have fewer pages than the one showing
+                    self.fault_page_index = 0;
+                    self.fault_cycle_timer = 0;
+                }
+            }
+
+            Message::RestoreAcknowledgedFaults => {
+                self.active_faults.extend(self.acknowledged_faults.drain());
+            }
+
             Message::Tick => {
+                if SHUTDOWN_REQUESTED.load(Ordering::Relaxed) {
+                    info!("Interrupted, shutting down");
+                    return self.update(Message::CloseRequested);
+                }
+
                 // Update current time
-                self.current_time = Local::now().format("%H:%M:%S").to_string();
+                let now = Local::now();
+                self.current_time = now.format("%H:%M:%S").to_string();
+
+                if let Some(udp) = &mut self.udp {
+                    udp.flush();
+                }
+                if let Some(receiver) = &self.rf_receiver {
+                    self.rf_frames_received += receiver.try_iter().count() as u64;
+                }
+
+                // Follow the auto-dim window when it opens or closes
+                if let Some((start, end)) = self.auto_dim_hours {
+                    let hour = now.hour();
+                    let in_window = if start < end {
+                        (start..end).contains(&hour)
+                    } else {
+                        hour >= start || hour < end
+                    };
+                    if self.auto_dim_active != Some(in_window) {
+                        self.auto_dim_active = Some(in_window);
+                        self.dimmed = in_window;
+                    }
+                }
+
+                // Accumulate distance, skipping periods where motor speed data is stale
+                let (motor1_fresh, motor2_fresh) = self.motor_data_fresh();
+                self.odometer
+                    .integrate(self.speed_mph, motor1_fresh || motor2_fresh);
+                self.session.record_speed(self.speed_mph);
+                self.trip_peak_mph = self.trip_peak_mph.max(self.speed_mph.abs());
+                let has_battery_data = self.session.has_battery_data();
+                self.session.integrate_power(
+                    self.battery_voltage,
+                    self.battery_current,
+                    has_battery_data,
+                );
 
                 // Update modem connection status (enhanced monitoring)
                 self.update_modem_status_enhanced();
 
-                // Handle fault cycling (faster)
+                // Periodically make sure logging isn't about to run out of space
+                if self
+                    .last_disk_check
+                    .is_none_or(|t| t.elapsed() >= DISK_CHECK_INTERVAL)
+                {
+                    self.last_disk_check = Some(std::time::Instant::now());
+                    self.check_log_disk_space();
+                }
+
+                if self
+                    .last_ws_status
+                    .is_none_or(|t| t.elapsed() >= WS_STATUS_INTERVAL)
+                {
+                    self.last_ws_status = Some(Instant::now());
+                    self.publish_ws_status();
+                }
+
+                // Cycle fault pages when they don't all fit on one
                 let fault_count = self.active_faults.len();
-                if fault_count > 5 {
+                if fault_count > self.faults_per_page {
                     // Increment the fault cycle timer
                     self.fault_cycle_timer += 1;
 
@@ -307,13 +941,13 @@ impl Application for TelemetryGui {
                         self.fault_cycle_timer = 0; // Reset timer
 
                         // Calculate total pages
-                        let total_pages = (fault_count + 4) / 5; // Ceiling division for 5 faults per page
+                        let total_pages = fault_count.div_ceil(self.faults_per_page);
 
                         // Move to next page, wrapping around if necessary
                         self.fault_page_index = (self.fault_page_index + 1) % total_pages;
                     }
                 } else {
-                    // Reset cycling state when we have 5 or fewer faults
+                    // Reset cycling state when every fault fits on one page
                     self.fault_page_index = 0;
                     self.fault_cycle_timer = 0;
                 }
@@ -323,34 +957,85 @@ impl Application for TelemetryGui {
         Command::none()
     }
 
-    fn view(&self) -> Element<Message> {
-        // Create data structs for each component
-        let battery_data = BatteryData {
-            voltage: self.battery_voltage,
-            current: self.battery_current,
-            charge: self.battery_charge,
-            temp: self.battery_temp,
-            temp_lo: self.battery_temp_lo,
-            temp_hi: self.battery_temp_hi,
-        };
+    fn view(&self) -> Element<'_, Message> {
+        let can_status = can_status_indicator(
+            self.can_connected,
+            self.can_reconnecting,
+            self.can_bus_load.load_percent(self.can_bitrate),
+        );
+        let radio_status = radio_status_indicators(
+            self.rfd_connected && self.rfd_enabled,
+            self.lora_enabled.then_some(self.lora_connected),
+            self.mqtt.as_ref().map(MqttPublisher::is_connected),
+        );
+        let time_display = time_display(&self.current_time, self.frozen.is_some());
+
+        if self.show_message_rates {
+            let rates = message_rates_panel(&self.message_rate_rows());
+            return diagnostics_layout(
+                self.fullscreen,
+                can_status,
+                radio_status,
+                rates,
+                time_display,
+            );
+        }
+        if self.show_diagnostics {
+            let diagnostics = diagnostics_panel(&self.get_diagnostics_data());
+            return diagnostics_layout(
+                self.fullscreen,
+                can_status,
+                radio_status,
+                diagnostics,
+                time_display,
+            );
+        }
 
-        let bps_data = BpsData {
-            ontime: self.bps_ontime,
-            state: self.bps_state.clone(),
+        // Live values, or the ones captured when the display was paused
+        let live;
+        let dashboard = match &self.frozen {
+            Some(snapshot) => snapshot,
+            None => {
+                live = self.dashboard_snapshot();
+                &live
+            }
         };
 
         // Create UI elements
-        let can_status = can_status_indicator(self.can_connected);
-        let radio_status = radio_status_indicators(self.rfd_connected && self.rfd_enabled);
-        let mppt_info = mppt_info_box(&self.mppt_data, &bps_data);
-        let speed_direction = direction_speed_display(&self.direction, self.speed_mph);
-        let battery_info = battery_box(&battery_data);
-        let fault_display = fault_display(&self.active_faults, self.fault_page_index);
-        let time_display = time_display(&self.current_time);
+        let mppt_info = mppt_info_box(
+            &dashboard.mppt,
+            &dashboard.bps,
+            &dashboard.display_units,
+            dashboard.mppt1_stale,
+            dashboard.mppt2_stale,
+        );
+        let speed_direction = direction_speed_display(
+            &dashboard.direction,
+            dashboard.speed_mph,
+            dashboard.session_miles,
+            dashboard.trip_peak_mph,
+            self.speed_unit,
+            self.show_motor_detail.then_some(&dashboard.motor_detail),
+        );
+        let battery_info = battery_box(&dashboard.battery, &dashboard.display_units);
+        let power_flow = power_box(&dashboard.power, self.speed_unit);
+        let fault_display = fault_display(
+            &self.active_faults,
+            self.acknowledged_faults.len(),
+            self.fault_page_index,
+            self.faults_per_page,
+            &self.fault_palette,
+        );
 
         // Create warning indicator for high battery current
-        let warning_indicator = if self.battery_current > 70.0 {
-            Some(battery_current_warning())
+        let battery_current = dashboard.battery.current;
+        let warning_indicator = if self
+            .current_critical_amps
+            .is_some_and(|critical| battery_current > critical)
+        {
+            Some(battery_current_warning(true))
+        } else if battery_current > self.current_warn_amps {
+            Some(battery_current_warning(false))
         } else {
             None
         };
@@ -363,6 +1048,7 @@ impl Application for TelemetryGui {
             mppt_info,
             speed_direction,
             battery_info,
+            power_flow,
             fault_display,
             time_display,
             warning_indicator,
@@ -372,54 +1058,72 @@ impl Application for TelemetryGui {
     fn subscription(&self) -> Subscription<Message> {
         // Combine subscriptions with optimized intervals
         Subscription::batch(vec![
-            // Enhanced CAN subscription with better error handling
-            {
-                let decoder = self.decoder.clone();
-                subscription::unfold("enhanced_can_subscription", decoder, |decoder| async {
-                    let socket = match CanSocket::open("can0") {
-                        Ok(s) => s,
-                        Err(e) => {
-                            eprintln!("Failed to open CAN socket: {}", e);
-                            // Sleep and try again
-                            tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
-                            // Create a dummy frame for the error case
-                            let dummy_frame = match CanFrame::new(
-                                socketcan::Id::Standard(StandardId::new(0).unwrap()),
-                                &[0; 8],
-                            ) {
-                                Some(frame) => frame,
-                                None => {
-                                    panic!("Failed to create dummy CAN frame");
-                                }
-                            };
-                            return (
-                                Message::CanFrameReceived("CAN Error".to_string(), dummy_frame),
-                                decoder,
-                            );
+            // Enhanced CAN subscription with better error handling (or an offline source)
+            if let Some(source) = &self.offline_source {
+                self.offline_subscription(source.clone())
+            } else {
+                let state = CanSubscriptionState {
+                    decoder: Arc::clone(&self.decoder),
+                    heartbeat: self.can_heartbeat.clone(),
+                    frames_received: Arc::clone(&self.can_frames_received),
+                    id_allowlist: self.can_id_allowlist.clone(),
+                    socket: None,
+                    last_frame: Instant::now(),
+                    stale_after: self.can_stale_after,
+                    retry_interval: self.can_retry_interval,
+                };
+                subscription::unfold("enhanced_can_subscription", state, |mut state| async {
+                    if state.socket.is_none() {
+                        match Self::open_can_socket(state.id_allowlist.as_deref()) {
+                            Ok(socket) => {
+                                state.socket = Some(socket);
+                                state.last_frame = Instant::now();
+                            }
+                            Err(e) => {
+                                warn!("{}", e);
+                                // Sleep and try again
+                                tokio::time::sleep(state.retry_interval).await;
+                                return (Message::CanLinkLost, state);
+                            }
                         }
-                    };
-
-                    // Set non-blocking mode with minimal timeout
-                    if let Err(e) = socket.set_nonblocking(true) {
-                        eprintln!("Failed to set non-blocking mode: {}", e);
                     }
 
                     loop {
-                        match socket.read_frame() {
+                        let read_result = match &state.socket {
+                            Some(socket) => socket.read_frame(),
+                            None => return (Message::CanLinkLost, state),
+                        };
+
+                        match read_result {
                             Ok(frame) => {
+                                state.heartbeat.beat();
+                                state.frames_received.fetch_add(1, Ordering::Relaxed);
+                                state.last_frame = Instant::now();
+
                                 // Always pass the frame along, even if decoding fails
-                                let decoded = decoder
-                                    .decode(frame.clone())
-                                    .unwrap_or_else(|| format!("Unknown frame: {:?}", frame));
-                                return (Message::CanFrameReceived(decoded, frame), decoder);
+                                let decoded = state
+                                    .decoder
+                                    .lock()
+                                    .unwrap()
+                                    .decode(frame)
+                                    .unwrap_or_else(DecodedMessage::unknown);
+                                return (Message::CanFrameReceived(decoded, frame), state);
                             }
                             Err(e) => {
                                 if e.kind() == std::io::ErrorKind::WouldBlock {
+                                    // A socket that outlived an interface flap can go silent, so
+                                    // re-open it once the bus has been quiet for the stale window
+                                    if state.last_frame.elapsed() >= state.stale_after {
+                                        state.socket = None;
+                                        return (Message::CanLinkLost, state);
+                                    }
                                     // No data available, yield to scheduler briefly for maximum responsiveness
                                     tokio::task::yield_now().await;
                                 } else {
-                                    eprintln!("CAN read error: {}", e);
-                                    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                                    warn!("CAN read error: {}, re-opening socket", e);
+                                    state.socket = None;
+                                    tokio::time::sleep(state.retry_interval).await;
+                                    return (Message::CanLinkLost, state);
                                 }
                             }
                         }
@@ -427,50 +1131,249 @@ impl Application for TelemetryGui {
                 })
             },
             // Timer for updating time and checking connections - optimized refresh
-            time::every(std::time::Duration::from_millis(100)).map(|_| Message::Tick),
+            time::every(TICK_INTERVAL).map(|_| Message::Tick),
+            // Keyboard shortcuts
+            keyboard::on_key_press(|key, _modifiers| match key.as_ref() {
+                Key::Character("d") => Some(Message::ToggleDiagnostics),
+                Key::Character("h") => Some(Message::ToggleMessageRates),
+                Key::Character("m") => Some(Message::ToggleMotorDetail),
+                Key::Character("n") => Some(Message::ToggleDim),
+                Key::Character("t") => Some(Message::ToggleTheme),
+                Key::Character("p") => Some(Message::Screenshot),
+                Key::Named(keyboard::key::Named::Space) => Some(Message::TogglePause),
+                Key::Character("k") => Some(Message::ToggleSpeedUnit),
+                Key::Character("r") => Some(Message::ReloadDbc),
+                Key::Character("u") => Some(Message::RestoreAcknowledgedFaults),
+                Key::Character("e") => Some(Message::ResetEnergy),
+                _ => None,
+            }),
+            // Window close is intercepted (exit_on_close_request = false) to write the summary
+            event::listen_with(|event, _status| match event {
+                iced::Event::Window(_, window::Event::CloseRequested) => {
+                    Some(Message::CloseRequested)
+                }
+                _ => None,
+            }),
         ])
     }
 }
 
 impl TelemetryGui {
+    // Emit frames from a replay or the simulator at their scheduled timing, as if read from can0
+    fn offline_subscription(&self, source: OfflineSource) -> Subscription<Message> {
+        let state = OfflineState {
+            decoder: Arc::clone(&self.decoder),
+            heartbeat: self.can_heartbeat.clone(),
+            frames_received: Arc::clone(&self.can_frames_received),
+            id_allowlist: self.can_id_allowlist.clone(),
+            pending: Some(source),
+            feed: None,
+            started: Instant::now(),
+        };
+        subscription::unfold("offline_can_subscription", state, |mut state| async move {
+            if let Some(source) = state.pending.take() {
+                let opened = match source {
+                    OfflineSource::Replay { path, speed } => {
+                        LogReplay::open(&path, speed).map(|replay| {
+                            info!("Replaying {} at {}x speed", path, speed);
+                            FrameFeed::Replay(replay)
+                        })
+                    }
+                    OfflineSource::Simulate => {
+                        info!("Simulating CAN traffic from the DBC");
+                        let decoder = state.decoder.lock().unwrap();
+                        Ok(FrameFeed::Simulated(FrameSimulator::new(&decoder)))
+                    }
+                    OfflineSource::Udp { port } => UdpReceiver::bind(port).map(|receiver| {
+                        info!("Receiving CAN frames over UDP on port {}", port);
+                        FrameFeed::Udp(receiver)
+                    }),
+                };
+                match opened {
+                    Ok(feed) => {
+                        state.feed = Some(feed);
+                        state.started = Instant::now();
+                    }
+                    Err(e) => error!("{}", e),
+                }
+            }
+
+            loop {
+                let next = match state.feed.as_mut() {
+                    Some(FrameFeed::Replay(replay)) => replay.next_frame(),
+                    Some(FrameFeed::Simulated(simulator)) => {
+                        Ok(simulator.next_frame(&state.decoder.lock().unwrap()))
+                    }
+                    // Live feed: frames are due as soon as they arrive
+                    Some(FrameFeed::Udp(receiver)) => receiver
+                        .next_frame()
+                        .await
+                        .map(|frame| Some((Duration::ZERO, frame))),
+                    // Source exhausted; keep the subscription alive without emitting anything
+                    None => return futures::future::pending().await,
+                };
+
+                match next {
+                    Ok(Some((offset, frame))) => {
+                        // Mirror the kernel filter the live socket would have applied
+                        let raw_id = match frame.id() {
+                            socketcan::Id::Standard(std_id) => std_id.as_raw() as u32,
+                            socketcan::Id::Extended(ext_id) => ext_id.as_raw(),
+                        };
+                        if let Some(ids) = &state.id_allowlist {
+                            if !ids.contains(&raw_id) {
+                                continue;
+                            }
+                        }
+
+                        if let Some(wait) = offset.checked_sub(state.started.elapsed()) {
+                            tokio::time::sleep(wait).await;
+                        }
+                        state.heartbeat.beat();
+                        state.frames_received.fetch_add(1, Ordering::Relaxed);
+
+                        let decoded = state
+                            .decoder
+                            .lock()
+                            .unwrap()
+                            .decode(frame)
+                            .unwrap_or_else(DecodedMessage::unknown);
+                        return (Message::CanFrameReceived(decoded, frame), state);
+                    }
+                    Ok(None) => {
+                        info!(
+                            "Offline CAN source finished: {} frames",
+                            state.frames_received.load(Ordering::Relaxed)
+                        );
+                        state.feed = None;
+                    }
+                    Err(e) => {
+                        error!("Replay stopped: {}", e);
+                        state.feed = None;
+                    }
+                }
+            }
+        })
+    }
+
+    // Open the CAN interface non-blocking, letting the kernel drop frames outside the allowlist
+    fn open_can_socket(id_allowlist: Option<&[u32]>) -> Result<CanSocket, String> {
+        let socket = CanSocket::open(CAN_INTERFACE)
+            .map_err(|e| format!("Failed to open CAN socket: {}", e))?;
+
+        if let Some(ids) = id_allowlist {
+            if let Err(e) = socket.set_filters(&build_id_filters(ids)) {
+                warn!("Failed to apply CAN ID filters: {}", e);
+            }
+        }
+
+        // Set non-blocking mode with minimal timeout
+        if let Err(e) = socket.set_nonblocking(true) {
+            warn!("Failed to set non-blocking mode: {}", e);
+        }
+
+        Ok(socket)
+    }
+
     // UPDATED: Create SerialManager with enhanced batching
-    fn create_enhanced_serial_manager(rfd_enabled: bool) -> SerialManager {
+    fn create_enhanced_serial_manager(
+        rfd_enabled: bool,
+        rfd_baud_rate: u32,
+        lora_enabled: bool,
+        rfd_port: Option<&str>,
+        rfd_auto_scan: bool,
+        scan_all_ports: bool,
+    ) -> SerialManager {
         let mut manager = SerialManager::new();
 
         // Configure modem settings
         manager.set_rfd_enabled(rfd_enabled);
+        manager.set_rfd_baud_rate(rfd_baud_rate);
+        manager.set_lora_enabled(lora_enabled);
+        manager.set_rfd_auto_scan(rfd_auto_scan);
+        manager.set_scan_all_ports(scan_all_ports);
+
+        // A chosen port replaces scanning; the scanner keeps retrying it if this fails
+        if let Some(port_name) = rfd_port {
+            if let Err(e) = manager.connect_rfd_port(port_name) {
+                warn!("RFD port {} not connected yet: {}", port_name, e);
+            }
+        }
 
         // Start background scanning
         if let Err(e) = manager.start_background_scanning() {
-            println!("Failed to start background scanning: {}", e);
+            error!("Failed to start background scanning: {}", e);
         }
 
         // Start enhanced batching
         if let Err(e) = manager.start_batching() {
-            println!("Failed to start enhanced batching: {}", e);
+            error!("Failed to start enhanced batching: {}", e);
         } else {
-            println!("✓ Enhanced batching started successfully");
-            println!("  - Automatic message filtering enabled");
-            println!("  - Synchronization markers enabled");
-            println!("  - Error recovery enabled");
+            info!("✓ Enhanced batching started successfully");
+            debug!("  - Automatic message filtering enabled");
+            debug!("  - Synchronization markers enabled");
+            debug!("  - Error recovery enabled");
         }
 
         manager
     }
 
+    // Forward inject requests arriving over the RF link onto can0 from a background thread
+    fn start_can_injector(serial_manager: &SerialManager) {
+        let writer = match CanWriter::open(CAN_INTERFACE) {
+            Ok(writer) => writer,
+            Err(e) => {
+                warn!("CAN inject disabled: {}", e);
+                return;
+            }
+        };
+        let requests = serial_manager.accept_inject_requests();
+
+        std::thread::spawn(move || {
+            for request in requests {
+                let Some(frame) = build_frame(request.id, request.is_extended, &request.data)
+                else {
+                    warn!("Ignoring invalid CAN inject request for 0x{:X}", request.id);
+                    continue;
+                };
+                match writer.send(&frame) {
+                    Ok(()) => info!(
+                        "Injected 0x{:X} [{}] onto {}",
+                        request.id,
+                        request.data.len(),
+                        CAN_INTERFACE
+                    ),
+                    Err(e) => error!("CAN inject failed: {}", e),
+                }
+            }
+        });
+    }
+
     // UPDATED: Enhanced CAN frame transmission with intelligent batching
-    fn send_can_frame_to_modems_enhanced(&self, can_id: u32, data: &[u8]) {
+    fn send_can_frame_to_modems_enhanced(&self, can_id: u32, is_extended: bool, data: &[u8]) {
         // The enhanced SerialManager now automatically handles:
         // - Message filtering to prevent spam (especially 0x300)
         // - Intelligent batching with proper synchronization
         // - Error recovery and health monitoring
         // - Rate limiting based on DBC transmission intervals
 
-        if let Err(e) = self.serial_manager.send_can_frame(can_id, data) {
-            // Only log errors occasionally to prevent console spam
-            if rand::random::<u8>() < 5 {
-                // ~2% of errors
-                eprintln!("CAN frame transmission error: {}", e);
+        // Outright transmit gate, ahead of any filtering or batching
+        if let Some(ids) = &self.tx_id_allowlist {
+            if !ids.contains(&can_id) {
+                return;
+            }
+        }
+
+        if let Err(e) = self
+            .serial_manager
+            .send_can_frame(can_id, is_extended, data)
+        {
+            match e {
+                // Expected while no radio is connected; the RFD indicator already shows it
+                SerialError::NoModems | SerialError::NotConnected(_) | SerialError::PortBusy => {
+                    debug!("CAN frame not transmitted: {}", e)
+                }
+                _ => warn!("CAN frame transmission error: {}", e),
             }
         }
     }
@@ -485,9 +1388,15 @@ impl TelemetryGui {
         } else {
             self.rfd_connected = false;
         }
+        if self.lora_enabled {
+            if let Ok(lora_status) = self.serial_manager.lora_status.try_lock() {
+                self.lora_connected = lora_status.connected;
+            }
+        }
 
         // Monitor batch queue health
         let rfd_queue = self.serial_manager.get_batch_stats();
+        let lora_queue = self.serial_manager.get_lora_queue_size();
 
         // Log enhanced statistics periodically for monitoring
         static mut LAST_STATS_LOG: Option<std::time::Instant> = None;
@@ -500,12 +1409,17 @@ impl TelemetryGui {
 
             if should_log {
                 if rfd_queue > 0 {
-                    println!("📊 Batch queues - RFD: {}", rfd_queue);
+                    debug!("📊 Batch queues - RFD: {}", rfd_queue);
                 }
+                if lora_queue > 0 {
+                    debug!("📊 Batch queues - LoRa: {}", lora_queue);
+                }
+
+                debug!("📡 {}", self.rf_reduction_summary());
 
                 // Check for queue buildup (potential issue)
                 if rfd_queue > 20 {
-                    println!("⚠ Large batch queues detected - possible transmission issues");
+                    warn!("⚠ Large batch queues detected - possible transmission issues");
                 }
 
                 LAST_STATS_LOG = Some(now);
@@ -514,109 +1428,87 @@ impl TelemetryGui {
     }
 
     // Helper method to update GUI values based on the configuration
-    fn update_gui_value(&mut self, gui_value_type: &GuiValueType, value: &str) {
+    fn update_gui_value(&mut self, gui_value_type: &GuiValueType, signal: &DecodedSignal) {
+        self.display_units.record(gui_value_type, &signal.unit);
+        self.data_last_update
+            .insert(gui_value_type.data_group(), Instant::now());
         match gui_value_type {
             GuiValueType::Motor1Speed => {
-                if let Ok(v) = value.parse::<f64>() {
-                    self.motor1_speed_rpm = v;
-                    self.motor1_last_update = Some(std::time::Instant::now());
-                    // Trigger speed recalculation immediately
-                    self.update_vehicle_speed();
-                }
+                self.motor1_speed_rpm = signal.value;
+                // Trigger speed recalculation immediately
+                self.update_vehicle_speed();
             }
             GuiValueType::Motor2Speed => {
-                if let Ok(v) = value.parse::<f64>() {
-                    self.motor2_speed_rpm = v;
-                    self.motor2_last_update = Some(std::time::Instant::now());
-                    // Trigger speed recalculation immediately
-                    self.update_vehicle_speed();
-                }
+                self.motor2_speed_rpm = signal.value;
+                // Trigger speed recalculation immediately
+                self.update_vehicle_speed();
             }
             GuiValueType::Motor1Direction => {
-                self.motor1_direction = value.to_string();
+                self.motor1_direction = signal.display_value();
                 self.update_vehicle_direction();
             }
             GuiValueType::Motor2Direction => {
-                self.motor2_direction = value.to_string();
+                self.motor2_direction = signal.display_value();
                 self.update_vehicle_direction();
             }
             GuiValueType::Mppt1InputVoltage => {
-                if let Ok(v) = value.parse::<f64>() {
-                    self.mppt_data.mppt1_input_voltage = v;
-                }
+                self.mppt_data.mppt1_input_voltage = signal.value;
             }
             GuiValueType::Mppt1InputCurrent => {
-                if let Ok(v) = value.parse::<f64>() {
-                    self.mppt_data.mppt1_input_current = v;
-                }
+                self.mppt_data.mppt1_input_current = signal.value;
             }
             GuiValueType::Mppt1OutputVoltage => {
-                if let Ok(v) = value.parse::<f64>() {
-                    self.mppt_data.mppt1_output_voltage = v;
-                }
+                self.mppt_data.mppt1_output_voltage = signal.value;
             }
             GuiValueType::Mppt1OutputCurrent => {
-                if let Ok(v) = value.parse::<f64>() {
-                    self.mppt_data.mppt1_output_current = v;
-                }
+                self.mppt_data.mppt1_output_current = signal.value;
             }
             GuiValueType::Mppt2InputVoltage => {
-                if let Ok(v) = value.parse::<f64>() {
-                    self.mppt_data.mppt2_input_voltage = v;
-                }
+                self.mppt_data.mppt2_input_voltage = signal.value;
             }
             GuiValueType::Mppt2InputCurrent => {
-                if let Ok(v) = value.parse::<f64>() {
-                    self.mppt_data.mppt2_input_current = v;
-                }
+                self.mppt_data.mppt2_input_current = signal.value;
             }
             GuiValueType::Mppt2OutputVoltage => {
-                if let Ok(v) = value.parse::<f64>() {
-                    self.mppt_data.mppt2_output_voltage = v;
-                }
+                self.mppt_data.mppt2_output_voltage = signal.value;
             }
             GuiValueType::Mppt2OutputCurrent => {
-                if let Ok(v) = value.parse::<f64>() {
-                    self.mppt_data.mppt2_output_current = v;
-                }
+                self.mppt_data.mppt2_output_current = signal.value;
+            }
+            GuiValueType::Mppt1Mode => {
+                self.mppt_data.mppt1_mode = mppt_mode_label(signal);
+            }
+            GuiValueType::Mppt2Mode => {
+                self.mppt_data.mppt2_mode = mppt_mode_label(signal);
             }
             GuiValueType::BatteryVoltage => {
-                if let Ok(v) = value.parse::<f64>() {
-                    self.battery_voltage = v;
-                }
+                self.battery_voltage = signal.value;
+                self.session.record_battery_voltage(signal.value);
             }
             GuiValueType::BatteryCurrent => {
-                if let Ok(v) = value.parse::<f64>() {
-                    self.battery_current = v;
-                }
+                self.battery_current = signal.value;
+                self.session.record_battery_current(signal.value);
             }
             GuiValueType::BatteryCharge => {
-                if let Ok(v) = value.parse::<f64>() {
-                    self.battery_charge = v;
-                }
+                self.battery_charge = signal.value;
+                self.session.record_battery_charge(signal.value);
             }
             GuiValueType::BatteryTemp => {
-                if let Ok(v) = value.parse::<f64>() {
-                    self.battery_temp = v;
-                }
+                self.battery_temp = signal.value;
+                self.session.record_battery_temp(signal.value);
             }
             GuiValueType::BatteryTempLo => {
-                if let Ok(v) = value.parse::<f64>() {
-                    self.battery_temp_lo = v;
-                }
+                self.battery_temp_lo = signal.value;
             }
             GuiValueType::BatteryTempHi => {
-                if let Ok(v) = value.parse::<f64>() {
-                    self.battery_temp_hi = v;
-                }
+                self.battery_temp_hi = signal.value;
             }
             GuiValueType::BpsOnTime => {
-                if let Ok(v) = value.parse::<u64>() {
-                    self.bps_ontime = v;
-                }
+                // Decoded values carry the DBC factor, so they can be fractional
+                self.bps_ontime = signal.value * self.bps_ontime_scale;
             }
             GuiValueType::BpsState => {
-                self.bps_state = value.to_string();
+                self.bps_state = signal.display_value();
             }
             // BMS data handling (keeping existing structure)
             GuiValueType::BmsPackDcl => {
@@ -652,40 +1544,330 @@ impl TelemetryGui {
         }
     }
 
+    // Insert or refresh an active fault, counting it for the session summary when it is new.
+    // An acknowledged fault is refreshed in place unless it escalated in severity.
+    fn raise_fault(&mut self, fault_key: String, fault: Fault) {
+        let previous_severity = self
+            .active_faults
+            .get(&fault_key)
+            .or_else(|| self.acknowledged_faults.get(&fault_key))
+            .map(|previous| previous.severity);
+        if previous_severity != Some(fault.severity) {
+            self.log_fault_event(FaultEvent::Set, &fault);
+        }
+
+        if let Some(acknowledged) = self.acknowledged_faults.get_mut(&fault_key) {
+            if fault.severity >= acknowledged.severity {
+                *acknowledged = fault;
+                return;
+            }
+            self.acknowledged_faults.remove(&fault_key);
+        }
+        if !self.active_faults.contains_key(&fault_key) {
+            self.session.record_fault(fault.severity);
+        }
+        if fault.severity == FaultSeverity::Critical
+            && self.audio_alarm
+            && self.alarmed_faults.insert(fault_key.clone())
+        {
+            sound_alarm();
+        }
+        self.active_faults.insert(fault_key, fault);
+    }
+
+    // Clearing also forgets the acknowledgement, so the fault shows again if it recurs
+    fn clear_fault(&mut self, fault_key: &str) {
+        let active = self.active_faults.remove(fault_key);
+        let acknowledged = self.acknowledged_faults.remove(fault_key);
+        self.alarmed_faults.remove(fault_key);
+        if let Some(fault) = active.or(acknowledged) {
+            self.log_fault_event(FaultEvent::Cleared, &fault);
+        }
+    }
+
+    fn log_fault_event(&mut self, event: FaultEvent, fault: &Fault) {
+        if let Some(fault_logger) = &mut self.fault_logger {
+            if let Err(e) = fault_logger.log_event(event, fault) {
+                error!("Failed to write fault log: {}", e);
+            }
+        }
+    }
+
+    fn bps_ontime_scale(decoder: &CanDecoder) -> f64 {
+        match decoder.signal_unit(BPS_THING_ID, "BPS_ON_Time") {
+            Some(unit) => seconds_per_unit(unit).unwrap_or_else(|| {
+                warn!("Unknown BPS_ON_Time unit '{}', assuming seconds", unit);
+                1.0
+            }),
+            None => 1.0,
+        }
+    }
+
+    // Swap in an edited DBC without restarting; a bad file keeps the old definitions and
+    // raises a warning until a reload succeeds
+    fn reload_dbc(&mut self) {
+        let mut decoder = self.decoder.lock().unwrap();
+        match decoder.reload() {
+            Ok(()) => {
+                info!("DBC reloaded");
+                self.bps_ontime_scale = Self::bps_ontime_scale(&decoder);
+                drop(decoder);
+                self.clear_fault(DBC_RELOAD_FAULT_KEY);
+            }
+            Err(e) => {
+                drop(decoder);
+                error!("DBC reload failed, keeping previous definitions: {}", e);
+                self.raise_fault(
+                    DBC_RELOAD_FAULT_KEY.to_string(),
+                    Fault {
+                        name: "DBC reload failed".to_string(),
+                        timestamp: chrono::Utc::now(),
+                        value: e,
+                        message_name: "Decoder".to_string(),
+                        severity: FaultSeverity::Warning,
+                    },
+                );
+            }
+        }
+    }
+
+    // Link health and active faults for WebSocket clients
+    fn publish_ws_status(&self) {
+        let Some(ws) = self.ws.as_ref().filter(|ws| ws.has_clients()) else {
+            return;
+        };
+        let health = self.get_transmission_health();
+        let faults = self
+            .active_faults
+            .values()
+            .chain(self.acknowledged_faults.values())
+            .map(|fault| WsFault {
+                name: &fault.name,
+                message: &fault.message_name,
+                severity: severity_name(fault.severity),
+                value: &fault.value,
+            })
+            .collect();
+
+        ws.publish(&WsEvent::Status {
+            timestamp_ms: now_ms(),
+            can_connected: self.can_connected,
+            rfd_connected: health.rfd_connected && self.rfd_enabled,
+            rfd_failures: health.rfd_failures,
+            rfd_queue: self.serial_manager.get_batch_stats(),
+            faults,
+        });
+    }
+
+    // Current dashboard values; view() renders these unless a paused snapshot is held
+    fn dashboard_snapshot(&self) -> DashboardSnapshot {
+        DashboardSnapshot {
+            battery: BatteryData {
+                voltage: self.battery_voltage,
+                current: self.battery_current,
+                charge: self.battery_charge,
+                temp: self.battery_temp,
+                temp_lo: self.battery_temp_lo,
+                temp_hi: self.battery_temp_hi,
+                energy_wh: self.energy.wh(),
+                stale: self.data_stale(DataGroup::Battery),
+            },
+            bps: BpsData {
+                ontime: self.bps_ontime,
+                state: self.bps_state.clone(),
+                stale: self.data_stale(DataGroup::Bps),
+            },
+            mppt: self.mppt_data.clone(),
+            mppt1_stale: self.data_stale(DataGroup::Mppt1),
+            mppt2_stale: self.data_stale(DataGroup::Mppt2),
+            display_units: self.display_units.clone(),
+            direction: self.direction.clone(),
+            speed_mph: self.speed_mph,
+            session_miles: self.odometer.session_miles(),
+            trip_peak_mph: self.trip_peak_mph,
+            motor_detail: self.get_motor_speed_detail(),
+            power: self.power_data(),
+        }
+    }
+
+    // Net power from the latest MPPT and battery readings, plus consumption per mile when moving
+    fn power_data(&self) -> PowerData {
+        let mppt = &self.mppt_data;
+        let solar_w = mppt.mppt1_output_voltage * mppt.mppt1_output_current
+            + mppt.mppt2_output_voltage * mppt.mppt2_output_current;
+        let battery_w = self.battery_voltage * self.battery_current;
+        let net_w = solar_w + battery_w;
+
+        let (motor1_fresh, motor2_fresh) = self.motor_data_fresh();
+        let wh_per_mile = ((motor1_fresh || motor2_fresh)
+            && self.speed_mph >= MIN_EFFICIENCY_SPEED_MPH)
+            .then(|| net_w / self.speed_mph); // W / mph = Wh per mile
+
+        PowerData {
+            solar_w,
+            battery_w,
+            net_w,
+            wh_per_mile,
+            stale: [DataGroup::Battery, DataGroup::Mppt1, DataGroup::Mppt2]
+                .into_iter()
+                .any(|group| self.data_stale(group)),
+        }
+    }
+
+    // Where session summaries and screenshots go: the CAN log directory, else the working directory
+    fn output_dir(&self) -> std::path::PathBuf {
+        match &self.logger {
+            Some(logger) => logger.log_dir().to_path_buf(),
+            None => std::path::PathBuf::from("."),
+        }
+    }
+
+    // Write the session summary and flush persistent state before the window closes
+    fn finish_session(&mut self) {
+        let (write_failures, connections) = {
+            let status = self.serial_manager.lock_rfd_status();
+            (status.write_failures, status.connections)
+        };
+        let link = LinkSummary {
+            frames_received: self.can_frames_received.load(Ordering::Relaxed),
+            frames_transmitted: self.serial_manager.get_frames_transmitted(),
+            batches_sent: self.serial_manager.get_batches_sent(),
+            write_failures,
+            reconnects: connections.saturating_sub(1), // First connect isn't a reconnect
+        };
+
+        match self
+            .session
+            .write_summary(&self.output_dir(), self.odometer.session_miles(), link)
+        {
+            Ok(path) => info!("Session summary written to {:?}", path),
+            Err(e) => error!("Failed to write session summary: {}", e),
+        }
+        self.odometer.save();
+
+        // Send the last queued frames and stop the modem threads before the process exits
+        self.serial_manager.shutdown();
+        if let Some(logger) = &mut self.logger {
+            if let Err(e) = logger.sync() {
+                error!("Failed to flush CAN log: {}", e);
+            }
+        }
+    }
+
     // Helper method to process regular faults (non-DTC)
-    fn process_regular_fault(&mut self, message_name: &str, signal_name: &str, value: &str) {
+    fn process_regular_fault(&mut self, message_name: &str, signal: &DecodedSignal) {
+        let signal_name = signal.name.as_str();
         let fault_key = format!("{}_{}", message_name, signal_name);
 
-        if is_fault_value(value) {
+        if is_fault_signal(signal) {
             // Fault is active
             let new_fault = Fault {
-                name: signal_name.to_string(),
+                name: get_fault_display_name(message_name, signal_name),
                 timestamp: chrono::Utc::now(),
-                is_active: true,
-                value: value.to_owned(),
+                value: signal.display_value(),
                 message_name: message_name.to_string(),
                 severity: get_fault_severity(message_name, signal_name),
             };
-            self.active_faults.insert(fault_key, new_fault);
+            self.raise_fault(fault_key, new_fault);
         } else {
             // Fault is cleared
-            self.active_faults.remove(&fault_key);
+            self.clear_fault(&fault_key);
         }
     }
 
+    // Helper method to raise/clear "Signal out of range" warnings from DBC min/max
+    fn process_range_fault(&mut self, raw_id: u32, message_name: &str, signal: &DecodedSignal) {
+        if signal.label.is_some() {
+            return; // Value descriptions (enums) have no numeric range
+        }
+        let signal_name = signal.name.as_str();
+        let fault_key = format!("Range_{}_{}", message_name, signal_name);
+
+        // A clamped value is back in range, but the decoder still flags it
+        let out_of_range = if signal.out_of_range {
+            Some(true)
+        } else {
+            self.decoder
+                .lock()
+                .unwrap()
+                .is_value_out_of_range(raw_id, signal_name, signal.value)
+        };
+        match out_of_range {
+            Some(true) => {
+                let new_fault = Fault {
+                    name: format!("Signal out of range: {}", signal_name),
+                    timestamp: chrono::Utc::now(),
+                    value: signal.display_value(),
+                    message_name: message_name.to_string(),
+                    severity: FaultSeverity::Warning,
+                };
+                self.raise_fault(fault_key, new_fault);
+            }
+            _ => {
+                self.clear_fault(&fault_key);
+            }
+        }
+    }
+
+    // Raise a Warning below the low-disk threshold, Critical below a tenth of it
+    fn check_log_disk_space(&mut self) {
+        let Some(logger) = &self.logger else {
+            return;
+        };
+        let Some(free_bytes) = free_disk_bytes(logger.log_dir()) else {
+            return;
+        };
+
+        let severity = if free_bytes < self.low_disk_bytes / 10 {
+            FaultSeverity::Critical
+        } else if free_bytes < self.low_disk_bytes {
+            FaultSeverity::Warning
+        } else {
+            self.clear_fault(LOW_DISK_FAULT_KEY);
+            return;
+        };
+
+        let free_mb = free_bytes / (1024 * 1024);
+        let previous = self
+            .active_faults
+            .get(LOW_DISK_FAULT_KEY)
+            .or_else(|| self.acknowledged_faults.get(LOW_DISK_FAULT_KEY));
+        if previous.map(|f| f.severity) != Some(severity) {
+            warn!("⚠ Log disk low: {} MB free", free_mb);
+        }
+        self.raise_fault(
+            LOW_DISK_FAULT_KEY.to_string(),
+            Fault {
+                name: "Log disk low".to_string(),
+                timestamp: chrono::Utc::now(),
+                value: format!("{} MB free", free_mb),
+                message_name: "Logger".to_string(),
+                severity,
+            },
+        );
+    }
+
+    // Whether `group` was updated within `max_age` (never-updated groups are not fresh)
+    fn data_fresh(&self, group: DataGroup, max_age: Duration) -> bool {
+        self.data_last_update
+            .get(&group)
+            .is_some_and(|t| t.elapsed() < max_age)
+    }
+
+    // Whether each motor's speed data is recent enough to trust
+    fn motor_data_fresh(&self) -> (bool, bool) {
+        (
+            self.data_fresh(DataGroup::Motor1, MOTOR_DATA_MAX_AGE),
+            self.data_fresh(DataGroup::Motor2, MOTOR_DATA_MAX_AGE),
+        )
+    }
+
+    fn data_stale(&self, group: DataGroup) -> bool {
+        !self.data_fresh(group, self.data_stale_after)
+    }
+
     fn update_vehicle_speed(&mut self) {
-        // Check data freshness (optional - helps with stale data)
-        let now = std::time::Instant::now();
-        let max_age = std::time::Duration::from_millis(250); // 250ms max age for more responsive updates
-
-        let motor1_fresh = self
-            .motor1_last_update
-            .map(|t| now.duration_since(t) < max_age)
-            .unwrap_or(false);
-        let motor2_fresh = self
-            .motor2_last_update
-            .map(|t| now.duration_since(t) < max_age)
-            .unwrap_or(false);
+        let (motor1_fresh, motor2_fresh) = self.motor_data_fresh();
 
         // Calculate speed using available data
         let (motor1_rpm, motor2_rpm) = match (motor1_fresh, motor2_fresh) {
@@ -701,7 +1883,6 @@ impl TelemetryGui {
 
     fn calculate_dual_motor_speed(&self, motor1_rpm: f64, motor2_rpm: f64) -> f64 {
         let min_threshold = 5.0; // Lower threshold for more sensitive speed detection
-        let wheel_diameter = 23.5; // Make this configurable later
 
         // Filter out noise
         let motor1_filtered = if motor1_rpm.abs() >= min_threshold {
@@ -726,9 +1907,30 @@ impl TelemetryGui {
             0.0 // No motors active
         };
 
+        self.rpm_to_mph(avg_rpm)
+    }
+
+    fn rpm_to_mph(&self, rpm: f64) -> f64 {
         // Convert to MPH
-        let wheel_circumference = wheel_diameter * std::f64::consts::PI;
-        (avg_rpm * wheel_circumference * 60.0) / 63360.0
+        let wheel_circumference = self.wheel_diameter_inches * std::f64::consts::PI;
+        (rpm * wheel_circumference * 60.0) / 63360.0
+    }
+
+    fn get_motor_speed_detail(&self) -> MotorSpeedDetail {
+        let (motor1_fresh, motor2_fresh) = self.motor_data_fresh();
+        let motor1_mph = self.rpm_to_mph(self.motor1_speed_rpm);
+        let motor2_mph = self.rpm_to_mph(self.motor2_speed_rpm);
+
+        MotorSpeedDetail {
+            motor1_rpm: self.motor1_speed_rpm,
+            motor2_rpm: self.motor2_speed_rpm,
+            motor1_mph,
+            motor2_mph,
+            // Only compare live readings; a stale motor would always look divergent
+            diverging: motor1_fresh
+                && motor2_fresh
+                && (motor1_mph.abs() - motor2_mph.abs()).abs() > MOTOR_DIVERGENCE_MPH,
+        }
     }
 
     fn update_vehicle_direction(&mut self) {
@@ -746,24 +1948,116 @@ impl TelemetryGui {
         };
     }
 
-    // OPTIONAL: Debugging and monitoring methods
-    pub fn enable_batching(&self, enabled: bool) {
-        self.serial_manager.enable_batching(enabled);
-        println!("Batching {}", if enabled { "enabled" } else { "disabled" });
+    // How much of the bus traffic actually makes it over RF after filtering and latest-wins batching
+    fn rf_reduction_summary(&self) -> String {
+        let received = self.can_frames_received.load(Ordering::Relaxed);
+        let sent = self.serial_manager.get_frames_transmitted();
+        let percent = if received > 0 {
+            sent as f64 * 100.0 / received as f64
+        } else {
+            0.0
+        };
+
+        format!(
+            "RF reduction: received {}, sent {} ({:.1}% of bus)",
+            received, sent, percent
+        )
+    }
+
+    // Rate-filter drops per CAN ID, most-filtered first
+    fn filtered_by_id(&self) -> Vec<(u32, u64)> {
+        let mut counts: Vec<_> = self
+            .serial_manager
+            .get_filtered_counts()
+            .into_iter()
+            .collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        counts
+    }
+
+    fn message_rate_rows(&self) -> Vec<MessageRateRow> {
+        let mut rows: Vec<MessageRateRow> = self
+            .message_rates
+            .iter()
+            .map(|(id, tracker)| MessageRateRow {
+                id: *id,
+                message_name: tracker.message_name.clone(),
+                last_seen_ms: tracker.last_seen_ms,
+                hz: tracker.hz(),
+                stale: tracker.is_stale(MESSAGE_STALE_AFTER),
+            })
+            .collect();
+        rows.sort_by_key(|row| row.id);
+        rows
     }
 
+    // Snapshot of subsystem liveness for the diagnostics screen
+    fn get_diagnostics_data(&self) -> DiagnosticsData {
+        let can_running = self.can_heartbeat.last_tick_ms().is_some();
+        let health = self.get_transmission_health();
+
+        DiagnosticsData {
+            subsystems: vec![
+                SubsystemHealth {
+                    name: "CAN subscription",
+                    last_activity_ms: self.can_heartbeat.last_tick_ms(),
+                    status: self.can_heartbeat.status(can_running, CAN_STALL_WINDOW),
+                },
+                SubsystemHealth {
+                    name: "Scan thread",
+                    last_activity_ms: self.serial_manager.scan_heartbeat().last_tick_ms(),
+                    status: self
+                        .serial_manager
+                        .scan_heartbeat()
+                        .status(self.serial_manager.is_scanning(), SCAN_STALL_WINDOW),
+                },
+                SubsystemHealth {
+                    name: "Batch thread",
+                    last_activity_ms: self.serial_manager.batch_heartbeat().last_tick_ms(),
+                    status: self
+                        .serial_manager
+                        .batch_heartbeat()
+                        .status(self.serial_manager.is_batching(), BATCH_STALL_WINDOW),
+                },
+            ],
+            rfd_connected: health.rfd_connected && self.rfd_enabled,
+            rfd_failures: health.rfd_failures,
+            rfd_queue: self.get_batching_stats(),
+            rfd_error: health.rfd_error,
+            rfd_rtt_ms: health.rfd_rtt.map(|rtt| rtt.as_secs_f64() * 1000.0),
+            probes_sent: health.probes_sent,
+            probes_echoed: health.probes_echoed,
+            rf_frames_received: self.rf_frames_received,
+            rf_loss: self.serial_manager.packet_loss_estimate(),
+            rf_reduction: self.rf_reduction_summary(),
+            tx_stats: self.serial_manager.get_tx_stats(),
+            filtered_by_id: self.filtered_by_id(),
+            session_miles: self.odometer.session_miles(),
+            lifetime_miles: self.odometer.lifetime_miles(),
+            energy_wh: self.energy.wh(),
+            can_bitrate: self.can_bitrate,
+            detected_can_bitrate: self.detected_can_bitrate,
+            undecoded_ids: self.undecoded_ids.iter().copied().collect(),
+            max_frame_time_us: frame_time_us(8, true, self.can_bitrate),
+        }
+    }
+
+    // OPTIONAL: Debugging and monitoring methods
     pub fn get_batching_stats(&self) -> usize {
         self.serial_manager.get_batch_stats()
     }
 
     // Get detailed transmission health information
     pub fn get_transmission_health(&self) -> TransmissionHealth {
-        let rfd_status = self.serial_manager.rfd_status.lock().unwrap();
+        let rfd_status = self.serial_manager.lock_rfd_status();
 
         TransmissionHealth {
             rfd_connected: rfd_status.connected,
             rfd_failures: rfd_status.consecutive_failures,
-            rfd_last_success: rfd_status.last_success,
+            rfd_error: rfd_status.error_message.clone(),
+            rfd_rtt: rfd_status.last_rtt,
+            probes_sent: rfd_status.probes_sent,
+            probes_echoed: rfd_status.probes_echoed,
         }
     }
 }
@@ -772,5 +2066,8 @@ impl TelemetryGui {
 pub struct TransmissionHealth {
     pub rfd_connected: bool,
     pub rfd_failures: u32,
-    pub rfd_last_success: Option<std::time::Instant>,
+    pub rfd_error: Option<String>,
+    pub rfd_rtt: Option<Duration>,
+    pub probes_sent: u64,
+    pub probes_echoed: u64,
 }
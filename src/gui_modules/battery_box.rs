@@ -1,4 +1,4 @@
-use crate::gui_modules::Message;
+use crate::gui_modules::{data_title, value_text_style, DisplayUnits, GuiValueType, Message};
 use iced::widget::{column, container, text};
 use iced::{Alignment, Element, Length};
 
@@ -10,19 +10,37 @@ pub struct BatteryData {
     pub temp: f64,
     pub temp_hi: f64,
     pub temp_lo: f64,
+    pub energy_wh: f64, // Since the last ResetEnergy
+    pub stale: bool,    // No BMS data within the stale timeout (or ever)
 }
 
-pub fn battery_box(data: &BatteryData) -> Element<'static, Message> {
+pub fn battery_box(data: &BatteryData, units: &DisplayUnits) -> Element<'static, Message> {
     container(
         column![
-            text("Battery Info").size(20),
-            text(format!("Voltage: {:.1} V", data.voltage)),
-            text(format!("Current: {:.1} A", data.current)),
-            text(format!("Charge: {:.1} %", data.charge)),
+            text(data_title("Battery Info", data.stale)).size(20),
             text(format!(
-                "Temp Avg: {:.1} | Hi: {:.1} | Lo: {:.1} °C",
-                data.temp, data.temp_hi, data.temp_lo
-            )),
+                "Voltage: {}",
+                units.format(&GuiValueType::BatteryVoltage, data.voltage)
+            ))
+            .style(value_text_style(data.stale)),
+            text(format!(
+                "Current: {}",
+                units.format(&GuiValueType::BatteryCurrent, data.current)
+            ))
+            .style(value_text_style(data.stale)),
+            text(format!(
+                "Charge: {}",
+                units.format(&GuiValueType::BatteryCharge, data.charge)
+            ))
+            .style(value_text_style(data.stale)),
+            text(format!(
+                "Temp Avg: {} | Hi: {} | Lo: {}",
+                units.format(&GuiValueType::BatteryTemp, data.temp),
+                units.format(&GuiValueType::BatteryTempHi, data.temp_hi),
+                units.format(&GuiValueType::BatteryTempLo, data.temp_lo)
+            ))
+            .style(value_text_style(data.stale)),
+            text(format!("Energy: {:.1} Wh", data.energy_wh)),
         ]
         .spacing(5)
         .align_items(Alignment::Start),
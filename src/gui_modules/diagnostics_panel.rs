@@ -0,0 +1,326 @@
+use crate::gui_modules::Message;
+use crate::health::{now_ms, SubsystemStatus};
+use crate::serial::TxStats;
+use chrono::{Local, TimeZone};
+use iced::widget::container::StyleSheet;
+use iced::widget::{button, column, container, row, scrollable, text};
+use iced::{Alignment, Color, Element, Length};
+
+#[derive(Clone)]
+pub struct SubsystemHealth {
+    pub name: &'static str,
+    pub last_activity_ms: Option<u64>,
+    pub status: SubsystemStatus,
+}
+
+#[derive(Clone)]
+pub struct DiagnosticsData {
+    pub subsystems: Vec<SubsystemHealth>,
+    pub rfd_connected: bool,
+    pub rfd_failures: u32,
+    pub rfd_queue: usize,
+    pub rfd_rtt_ms: Option<f64>, // Last echoed probe round trip
+    pub probes_sent: u64,
+    pub probes_echoed: u64,
+    pub rf_frames_received: u64,
+    pub rf_loss: f64, // Estimated from per-ID sequence gaps in received frames (0.0-1.0)
+    pub rf_reduction: String,
+    pub tx_stats: TxStats,
+    pub filtered_by_id: Vec<(u32, u64)>, // Most-filtered IDs first
+    // Why the modem isn't connected (e.g. serial port enumeration failing)
+    pub rfd_error: Option<String>,
+    pub session_miles: f64,
+    pub lifetime_miles: f64,
+    pub energy_wh: f64,
+    pub can_bitrate: u32,
+    pub detected_can_bitrate: Option<u32>,
+    pub max_frame_time_us: f64, // Worst-case 8-byte extended frame at the configured bitrate
+    pub undecoded_ids: Vec<u32>, // Not in the DBC and not listed in --known-unknown-ids
+}
+
+// One observed CAN ID on the message rate page
+#[derive(Clone)]
+pub struct MessageRateRow {
+    pub id: u32,
+    pub message_name: String,
+    pub last_seen_ms: u64,
+    pub hz: f64,
+    pub stale: bool, // Not seen within the stale window
+}
+
+fn status_style(status: SubsystemStatus) -> iced::theme::Container {
+    let background = match status {
+        SubsystemStatus::Ok => Color::from_rgb(0.0, 0.8, 0.0),
+        SubsystemStatus::Stalled => Color::from_rgb(1.0, 0.8, 0.0),
+        SubsystemStatus::Stopped => Color::from_rgb(0.8, 0.0, 0.0),
+    };
+    let text_color = match status {
+        SubsystemStatus::Stalled => Color::BLACK,
+        _ => Color::WHITE,
+    };
+
+    iced::theme::Container::Custom(Box::new(move |theme: &iced::Theme| {
+        let mut appearance = theme.appearance(&iced::theme::Container::Box);
+        appearance.background = Some(background.into());
+        appearance.text_color = Some(text_color);
+        appearance
+    }))
+}
+
+fn format_last_activity(last_activity_ms: Option<u64>) -> String {
+    match last_activity_ms {
+        Some(ms) => {
+            let age_secs = now_ms().saturating_sub(ms) as f64 / 1000.0;
+            let wall_clock = Local
+                .timestamp_millis_opt(ms as i64)
+                .single()
+                .map(|t| t.format("%H:%M:%S").to_string())
+                .unwrap_or_else(|| "--:--:--".to_string());
+            format!("{} ({:.1}s ago)", wall_clock, age_secs)
+        }
+        None => "never".to_string(),
+    }
+}
+
+pub fn diagnostics_panel(data: &DiagnosticsData) -> Element<'static, Message> {
+    let header = container(
+        row![
+            text("Subsystem").size(14).width(Length::FillPortion(2)),
+            text("Last Activity").size(14).width(Length::FillPortion(2)),
+            text("Status").size(14).width(Length::FillPortion(1)),
+        ]
+        .spacing(5)
+        .padding(3),
+    )
+    .width(Length::Fill)
+    .style(iced::theme::Container::Custom(Box::new(
+        |theme: &iced::Theme| {
+            let mut appearance = theme.appearance(&iced::theme::Container::Box);
+            appearance.background = Some(Color::from_rgb(0.2, 0.2, 0.2).into());
+            appearance.text_color = Some(Color::WHITE);
+            appearance
+        },
+    )));
+
+    let mut subsystem_list = column![header].spacing(2);
+
+    for subsystem in &data.subsystems {
+        let subsystem_row = row![
+            text(subsystem.name).size(14).width(Length::FillPortion(2)),
+            text(format_last_activity(subsystem.last_activity_ms))
+                .size(14)
+                .width(Length::FillPortion(2)),
+            container(
+                text(subsystem.status.label())
+                    .size(14)
+                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+            )
+            .padding(2)
+            .width(Length::FillPortion(1))
+            .style(status_style(subsystem.status)),
+        ]
+        .spacing(5)
+        .padding(3)
+        .align_items(Alignment::Center);
+
+        subsystem_list = subsystem_list.push(subsystem_row);
+    }
+
+    let detected = match data.detected_can_bitrate {
+        Some(actual) if actual != data.can_bitrate => {
+            format!("{} kbit/s (MISMATCH)", actual / 1000)
+        }
+        Some(actual) => format!("{} kbit/s", actual / 1000),
+        None => "unknown".to_string(),
+    };
+    let undecoded = if data.undecoded_ids.is_empty() {
+        "none".to_string()
+    } else {
+        data.undecoded_ids
+            .iter()
+            .map(|id| format!("0x{:X}", id))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let bus_info = column![
+        text("CAN Bus").size(16),
+        text(format!(
+            "Bitrate: {} kbit/s configured, {} detected | Max frame time: {:.0} us",
+            data.can_bitrate / 1000,
+            detected,
+            data.max_frame_time_us
+        ))
+        .size(14),
+        text(format!("Undecoded IDs: {}", undecoded)).size(14),
+        button(text("Message Rates").size(12))
+            .on_press(Message::ToggleMessageRates)
+            .padding(4),
+    ]
+    .spacing(4);
+
+    let mut link_info = column![
+        text("RFD Link").size(16),
+        text(format!(
+            "Connected: {} | Consecutive failures: {} | Queued frames: {}",
+            if data.rfd_connected { "YES" } else { "NO" },
+            data.rfd_failures,
+            data.rfd_queue
+        ))
+        .size(14),
+        text(format!(
+            "Round trip: {} | Probes echoed: {}/{}",
+            data.rfd_rtt_ms
+                .map(|ms| format!("{:.0} ms", ms))
+                .unwrap_or_else(|| "no echo".to_string()),
+            data.probes_echoed,
+            data.probes_sent
+        ))
+        .size(14),
+        text(format!(
+            "Received: {} frames | Estimated loss: {:.1}%",
+            data.rf_frames_received,
+            data.rf_loss * 100.0
+        ))
+        .size(14),
+        text(&data.rf_reduction).size(14),
+        text(format!(
+            "Throughput: {:.1} frames/s, {:.0} B/s | Batches: {} | Rate-filtered: {} | Overflowed: {} | Preserved dropped: {}",
+            data.tx_stats.frames_per_sec,
+            data.tx_stats.bytes_per_sec,
+            data.tx_stats.batches_sent,
+            data.tx_stats.frames_filtered,
+            data.tx_stats.frames_overflowed,
+            data.tx_stats.preserved_dropped
+        ))
+        .size(14),
+    ]
+    .spacing(4);
+    if !data.filtered_by_id.is_empty() {
+        let ids: Vec<String> = data
+            .filtered_by_id
+            .iter()
+            .take(5)
+            .map(|(id, count)| format!("0x{:X}: {}", id, count))
+            .collect();
+        link_info = link_info.push(text(format!("Filtered by ID: {}", ids.join(", "))).size(14));
+    }
+    if let Some(error) = &data.rfd_error {
+        link_info = link_info.push(text(format!("Error: {}", error)).size(14));
+    }
+
+    let distance_info = column![
+        text("Distance").size(16),
+        row![
+            text(format!(
+                "Session: {:.2} mi | Lifetime: {:.1} mi",
+                data.session_miles, data.lifetime_miles
+            ))
+            .size(14),
+            button(text("Reset Trip").size(12))
+                .on_press(Message::ResetTrip)
+                .padding(4),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+        row![
+            text(format!("Energy: {:.1} Wh", data.energy_wh)).size(14),
+            button(text("Reset Energy").size(12))
+                .on_press(Message::ResetEnergy)
+                .padding(4),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+    ]
+    .spacing(4);
+
+    container(
+        column![
+            text("Diagnostics").size(20),
+            subsystem_list,
+            bus_info,
+            link_info,
+            distance_info
+        ]
+        .spacing(10)
+        .align_items(Alignment::Start),
+    )
+    .padding(10)
+    .width(Length::Fill)
+    .style(iced::theme::Container::Box)
+    .into()
+}
+
+// Every CAN ID seen this session, sorted by ID; stale IDs are highlighted
+pub fn message_rates_panel(rows: &[MessageRateRow]) -> Element<'static, Message> {
+    let header = container(
+        row![
+            text("CAN ID").size(14).width(Length::FillPortion(1)),
+            text("Message").size(14).width(Length::FillPortion(2)),
+            text("Last Seen").size(14).width(Length::FillPortion(2)),
+            text("Rate").size(14).width(Length::FillPortion(1)),
+        ]
+        .spacing(5)
+        .padding(3),
+    )
+    .width(Length::Fill)
+    .style(iced::theme::Container::Custom(Box::new(
+        |theme: &iced::Theme| {
+            let mut appearance = theme.appearance(&iced::theme::Container::Box);
+            appearance.background = Some(Color::from_rgb(0.2, 0.2, 0.2).into());
+            appearance.text_color = Some(Color::WHITE);
+            appearance
+        },
+    )));
+
+    let mut id_list = column![].spacing(2);
+    for rate in rows {
+        let id_row = container(
+            row![
+                text(format!("0x{:X}", rate.id))
+                    .size(14)
+                    .width(Length::FillPortion(1)),
+                text(&rate.message_name)
+                    .size(14)
+                    .width(Length::FillPortion(2)),
+                text(format_last_activity(Some(rate.last_seen_ms)))
+                    .size(14)
+                    .width(Length::FillPortion(2)),
+                text(format!("{:.1} Hz", rate.hz))
+                    .size(14)
+                    .width(Length::FillPortion(1)),
+            ]
+            .spacing(5)
+            .padding(3)
+            .align_items(Alignment::Center),
+        )
+        .width(Length::Fill);
+
+        id_list = id_list.push(if rate.stale {
+            id_row.style(status_style(SubsystemStatus::Stalled))
+        } else {
+            id_row
+        });
+    }
+
+    container(
+        column![
+            row![
+                text(format!("Message Rates ({} IDs)", rows.len())).size(20),
+                button(text("Back").size(12))
+                    .on_press(Message::ToggleMessageRates)
+                    .padding(4),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+            header,
+            scrollable(id_list).height(Length::Fill),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Start),
+    )
+    .padding(10)
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .style(iced::theme::Container::Box)
+    .into()
+}
@@ -1,14 +1,17 @@
-use crate::gui_modules::{get_fault_container_style, Fault, FaultSeverity, Message};
+use crate::gui_modules::{get_fault_container_style, Fault, FaultPalette, FaultSeverity, Message};
 use iced::widget::container::StyleSheet;
-use iced::widget::{column, container, row, text, Space};
+use iced::widget::{button, column, container, row, text, Space};
 use iced::{Alignment, Color, Element, Length};
 use std::collections::HashMap;
 
-const FAULTS_PER_PAGE: usize = 3;
-
+// Tapping a fault row acknowledges it (Message::AcknowledgeFault). Severities are colored
+// from `palette` (--palette).
 pub fn fault_display(
     active_faults: &HashMap<String, Fault>,
+    acknowledged_count: usize,
     current_page: usize,
+    faults_per_page: usize,
+    palette: &FaultPalette,
 ) -> Element<'static, Message> {
     let fault_count = active_faults.len();
 
@@ -16,10 +19,12 @@ pub fn fault_display(
     let total_pages = if fault_count == 0 {
         0
     } else {
-        (fault_count + FAULTS_PER_PAGE - 1) / FAULTS_PER_PAGE // Ceiling division
+        fault_count.div_ceil(faults_per_page)
     };
+    // Faults may have cleared since the page was chosen
+    let current_page = current_page.min(total_pages.saturating_sub(1));
 
-    let page_info = if total_pages > 1 {
+    let mut page_info = if total_pages > 1 {
         format!(
             "ACTIVE FAULTS: {} (Page {}/{})",
             fault_count,
@@ -29,6 +34,9 @@ pub fn fault_display(
     } else {
         format!("ACTIVE FAULTS: {}", fault_count)
     };
+    if acknowledged_count > 0 {
+        page_info.push_str(&format!(" | {} acknowledged", acknowledged_count));
+    }
 
     // Header showing fault count and page info - will be updated after determining most severe
     let header_text = text(page_info)
@@ -58,8 +66,8 @@ pub fn fault_display(
     }
 
     // Sort faults by severity first, then by timestamp (most recent first)
-    let mut faults_vec: Vec<_> = active_faults.values().collect();
-    faults_vec.sort_by(|a, b| {
+    let mut faults_vec: Vec<_> = active_faults.iter().collect();
+    faults_vec.sort_by(|(_, a), (_, b)| {
         // First sort by severity (Critical -> Error -> Warning)
         match a.severity.cmp(&b.severity) {
             std::cmp::Ordering::Equal => {
@@ -73,12 +81,12 @@ pub fn fault_display(
     // Determine the most severe fault level for header styling
     let most_severe = faults_vec
         .first()
-        .map(|f| &f.severity)
+        .map(|(_, f)| &f.severity)
         .unwrap_or(&FaultSeverity::Error);
 
     // Calculate the range of faults to display for current page
-    let start_index = current_page * FAULTS_PER_PAGE;
-    let end_index = std::cmp::min(start_index + FAULTS_PER_PAGE, fault_count);
+    let start_index = current_page * faults_per_page;
+    let end_index = std::cmp::min(start_index + faults_per_page, fault_count);
 
     // Get the faults for the current page
     let current_page_faults = &faults_vec[start_index..end_index];
@@ -86,41 +94,26 @@ pub fn fault_display(
     // Create list of faults for current page
     let mut fault_list = column![];
 
-    for (idx, fault) in current_page_faults.iter().enumerate() {
+    for (idx, (fault_key, fault)) in current_page_faults.iter().enumerate() {
         // Create severity-based styling with alternating opacity
-        let fault_style = match fault.severity {
-            FaultSeverity::Warning => {
-                let opacity = if idx % 2 == 0 { 0.15 } else { 0.1 };
-                iced::theme::Container::Custom(Box::new(move |theme: &iced::Theme| {
-                    let mut appearance = theme.appearance(&iced::theme::Container::Box);
-                    appearance.background =
-                        Some(Color::from_rgba(238.0, 210.0, 2.0, opacity).into());
-                    appearance.border.color = Color::from_rgb(238.0, 210.0, 2.0);
-                    appearance.border.width = 1.0;
-                    appearance
-                }))
-            }
-            FaultSeverity::Error => {
-                let opacity = if idx % 2 == 0 { 0.1 } else { 0.05 };
-                iced::theme::Container::Custom(Box::new(move |theme: &iced::Theme| {
-                    let mut appearance = theme.appearance(&iced::theme::Container::Box);
-                    appearance.background = Some(Color::from_rgba(0.8, 0.0, 0.0, opacity).into());
-                    appearance.border.color = Color::from_rgb(0.8, 0.0, 0.0);
-                    appearance.border.width = 1.0;
-                    appearance
-                }))
-            }
-            FaultSeverity::Critical => {
-                let opacity = if idx % 2 == 0 { 0.2 } else { 0.15 };
-                iced::theme::Container::Custom(Box::new(move |theme: &iced::Theme| {
-                    let mut appearance = theme.appearance(&iced::theme::Container::Box);
-                    appearance.background = Some(Color::from_rgba(1.0, 0.0, 0.0, opacity).into());
-                    appearance.border.color = Color::from_rgb(1.0, 0.0, 0.0);
-                    appearance.border.width = 2.0; // Thicker border for critical
-                    appearance
-                }))
-            }
+        let opacity = match fault.severity {
+            FaultSeverity::Warning => [0.15, 0.1],
+            FaultSeverity::Error => [0.1, 0.05],
+            FaultSeverity::Critical => [0.2, 0.15],
+        }[idx % 2];
+        let border_width = match fault.severity {
+            FaultSeverity::Critical => 2.0, // Thicker border for critical
+            _ => 1.0,
         };
+        let accent = palette.colors(fault.severity).accent;
+        let tint = Color::from_rgba(accent.r, accent.g, accent.b, opacity);
+        let fault_style = iced::theme::Container::Custom(Box::new(move |theme: &iced::Theme| {
+            let mut appearance = theme.appearance(&iced::theme::Container::Box);
+            appearance.background = Some(tint.into());
+            appearance.border.color = accent;
+            appearance.border.width = border_width;
+            appearance
+        }));
 
         let fault_row = container(
             row![
@@ -153,11 +146,15 @@ pub fn fault_display(
         .width(Length::Fill)
         .style(fault_style);
 
+        let fault_row = button(fault_row)
+            .padding(0)
+            .style(iced::theme::Button::Text)
+            .on_press(Message::AcknowledgeFault(fault_key.to_string()));
         fault_list = fault_list.push(fault_row);
     }
 
-    // Add empty rows to maintain consistent height (always show space for 5 rows)
-    let empty_rows_needed = FAULTS_PER_PAGE - current_page_faults.len();
+    // Add empty rows to maintain consistent height (always show space for a full page)
+    let empty_rows_needed = faults_per_page - current_page_faults.len();
     for _i in 0..empty_rows_needed {
         let empty_row = container(
             row![
@@ -189,9 +186,11 @@ pub fn fault_display(
     .width(Length::Fill)
     .style(iced::theme::Container::Custom(Box::new(
         |theme: &iced::Theme| {
+            // Strong background shade of the active theme, so the header reads in dark and light
+            let strong = theme.extended_palette().background.strong;
             let mut appearance = theme.appearance(&iced::theme::Container::Box);
-            appearance.background = Some(Color::from_rgb(0.2, 0.2, 0.2).into());
-            appearance.text_color = Some(Color::WHITE);
+            appearance.background = Some(strong.color.into());
+            appearance.text_color = Some(strong.text);
             appearance
         },
     )));
@@ -200,7 +199,7 @@ pub fn fault_display(
     let header = container(header_text)
         .width(Length::Fill)
         .padding(5)
-        .style(get_fault_container_style(most_severe));
+        .style(get_fault_container_style(most_severe, palette));
 
     // Combine everything - no scrollable needed since we limit to 5 faults
     column![
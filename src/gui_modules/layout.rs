@@ -2,23 +2,30 @@ use crate::gui_modules::Message;
 use iced::widget::{button, column, container, row, text, Space};
 use iced::{Alignment, Element, Length};
 
-pub fn main_layout<'a>(
+fn top_row<'a>(
     is_fullscreen: bool,
+    showing_diagnostics: bool,
     can_status: Element<'a, Message>,
     radio_status: Element<'a, Message>,
-    mppt_bps_info: Element<'a, Message>,
-    speed_direction: Element<'a, Message>,
-    battery_info: Element<'a, Message>,
-    fault_display: Element<'a, Message>,
-    time_display: Element<'a, Message>,
-    warning_indicator: Option<Element<'a, Message>>,
 ) -> Element<'a, Message> {
-    // Top row: CAN status (left), spacer, radio status, fullscreen button (right)
-    let top_row = container(
+    // Top row: CAN status (left), spacer, radio status, screen/fullscreen buttons (right)
+    container(
         row![
             can_status,
             Space::with_width(Length::Fill),
             radio_status,
+            container(
+                button(
+                    text(if showing_diagnostics {
+                        "Dashboard"
+                    } else {
+                        "Diagnostics"
+                    })
+                    .size(14)
+                )
+                .on_press(Message::ToggleDiagnostics),
+            )
+            .width(Length::Shrink),
             container(
                 button(
                     text(if is_fullscreen {
@@ -37,7 +44,24 @@ pub fn main_layout<'a>(
     )
     .width(Length::Fill)
     .height(Length::Fixed(40.0))
-    .padding([5, 10]);
+    .padding([5, 10])
+    .into()
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn main_layout<'a>(
+    is_fullscreen: bool,
+    can_status: Element<'a, Message>,
+    radio_status: Element<'a, Message>,
+    mppt_bps_info: Element<'a, Message>,
+    speed_direction: Element<'a, Message>,
+    battery_info: Element<'a, Message>,
+    power_flow: Element<'a, Message>,
+    fault_display: Element<'a, Message>,
+    time_display: Element<'a, Message>,
+    warning_indicator: Option<Element<'a, Message>>,
+) -> Element<'a, Message> {
+    let top_row = top_row(is_fullscreen, false, can_status, radio_status);
 
     // Main info row: MPPT/BPS info (left), speed/direction (center), battery info (right)
     // Fixed height to prevent shrinking
@@ -50,6 +74,12 @@ pub fn main_layout<'a>(
     .height(Length::Fixed(220.0)) // Fixed height for consistency
     .padding([5, 10]);
 
+    // Solar / battery / net power strip under the main info
+    let power_row = container(power_flow)
+        .width(Length::Fill)
+        .height(Length::Fixed(30.0))
+        .padding([0, 10]);
+
     // Fault display row with fixed container height
     // The fault panel inside can scroll, but the container stays the same size
     let fault_row = container(fault_display)
@@ -77,6 +107,7 @@ pub fn main_layout<'a>(
     column![
         top_row,
         main_info_row,
+        power_row,
         fault_row,
         Space::with_height(Length::Fill), // This will absorb any extra space
         bottom_row,
@@ -86,3 +117,30 @@ pub fn main_layout<'a>(
     .height(Length::Fill)
     .into()
 }
+
+pub fn diagnostics_layout<'a>(
+    is_fullscreen: bool,
+    can_status: Element<'a, Message>,
+    radio_status: Element<'a, Message>,
+    diagnostics: Element<'a, Message>,
+    time_display: Element<'a, Message>,
+) -> Element<'a, Message> {
+    let top_row = top_row(is_fullscreen, true, can_status, radio_status);
+
+    let diagnostics_row = container(diagnostics).width(Length::Fill).padding([0, 10]);
+
+    let bottom_row = container(time_display)
+        .width(Length::Fill)
+        .height(Length::Fixed(50.0));
+
+    column![
+        top_row,
+        diagnostics_row,
+        Space::with_height(Length::Fill),
+        bottom_row,
+    ]
+    .spacing(10)
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
@@ -1,18 +1,21 @@
 // Export all components and types
 mod battery_box;
-mod bms_info_box;
+mod diagnostics_panel;
 mod fault_panel;
 mod layout;
 mod mppt_info_box;
+mod power_box;
 mod radio_status;
 mod status_box;
 mod types;
 
 // Re-export for easy import
 pub use battery_box::*;
+pub use diagnostics_panel::*;
 pub use fault_panel::*;
 pub use layout::*;
 pub use mppt_info_box::*;
+pub use power_box::*;
 pub use radio_status::*;
 pub use status_box::*;
 pub use types::*;
@@ -1,4 +1,5 @@
-use crate::gui_modules::Message;
+use crate::can::DecodedSignal;
+use crate::gui_modules::{data_title, value_text_style, DisplayUnits, GuiValueType, Message};
 use iced::widget::{column, container, row, text};
 use iced::{Alignment, Element, Length};
 
@@ -12,6 +13,8 @@ pub struct MpptData {
     pub mppt2_input_current: f64,
     pub mppt2_output_voltage: f64,
     pub mppt2_output_current: f64,
+    pub mppt1_mode: String, // Operating mode from the DBC value table (e.g. "Const In Voltage")
+    pub mppt2_mode: String,
 }
 
 impl Default for MpptData {
@@ -25,51 +28,87 @@ impl Default for MpptData {
             mppt2_input_current: 0.0,
             mppt2_output_voltage: 0.0,
             mppt2_output_current: 0.0,
+            mppt1_mode: "--".to_string(),
+            mppt2_mode: "--".to_string(),
         }
     }
 }
 
+// Decoded MPPT mode is the DBC description; fall back to the raw code for undescribed values
+pub fn mppt_mode_label(signal: &DecodedSignal) -> String {
+    match &signal.label {
+        Some(label) => label.clone(),
+        None => format!("Mode {}", signal.raw),
+    }
+}
+
 #[derive(Clone)]
 pub struct BpsData {
-    pub ontime: u64,
+    pub ontime: f64, // Seconds
     pub state: String,
+    pub stale: bool,
 }
 
-pub fn mppt_info_box(data: &MpptData, bps_data: &BpsData) -> Element<'static, Message> {
+// `mppt1_stale`/`mppt2_stale`: no data from that MPPT within the stale timeout (or ever)
+pub fn mppt_info_box(
+    data: &MpptData,
+    bps_data: &BpsData,
+    units: &DisplayUnits,
+    mppt1_stale: bool,
+    mppt2_stale: bool,
+) -> Element<'static, Message> {
+    let unit = |value_type: GuiValueType, value: f64| units.format(&value_type, value);
+    let (mppt1_style, mppt2_style) = (value_text_style(mppt1_stale), value_text_style(mppt2_stale));
+    let bps_style = value_text_style(bps_data.stale);
+
     container(
         column![
             text("MPPT & BPS Info").size(18),
             row![
                 // MPPT 1 Column
                 column![
-                    text("MPPT Back").size(16),
+                    text(data_title("MPPT Back", mppt1_stale)).size(16),
                     text(format!(
-                        "In: {:.1}V / {:.1}A",
-                        data.mppt1_input_voltage, data.mppt1_input_current
+                        "In: {} / {}",
+                        unit(GuiValueType::Mppt1InputVoltage, data.mppt1_input_voltage),
+                        unit(GuiValueType::Mppt1InputCurrent, data.mppt1_input_current)
                     ))
-                    .size(14),
+                    .size(14)
+                    .style(mppt1_style),
                     text(format!(
-                        "Out: {:.1}V / {:.1}A",
-                        data.mppt1_output_voltage, data.mppt1_output_current
+                        "Out: {} / {}",
+                        unit(GuiValueType::Mppt1OutputVoltage, data.mppt1_output_voltage),
+                        unit(GuiValueType::Mppt1OutputCurrent, data.mppt1_output_current)
                     ))
-                    .size(14),
+                    .size(14)
+                    .style(mppt1_style),
+                    text(format!("Mode: {}", data.mppt1_mode))
+                        .size(14)
+                        .style(mppt1_style),
                 ]
                 .spacing(4)
                 .align_items(Alignment::Start)
                 .width(Length::FillPortion(1)),
                 // MPPT 2 Column
                 column![
-                    text("MPPT Front").size(16),
+                    text(data_title("MPPT Front", mppt2_stale)).size(16),
                     text(format!(
-                        "In: {:.1}V / {:.1}A",
-                        data.mppt2_input_voltage, data.mppt2_input_current
+                        "In: {} / {}",
+                        unit(GuiValueType::Mppt2InputVoltage, data.mppt2_input_voltage),
+                        unit(GuiValueType::Mppt2InputCurrent, data.mppt2_input_current)
                     ))
-                    .size(14),
+                    .size(14)
+                    .style(mppt2_style),
                     text(format!(
-                        "Out: {:.1}V / {:.1}A",
-                        data.mppt2_output_voltage, data.mppt2_output_current
+                        "Out: {} / {}",
+                        unit(GuiValueType::Mppt2OutputVoltage, data.mppt2_output_voltage),
+                        unit(GuiValueType::Mppt2OutputCurrent, data.mppt2_output_current)
                     ))
-                    .size(14),
+                    .size(14)
+                    .style(mppt2_style),
+                    text(format!("Mode: {}", data.mppt2_mode))
+                        .size(14)
+                        .style(mppt2_style),
                 ]
                 .spacing(4)
                 .align_items(Alignment::Start)
@@ -78,8 +117,8 @@ pub fn mppt_info_box(data: &MpptData, bps_data: &BpsData) -> Element<'static, Me
             .spacing(10),
             row![column![
                 text("------------").size(20),
-                text(format!("Time On: {:.1} Seconds", bps_data.ontime)),
-                text(format!("BPS State: {}", bps_data.state)),
+                text(format!("Time On: {:.1} Seconds", bps_data.ontime)).style(bps_style),
+                text(format!("BPS State: {}", bps_data.state)).style(bps_style),
             ]
             .spacing(5)
             .align_items(Alignment::Start),]
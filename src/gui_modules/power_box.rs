@@ -0,0 +1,47 @@
+use crate::gui_modules::{value_text_style, Message, SpeedUnit};
+use iced::widget::{container, row, text, Space};
+use iced::{Alignment, Element, Length};
+
+#[derive(Clone)]
+pub struct PowerData {
+    pub solar_w: f64,             // Sum of both MPPT outputs
+    pub battery_w: f64,           // Pack V*A, positive while discharging
+    pub net_w: f64,               // Drawn by the vehicle: solar plus battery
+    pub wh_per_mile: Option<f64>, // None while stopped or without fresh speed data
+    pub stale: bool,              // Battery or an MPPT has stopped reporting
+}
+
+pub fn power_box(data: &PowerData, unit: SpeedUnit) -> Element<'static, Message> {
+    // Wh per mile divided by km per mile gives Wh per km
+    let efficiency = match data.wh_per_mile {
+        Some(wh_per_mile) => format!(
+            "{:.0} Wh/{}",
+            wh_per_mile / unit.convert_mph(1.0),
+            unit.distance_label()
+        ),
+        None => format!("-- Wh/{}", unit.distance_label()),
+    };
+    let style = value_text_style(data.stale);
+
+    container(
+        row![
+            text(format!("Solar: {:.0} W", data.solar_w))
+                .size(16)
+                .style(style),
+            text(format!("Battery: {:.0} W", data.battery_w))
+                .size(16)
+                .style(style),
+            text(format!("Net: {:.0} W", data.net_w))
+                .size(16)
+                .style(style),
+            Space::with_width(Length::Fill),
+            text(efficiency).size(16).style(style),
+        ]
+        .spacing(20)
+        .align_items(Alignment::Center),
+    )
+    .padding([2, 10])
+    .width(Length::Fill)
+    .style(iced::theme::Container::Box)
+    .into()
+}
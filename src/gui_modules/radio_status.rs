@@ -1,20 +1,33 @@
 use crate::gui_modules::Message;
 use iced::widget::container::StyleSheet;
-use iced::widget::{container, text};
+use iced::widget::{container, row, text};
 use iced::{Color, Element, Length};
 
+// `lora_connected` / `mqtt_connected` are None when that link isn't configured (its box is hidden)
 pub fn radio_status_indicators(
     rfd_connected: bool,
+    lora_connected: Option<bool>,
+    mqtt_connected: Option<bool>,
 ) -> Element<'static, Message> {
+    let mut indicators = row![radio_box("RFD", rfd_connected)].spacing(5);
+    if let Some(connected) = lora_connected {
+        indicators = indicators.push(radio_box("LoRa", connected));
+    }
+    if let Some(connected) = mqtt_connected {
+        indicators = indicators.push(radio_box("MQTT", connected));
+    }
+    indicators.into()
+}
 
-    let rfd_box = container(
-        text("RFD")
+fn radio_box(label: &'static str, connected: bool) -> Element<'static, Message> {
+    container(
+        text(label)
             .size(14)
             .horizontal_alignment(iced::alignment::Horizontal::Center),
     )
     .padding(4)
     .width(Length::Fixed(50.0))
-    .style(if rfd_connected {
+    .style(if connected {
         iced::theme::Container::Custom(Box::new(|theme: &iced::Theme| {
             let mut appearance = theme.appearance(&iced::theme::Container::Box);
             appearance.background = Some(Color::from_rgb(0.0, 0.8, 0.0).into());
@@ -28,7 +41,6 @@ pub fn radio_status_indicators(
             appearance.text_color = Some(Color::WHITE);
             appearance
         }))
-    });
-
-    rfd_box.into()
+    })
+    .into()
 }
@@ -1,19 +1,36 @@
-use crate::gui_modules::Message;
+use crate::gui_modules::{Message, SpeedUnit};
 use iced::widget::container::StyleSheet;
-use iced::widget::{column, container, text};
+use iced::widget::{column, container, row, text};
 use iced::{Alignment, Color, Element, Length};
 
-pub fn can_status_indicator(can_connected: bool) -> Element<'static, Message> {
+// `reconnecting` means frames were flowing and the socket is being re-opened after going quiet.
+// The estimated bus load is shown under the status ("--" while offline).
+pub fn can_status_indicator(
+    can_connected: bool,
+    reconnecting: bool,
+    bus_load_percent: f64,
+) -> Element<'static, Message> {
     let status_text = if can_connected {
         "CAN: ONLINE"
+    } else if reconnecting {
+        "CAN: WAITING"
     } else {
         "CAN: OFFLINE"
     };
+    let load_text = if can_connected {
+        format!("Load: {:.0}%", bus_load_percent)
+    } else {
+        "Load: --".to_string()
+    };
 
     container(
-        text(status_text)
-            .size(14)
-            .horizontal_alignment(iced::alignment::Horizontal::Center),
+        column![
+            text(status_text)
+                .size(14)
+                .horizontal_alignment(iced::alignment::Horizontal::Center),
+            text(load_text).size(12),
+        ]
+        .align_items(Alignment::Center),
     )
     .padding(4)
     .width(Length::Fixed(100.0))
@@ -24,6 +41,13 @@ pub fn can_status_indicator(can_connected: bool) -> Element<'static, Message> {
             appearance.text_color = Some(Color::WHITE);
             appearance
         }))
+    } else if reconnecting {
+        iced::theme::Container::Custom(Box::new(|theme: &iced::Theme| {
+            let mut appearance = theme.appearance(&iced::theme::Container::Box);
+            appearance.background = Some(Color::from_rgb(1.0, 0.8, 0.0).into());
+            appearance.text_color = Some(Color::BLACK);
+            appearance
+        }))
     } else {
         iced::theme::Container::Custom(Box::new(|theme: &iced::Theme| {
             let mut appearance = theme.appearance(&iced::theme::Container::Box);
@@ -35,32 +59,100 @@ pub fn can_status_indicator(can_connected: bool) -> Element<'static, Message> {
     .into()
 }
 
-pub fn direction_speed_display(direction: &str, speed: f64) -> Element<'static, Message> {
-    container(
+// Per-motor breakdown shown under the combined speed
+#[derive(Clone)]
+pub struct MotorSpeedDetail {
+    pub motor1_rpm: f64,
+    pub motor2_rpm: f64,
+    pub motor1_mph: f64,
+    pub motor2_mph: f64,
+    pub diverging: bool, // Motor speeds differ enough to suggest wheel slip or a sensor fault
+}
+
+fn motor_speed_detail(detail: &MotorSpeedDetail, unit: SpeedUnit) -> Element<'static, Message> {
+    let motor_column = |label: &str, rpm: f64, mph: f64| {
         column![
-            text(format!("{:.1}", speed))
-                .size(72)
-                .horizontal_alignment(iced::alignment::Horizontal::Center),
-            text("MPH")
-                .size(24)
-                .horizontal_alignment(iced::alignment::Horizontal::Center),
-            text(direction)
-                .size(20)
-                .horizontal_alignment(iced::alignment::Horizontal::Center)
+            text(label).size(12),
+            text(format!("{:.0} RPM", rpm)).size(12),
+            text(format!("{:.1} {}", unit.convert_mph(mph), unit.label())).size(12),
         ]
-        .spacing(0)
         .align_items(Alignment::Center)
-        .width(Length::Fill),
+    };
+
+    let diverging = detail.diverging;
+    container(
+        row![
+            motor_column("M1", detail.motor1_rpm, detail.motor1_mph),
+            motor_column("M2", detail.motor2_rpm, detail.motor2_mph),
+        ]
+        .spacing(20),
     )
-    .width(Length::FillPortion(1))
-    .center_x()
-    .center_y()
+    .padding(4)
+    .style(iced::theme::Container::Custom(Box::new(
+        move |theme: &iced::Theme| {
+            let mut appearance = theme.appearance(&iced::theme::Container::Box);
+            if diverging {
+                appearance.background = Some(Color::from_rgb(1.0, 0.8, 0.0).into());
+                appearance.text_color = Some(Color::BLACK);
+            }
+            appearance
+        },
+    )))
     .into()
 }
 
-pub fn time_display(current_time: &str) -> Element<'static, Message> {
-    container(
+// `trip_miles` and `peak_mph` are since the last ResetTrip
+pub fn direction_speed_display(
+    direction: &str,
+    speed_mph: f64,
+    trip_miles: f64,
+    peak_mph: f64,
+    unit: SpeedUnit,
+    motor_detail: Option<&MotorSpeedDetail>,
+) -> Element<'static, Message> {
+    let mut content = column![
+        text(format!("{:.1}", unit.convert_mph(speed_mph)))
+            .size(72)
+            .horizontal_alignment(iced::alignment::Horizontal::Center),
+        text(unit.label())
+            .size(24)
+            .horizontal_alignment(iced::alignment::Horizontal::Center),
+        text(direction)
+            .size(20)
+            .horizontal_alignment(iced::alignment::Horizontal::Center),
+        text(format!(
+            "Trip: {:.2} {} | Peak: {:.1}",
+            unit.convert_mph(trip_miles),
+            unit.distance_label(),
+            unit.convert_mph(peak_mph)
+        ))
+        .size(14)
+        .horizontal_alignment(iced::alignment::Horizontal::Center)
+    ]
+    .spacing(0)
+    .align_items(Alignment::Center)
+    .width(Length::Fill);
+
+    if let Some(detail) = motor_detail {
+        content = content.push(motor_speed_detail(detail, unit));
+    }
+
+    container(content)
+        .width(Length::FillPortion(1))
+        .center_x()
+        .center_y()
+        .into()
+}
+
+// While `paused` (live values frozen on screen) the clock is followed by an orange PAUSED
+pub fn time_display(current_time: &str, paused: bool) -> Element<'static, Message> {
+    let label = if paused {
+        text(format!("{} - PAUSED", current_time)).style(Color::from_rgb(1.0, 0.5, 0.0))
+    } else {
         text(current_time)
+    };
+    container(
+        label
             .size(16)
             .horizontal_alignment(iced::alignment::Horizontal::Center),
     )
@@ -70,19 +162,36 @@ pub fn time_display(current_time: &str) -> Element<'static, Message> {
     .into()
 }
 
-pub fn battery_current_warning() -> Element<'static, Message> {
+// Yellow above --current-warn, red once `critical` (above --current-critical)
+pub fn battery_current_warning(critical: bool) -> Element<'static, Message> {
+    let (message, background, text_color) = if critical {
+        (
+            "CRITICAL: BATTERY CURRENT OVER LIMIT - SLOW DOWN NOW",
+            Color::from_rgb(0.8, 0.0, 0.0),
+            Color::WHITE,
+        )
+    } else {
+        (
+            "WARNING: HIGH BATTERY CURRENT - SLOW DOWN",
+            Color::from_rgb(1.0, 1.0, 0.0),
+            Color::BLACK,
+        )
+    };
+
     container(
-        text("WARNING: HIGH BATTERY CURRENT - SLOW DOWN")
+        text(message)
             .size(16)
             .horizontal_alignment(iced::alignment::Horizontal::Center),
     )
     .padding(8)
     .width(Length::Fill)
-    .style(iced::theme::Container::Custom(Box::new(|theme: &iced::Theme| {
-        let mut appearance = theme.appearance(&iced::theme::Container::Box);
-        appearance.background = Some(Color::from_rgb(1.0, 1.0, 0.0).into());
-        appearance.text_color = Some(Color::BLACK);
-        appearance
-    })))
+    .style(iced::theme::Container::Custom(Box::new(
+        move |theme: &iced::Theme| {
+            let mut appearance = theme.appearance(&iced::theme::Container::Box);
+            appearance.background = Some(background.into());
+            appearance.text_color = Some(text_color);
+            appearance
+        },
+    )))
     .into()
 }
@@ -1,11 +1,16 @@
+use crate::can::{DecodedMessage, DecodedSignal};
 use chrono::{DateTime, Utc};
 use iced::{widget::container::StyleSheet, Color, Theme};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
 use socketcan::CanFrame;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
 
 // Re-export common types and messages for all components
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum FaultSeverity {
     Critical = 0, // Highest priority - most severe
     Error = 1,    // Medium priority
@@ -16,59 +21,192 @@ pub enum FaultSeverity {
 pub struct Fault {
     pub name: String,
     pub timestamp: DateTime<Utc>,
-    pub is_active: bool,
     pub value: String,
     pub message_name: String, // Added to track which CAN message this came from
     pub severity: FaultSeverity, // New field for severity classification
 }
 
+// Unit the speed readouts are shown in; speeds are computed and stored in MPH
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpeedUnit {
+    Mph,
+    Kph,
+}
+
+impl SpeedUnit {
+    pub fn convert_mph(self, mph: f64) -> f64 {
+        match self {
+            SpeedUnit::Mph => mph,
+            SpeedUnit::Kph => mph * 1.609_344,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SpeedUnit::Mph => "MPH",
+            SpeedUnit::Kph => "KM/H",
+        }
+    }
+
+    // Distances convert with the same factor (convert_mph(miles) gives km)
+    pub fn distance_label(self) -> &'static str {
+        match self {
+            SpeedUnit::Mph => "mi",
+            SpeedUnit::Kph => "km",
+        }
+    }
+
+    pub fn toggled(self) -> Self {
+        match self {
+            SpeedUnit::Mph => SpeedUnit::Kph,
+            SpeedUnit::Kph => SpeedUnit::Mph,
+        }
+    }
+}
+
+// Night-driving variant of the dark theme: lower text brightness and contrast so the
+// dashboard isn't blinding. Fault/indicator colors keep their own styling.
+pub fn dimmed_theme() -> Theme {
+    Theme::custom(
+        "Dimmed".to_string(),
+        iced::theme::Palette {
+            background: Color::from_rgb(0.05, 0.05, 0.05),
+            text: Color::from_rgb(0.5, 0.5, 0.5),
+            primary: Color::from_rgb(0.25, 0.3, 0.45),
+            success: Color::from_rgb(0.1, 0.4, 0.1),
+            danger: Color::from_rgb(0.5, 0.1, 0.1),
+        },
+    )
+}
+
 // Message enum shared between all components
 #[derive(Debug, Clone)]
 pub enum Message {
-    CanFrameReceived(String, CanFrame),
+    CanFrameReceived(DecodedMessage, CanFrame),
+    CanLinkLost, // Socket closed for re-open (no frames within the stale window, or a read error)
     ToggleFullscreen,
+    EnterFullscreen, // Delayed one-shot issued at startup
+    ToggleDiagnostics,
+    ToggleMessageRates, // Per-ID rate page, reached from diagnostics or with "h"
+    ResetTrip,          // Zero the session distance and peak speed
+    ResetEnergy,        // Zero the dashboard energy counter at the start of a run
+    ToggleMotorDetail,
+    ToggleDim,
+    ToggleTheme, // Dark/light; light reads better in direct sunlight
+    Screenshot,  // Capture the window to a PNG next to the logs
+    TogglePause, // Freeze the displayed values; logging and transmission carry on
+    ScreenshotCaptured(iced::window::Screenshot),
+    ToggleSpeedUnit,
+    ReloadDbc,
+    AcknowledgeFault(String), // Fault key; hides a known fault until it clears and recurs
+    RestoreAcknowledgedFaults,
+    CloseRequested, // Window close intercepted so the session summary can be written
     Tick, // For updating time display
 }
 
-// Container styling helpers for different fault severities
-pub fn create_error_container_style() -> iced::theme::Container {
-    iced::theme::Container::Custom(Box::new(move |theme: &Theme| {
-        let mut appearance = theme.appearance(&iced::theme::Container::Box);
-        appearance.background = Some(Color::from_rgb(1.0, 0.0, 0.0).into());
-        appearance.text_color = Some(Color::WHITE);
-        appearance
-    }))
+// Colors for one severity: the fault panel header (background/text) and the tint and
+// outline of that severity's fault rows (accent, also the critical header border)
+#[derive(Debug, Clone, Copy)]
+pub struct SeverityColors {
+    pub background: Color,
+    pub text: Color,
+    pub accent: Color,
 }
 
-pub fn create_warning_container_style() -> iced::theme::Container {
-    iced::theme::Container::Custom(Box::new(move |theme: &Theme| {
-        let mut appearance = theme.appearance(&iced::theme::Container::Box);
-        appearance.background = Some(Color::from_rgb(1.0, 0.8, 0.0).into()); // Yellow background
-        appearance.text_color = Some(Color::BLACK); // Black text for better readability on yellow
-        appearance
-    }))
+// Per-severity fault colors, selected with --palette
+#[derive(Debug, Clone, Copy)]
+pub struct FaultPalette {
+    pub warning: SeverityColors,
+    pub error: SeverityColors,
+    pub critical: SeverityColors,
 }
 
-pub fn create_critical_container_style() -> iced::theme::Container {
+impl Default for FaultPalette {
+    // Traditional yellow / red / dark red
+    fn default() -> Self {
+        Self {
+            warning: SeverityColors {
+                background: Color::from_rgb(1.0, 0.8, 0.0),
+                text: Color::BLACK, // Black text for better readability on yellow
+                accent: Color::from_rgb8(238, 210, 2),
+            },
+            error: SeverityColors {
+                background: Color::from_rgb(1.0, 0.0, 0.0),
+                text: Color::WHITE,
+                accent: Color::from_rgb(0.8, 0.0, 0.0),
+            },
+            critical: SeverityColors {
+                background: Color::from_rgb(0.8, 0.0, 0.0),
+                text: Color::WHITE,
+                accent: Color::from_rgb(1.0, 0.0, 0.0),
+            },
+        }
+    }
+}
+
+impl FaultPalette {
+    // Okabe-Ito yellow / orange / blue, distinguishable with red-green color blindness
+    pub fn colorblind() -> Self {
+        Self {
+            warning: SeverityColors {
+                background: Color::from_rgb8(240, 228, 66),
+                text: Color::BLACK,
+                accent: Color::from_rgb8(240, 228, 66),
+            },
+            error: SeverityColors {
+                background: Color::from_rgb8(230, 159, 0),
+                text: Color::BLACK,
+                accent: Color::from_rgb8(230, 159, 0),
+            },
+            critical: SeverityColors {
+                background: Color::from_rgb8(0, 90, 160),
+                text: Color::WHITE,
+                accent: Color::from_rgb8(86, 180, 233),
+            },
+        }
+    }
+
+    // Look up a built-in preset by its --palette name
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.trim() {
+            "default" => Ok(Self::default()),
+            "colorblind" => Ok(Self::colorblind()),
+            other => Err(format!(
+                "unknown palette '{}' (expected default or colorblind)",
+                other
+            )),
+        }
+    }
+
+    pub fn colors(&self, severity: FaultSeverity) -> &SeverityColors {
+        match severity {
+            FaultSeverity::Warning => &self.warning,
+            FaultSeverity::Error => &self.error,
+            FaultSeverity::Critical => &self.critical,
+        }
+    }
+}
+
+// Helper function to get container style based on fault severity
+pub fn get_fault_container_style(
+    severity: &FaultSeverity,
+    palette: &FaultPalette,
+) -> iced::theme::Container {
+    let colors = *palette.colors(*severity);
+    let critical = *severity == FaultSeverity::Critical;
+
     iced::theme::Container::Custom(Box::new(move |theme: &Theme| {
         let mut appearance = theme.appearance(&iced::theme::Container::Box);
-        appearance.background = Some(Color::from_rgb(0.8, 0.0, 0.0).into()); // Dark red background
-        appearance.text_color = Some(Color::WHITE);
-        appearance.border.color = Color::from_rgb(1.0, 0.0, 0.0); // Bright red border
-        appearance.border.width = 2.0; // Thicker border for critical
+        appearance.background = Some(colors.background.into());
+        appearance.text_color = Some(colors.text);
+        if critical {
+            appearance.border.color = colors.accent;
+            appearance.border.width = 2.0; // Thicker border for critical
+        }
         appearance
     }))
 }
 
-// Helper function to get container style based on fault severity
-pub fn get_fault_container_style(severity: &FaultSeverity) -> iced::theme::Container {
-    match severity {
-        FaultSeverity::Warning => create_warning_container_style(),
-        FaultSeverity::Error => create_error_container_style(),
-        FaultSeverity::Critical => create_critical_container_style(),
-    }
-}
-
 // DTC fault definitions for BMS with severity classification
 pub const DTC_FLAGS_1_FAULTS: &[(u16, &str, FaultSeverity)] = &[
     (0x0001, "Discharge Limit Enforcement", FaultSeverity::Critical),
@@ -100,11 +238,244 @@ pub const DTC_FLAGS_2_FAULTS: &[(u16, &str, FaultSeverity)] = &[
     (0x8000, "Charge Limit Enforcement", FaultSeverity::Critical),
 ];
 
-// Configuration for GUI value updates using (message_name, signal_name) as key
-// Returns a Vec of GuiValueType since one signal might update multiple GUI values
-// Returns a Vec of GuiValueType since one signal might update multiple GUI values
-pub fn get_gui_value_mappings() -> HashMap<(&'static str, &'static str), Vec<GuiValueType>> {
-    let mut mappings = HashMap::new();
+pub const BMS_DTC_ID: u32 = 0x300;
+
+// One flag of a bit-packed fault word. While `mask` is set in the little-endian word starting
+// at `byte` of frame `id`, the frame decodes with a `<prefix>_<name>` signal (labelled `name`)
+// that the GUI raises as a fault of `severity`; it clears once the bit reads 0 again. The
+// prefix doubles as the fault key namespace, so words on different IDs need distinct ones.
+// The frame's DBC signals are all kept unless `replaces` names one the flags stand in for.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BitFault {
+    pub id: u32,
+    pub byte: u8,
+    pub mask: u32,
+    pub prefix: String,
+    pub name: String,
+    pub severity: FaultSeverity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaces: Option<String>,
+}
+
+impl BitFault {
+    pub fn signal_name(&self) -> String {
+        format!("{}_{}", self.prefix, self.name.split(':').next().unwrap())
+    }
+
+    // The flag's bits in an 8-byte frame (bit n of byte b is bit 8b + n); bits past the
+    // end of the frame are dropped
+    pub fn frame_bits(&self) -> u64 {
+        (self.mask as u64)
+            .checked_shl(self.byte as u32 * 8)
+            .unwrap_or(0)
+    }
+}
+
+// The BMS DTC words on 0x300: DTC_Flags_1 in bytes 0-1, DTC_Flags_2 in bytes 2-3. Their
+// flags replace the raw words, whose zero values would otherwise read as faults.
+pub fn builtin_bit_faults() -> Vec<BitFault> {
+    let word = |byte: u8, prefix: &'static str, table: &'static [(u16, &str, FaultSeverity)]| {
+        let replaces = format!("DTC_Flags_{}", byte / 2 + 1);
+        table.iter().map(move |&(mask, name, severity)| BitFault {
+            id: BMS_DTC_ID,
+            byte,
+            mask: mask as u32,
+            prefix: prefix.to_string(),
+            name: name.to_string(),
+            severity,
+            replaces: Some(replaces.clone()),
+        })
+    };
+    word(0, "Fault_DTC1", DTC_FLAGS_1_FAULTS)
+        .chain(word(2, "Fault_DTC2", DTC_FLAGS_2_FAULTS))
+        .collect()
+}
+
+// Motor controller error bits (MC_ERRn, same layout on both controllers) with severity classification
+pub const MC_ERR_FAULTS: &[(&str, &str, FaultSeverity)] = &[
+    ("MC_ERR0", "Identification error", FaultSeverity::Error),
+    ("MC_ERR1", "Over voltage", FaultSeverity::Critical),
+    ("MC_ERR2", "Low voltage", FaultSeverity::Error),
+    ("MC_ERR3", "Reserved", FaultSeverity::Warning),
+    ("MC_ERR4", "Stall", FaultSeverity::Critical),
+    ("MC_ERR5", "Internal volts fault", FaultSeverity::Critical),
+    ("MC_ERR6", "Controller over temperature", FaultSeverity::Critical),
+    ("MC_ERR7", "Throttle error at power-up", FaultSeverity::Error),
+    ("MC_ERR8", "Reserved", FaultSeverity::Warning),
+    ("MC_ERR9", "Internal reset", FaultSeverity::Error),
+    ("MC_ERR10", "Hall throttle open/short", FaultSeverity::Error),
+    ("MC_ERR11", "Angle sensor error", FaultSeverity::Critical),
+    ("MC_ERR12", "Reserved", FaultSeverity::Warning),
+    ("MC_ERR13", "Reserved", FaultSeverity::Warning),
+    ("MC_ERR14", "Motor over temperature", FaultSeverity::Critical),
+    ("MC_ERR15", "Hall galvanometer sensor", FaultSeverity::Error),
+];
+
+// Message name -> signal name -> GUI values it updates (one signal may update several)
+pub type GuiValueMappings = HashMap<String, HashMap<String, Vec<GuiValueType>>>;
+
+// Message name -> signals in that message that are treated as faults
+pub type FaultSignalConfig = HashMap<String, Vec<String>>;
+
+// Message name -> signals checked against their DBC [min|max] (with --range-faults)
+pub type RangeCheckConfig = HashMap<String, Vec<String>>;
+
+// Read at startup when present, so signals can be remapped without a rebuild (--mappings)
+pub const SIGNAL_MAPPINGS_FILE: &str = "signal_mappings.toml";
+
+// On-disk form of the mappings:
+//
+//   [values.BMS_Power]
+//   Pack_Current = ["BatteryCurrent"]
+//
+//   [faults]
+//   MotorController_1 = ["MC_ERR0", "MC_ERR1"]
+//
+//   [[bit_faults]]
+//   id = 0x300
+//   byte = 0
+//   mask = 0x0080
+//   prefix = "Fault_DTC1"
+//   name = "Pack Too Hot"
+//   severity = "Critical"
+//   replaces = "DTC_Flags_1"   # optional: DBC signal dropped in favour of the flags
+//
+//   [range_checks]
+//   MPPT1 = ["Input_Voltage_V", "Input_Current_A"]
+//
+// A section left out of the file keeps the built-in defaults; a section that is present
+// replaces them entirely.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SignalMappingsFile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    values: Option<BTreeMap<String, BTreeMap<String, Vec<GuiValueType>>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    faults: Option<BTreeMap<String, Vec<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bit_faults: Option<Vec<BitFault>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    range_checks: Option<BTreeMap<String, Vec<String>>>,
+}
+
+pub struct SignalMappings {
+    pub values: GuiValueMappings,
+    pub faults: FaultSignalConfig,
+    pub bit_faults: Vec<BitFault>, // Handed to CanDecoder::set_bit_faults
+    pub range_checks: RangeCheckConfig,
+}
+
+impl SignalMappings {
+    pub fn builtin() -> Self {
+        Self {
+            values: get_gui_value_mappings(),
+            faults: get_fault_signal_config(),
+            bit_faults: builtin_bit_faults(),
+            range_checks: get_range_check_config(),
+        }
+    }
+
+    // Load `path`, or SIGNAL_MAPPINGS_FILE if it exists. Falls back to the built-in
+    // mappings (with an error printed) when the file can't be read or parsed.
+    pub fn load_or_builtin(path: Option<&Path>) -> Self {
+        let path = match path {
+            Some(path) => path,
+            None if Path::new(SIGNAL_MAPPINGS_FILE).exists() => Path::new(SIGNAL_MAPPINGS_FILE),
+            None => return Self::builtin(),
+        };
+
+        let loaded = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+            .and_then(|content| Self::from_toml_str(&content));
+        match loaded {
+            Ok(mappings) => {
+                info!("Signal mappings loaded from {}", path.display());
+                mappings
+            }
+            Err(e) => {
+                warn!("{}; using built-in signal mappings", e);
+                Self::builtin()
+            }
+        }
+    }
+
+    pub fn from_toml_str(content: &str) -> Result<Self, String> {
+        let file: SignalMappingsFile =
+            toml::from_str(content).map_err(|e| format!("Invalid signal mappings: {}", e))?;
+
+        let values = match file.values {
+            Some(values) => values
+                .into_iter()
+                .map(|(message, signals)| (message, signals.into_iter().collect()))
+                .collect(),
+            None => get_gui_value_mappings(),
+        };
+        let faults = match file.faults {
+            Some(faults) => faults.into_iter().collect(),
+            None => get_fault_signal_config(),
+        };
+        let bit_faults = file.bit_faults.unwrap_or_else(builtin_bit_faults);
+        for fault in &bit_faults {
+            if fault.mask == 0 || fault.frame_bits().count_ones() != fault.mask.count_ones() {
+                return Err(format!(
+                    "Invalid signal mappings: bit fault {} (0x{:X} byte {} mask 0x{:X}) \
+                     must set bits within an 8-byte frame",
+                    fault.signal_name(),
+                    fault.id,
+                    fault.byte,
+                    fault.mask
+                ));
+            }
+        }
+        let range_checks = match file.range_checks {
+            Some(range_checks) => range_checks.into_iter().collect(),
+            None => get_range_check_config(),
+        };
+        Ok(Self {
+            values,
+            faults,
+            bit_faults,
+            range_checks,
+        })
+    }
+
+    // Every section written out, sorted, as a starting point for a mappings file
+    pub fn to_toml_string(&self) -> Result<String, String> {
+        let file = SignalMappingsFile {
+            values: Some(
+                self.values
+                    .iter()
+                    .map(|(message, signals)| {
+                        let signals = signals
+                            .iter()
+                            .map(|(signal, value_types)| (signal.clone(), value_types.clone()))
+                            .collect();
+                        (message.clone(), signals)
+                    })
+                    .collect(),
+            ),
+            faults: Some(
+                self.faults
+                    .iter()
+                    .map(|(message, signals)| (message.clone(), signals.clone()))
+                    .collect(),
+            ),
+            bit_faults: Some(self.bit_faults.clone()),
+            range_checks: Some(
+                self.range_checks
+                    .iter()
+                    .map(|(message, signals)| (message.clone(), signals.clone()))
+                    .collect(),
+            ),
+        };
+        toml::to_string_pretty(&file).map_err(|e| format!("Failed to write signal mappings: {}", e))
+    }
+}
+
+// Built-in GUI value updates, keyed by (message_name, signal_name)
+pub fn get_gui_value_mappings() -> GuiValueMappings {
+    let mut mappings: HashMap<(&str, &str), Vec<GuiValueType>> = HashMap::new();
 
     mappings.insert(
         ("MotorController_1", "Actual_Speed_RPM"),
@@ -213,12 +584,21 @@ pub fn get_gui_value_mappings() -> HashMap<(&'static str, &'static str), Vec<Gui
         ("MPPT2", "Output_Current_A"),
         vec![GuiValueType::Mppt2OutputCurrent],
     );
+    mappings.insert(("MPPT1", "Mode"), vec![GuiValueType::Mppt1Mode]);
+    mappings.insert(("MPPT2", "Mode"), vec![GuiValueType::Mppt2Mode]);
 
-    mappings
+    let mut nested = GuiValueMappings::new();
+    for ((message, signal), value_types) in mappings {
+        nested
+            .entry(message.to_string())
+            .or_default()
+            .insert(signal.to_string(), value_types);
+    }
+    nested
 }
 
-// Enum for different GUI value types
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+// Enum for different GUI value types (named as-is in the signal mappings file)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum GuiValueType {
     BmsPackDcl,
     BmsPackDclKw,
@@ -250,11 +630,112 @@ pub enum GuiValueType {
     Mppt2InputCurrent,
     Mppt2OutputVoltage,
     Mppt2OutputCurrent,
+    Mppt1Mode,
+    Mppt2Mode,
+}
+
+// Displayed values that go stale together when the device sending them stops transmitting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataGroup {
+    Battery,
+    Bps,
+    Mppt1,
+    Mppt2,
+    Motor1,
+    Motor2,
+}
+
+impl GuiValueType {
+    pub fn data_group(&self) -> DataGroup {
+        match self {
+            GuiValueType::BmsPackDcl
+            | GuiValueType::BmsPackDclKw
+            | GuiValueType::BmsPackCcl
+            | GuiValueType::BmsPackCclKw
+            | GuiValueType::BmsPackDod
+            | GuiValueType::BmsPackHealth
+            | GuiValueType::BmsAdaptiveSoc
+            | GuiValueType::BmsPackSoc
+            | GuiValueType::BmsAdaptiveAmphours
+            | GuiValueType::BmsPackAmphours
+            | GuiValueType::BatteryVoltage
+            | GuiValueType::BatteryCurrent
+            | GuiValueType::BatteryCharge
+            | GuiValueType::BatteryTemp
+            | GuiValueType::BatteryTempHi
+            | GuiValueType::BatteryTempLo => DataGroup::Battery,
+            GuiValueType::BpsOnTime | GuiValueType::BpsState => DataGroup::Bps,
+            GuiValueType::Motor1Speed | GuiValueType::Motor1Direction => DataGroup::Motor1,
+            GuiValueType::Motor2Speed | GuiValueType::Motor2Direction => DataGroup::Motor2,
+            GuiValueType::Mppt1InputVoltage
+            | GuiValueType::Mppt1InputCurrent
+            | GuiValueType::Mppt1OutputVoltage
+            | GuiValueType::Mppt1OutputCurrent
+            | GuiValueType::Mppt1Mode => DataGroup::Mppt1,
+            GuiValueType::Mppt2InputVoltage
+            | GuiValueType::Mppt2InputCurrent
+            | GuiValueType::Mppt2OutputVoltage
+            | GuiValueType::Mppt2OutputCurrent
+            | GuiValueType::Mppt2Mode => DataGroup::Mppt2,
+        }
+    }
+}
+
+// Stale values are grayed out so "0 A" can be told apart from "no data"
+pub fn value_text_style(stale: bool) -> iced::theme::Text {
+    if stale {
+        iced::theme::Text::Color(Color::from_rgb(0.45, 0.45, 0.45))
+    } else {
+        iced::theme::Text::Default
+    }
+}
+
+// Box title, marked when its values are stale
+pub fn data_title(title: &str, stale: bool) -> String {
+    if stale {
+        format!("{} - NO DATA", title)
+    } else {
+        title.to_string()
+    }
+}
+
+// Short display form of the unit spellings used in our DBC ("Volts" -> "V")
+pub fn unit_symbol(unit: &str) -> &str {
+    match unit {
+        "Volts" => "V",
+        "Amps" => "A",
+        "Percent" => "%",
+        "Celsius" | "C" => "°C",
+        "Amphours" | "Ahr" => "Ah",
+        "Ohms" => "Ω",
+        other => other,
+    }
+}
+
+// Units of the displayed values, taken from the DBC as signals are decoded
+#[derive(Clone, Default)]
+pub struct DisplayUnits(HashMap<GuiValueType, String>);
+
+impl DisplayUnits {
+    pub fn record(&mut self, value_type: &GuiValueType, dbc_unit: &str) {
+        let symbol = unit_symbol(dbc_unit);
+        if self.0.get(value_type).map(String::as_str) != Some(symbol) {
+            self.0.insert(value_type.clone(), symbol.to_string());
+        }
+    }
+
+    // "12.3 V", or just "12.3" while the unit is unknown or empty
+    pub fn format(&self, value_type: &GuiValueType, value: f64) -> String {
+        match self.0.get(value_type).filter(|unit| !unit.is_empty()) {
+            Some(unit) => format!("{:.1} {}", value, unit),
+            None => format!("{:.1}", value),
+        }
+    }
 }
 
 // Configuration for fault signals - defines which signals in which messages are faults
-pub fn get_fault_signal_config() -> HashMap<&'static str, Vec<&'static str>> {
-    let mut config = HashMap::new();
+pub fn get_fault_signal_config() -> FaultSignalConfig {
+    let mut config: HashMap<&str, Vec<&str>> = HashMap::new();
 
     // Motor Controller 1 faults
     config.insert(
@@ -298,10 +779,49 @@ pub fn get_fault_signal_config() -> HashMap<&'static str, Vec<&'static str>> {
         ],
     );
 
-    // Note: BMS DTC faults are handled separately via DTC_FLAGS_1_FAULTS and DTC_FLAGS_2_FAULTS
-    // They are not included here because they use a different fault detection mechanism
+    // Note: BMS DTC faults are not included here; they are bit faults (see builtin_bit_faults)
 
     config
+        .into_iter()
+        .map(|(message, signals)| {
+            let signals = signals.into_iter().map(str::to_string).collect();
+            (message.to_string(), signals)
+        })
+        .collect()
+}
+
+// Built-in signals checked against the DBC [min|max] when --range-faults is on; a
+// [range_checks] table in --mappings replaces this list at runtime.
+// Opt-in per signal: many DBC entries carry placeholder ranges that would only produce noise.
+pub fn get_range_check_config() -> RangeCheckConfig {
+    let mut config: HashMap<&str, Vec<&str>> = HashMap::new();
+
+    let motor_signals = vec![
+        "Battery_Voltage_V",
+        "Actual_Current_A",
+        "Actual_Speed_RPM",
+        "Motor_Temperature_C",
+        "Controller_Temperature_C",
+    ];
+    config.insert("MotorController_1", motor_signals.clone());
+    config.insert("MotorController_2", motor_signals);
+
+    let mppt_signals = vec![
+        "Input_Voltage_V",
+        "Input_Current_A",
+        "Output_Voltage_V",
+        "Output_Current_A",
+    ];
+    config.insert("MPPT1", mppt_signals.clone());
+    config.insert("MPPT2", mppt_signals);
+
+    config
+        .into_iter()
+        .map(|(message, signals)| {
+            let signals = signals.into_iter().map(str::to_string).collect();
+            (message.to_string(), signals)
+        })
+        .collect()
 }
 
 // Helper function to check if a signal value indicates a fault (for non-DTC faults)
@@ -310,45 +830,43 @@ pub fn is_fault_value(value: &str) -> bool {
     let upper_value = trimmed.to_uppercase();
 
     // A signal is NOT a fault if it's empty, "0", "0.0", "OK", or "RESERVED"
-    if trimmed.is_empty()
+    !(trimmed.is_empty()
         || trimmed == "0"
         || trimmed == "0.0"
         || upper_value == "OK"
-        || upper_value == "RESERVED"
-    {
-        false
-    } else {
-        true
-    }
+        || upper_value == "RESERVED")
 }
 
-// Helper function to determine fault severity from DTC fault name
-pub fn get_dtc_fault_severity(fault_name: &str) -> FaultSeverity {
-    // Check DTC_FLAGS_1_FAULTS
-    for &(_, name, severity) in DTC_FLAGS_1_FAULTS.iter() {
-        if fault_name.contains(name) {
-            return severity;
-        }
+// Labelled signals go through is_fault_value; numeric ones are faults when non-zero
+pub fn is_fault_signal(signal: &DecodedSignal) -> bool {
+    match &signal.label {
+        Some(label) => is_fault_value(label),
+        None => signal.value != 0.0,
     }
-    
-    // Check DTC_FLAGS_2_FAULTS
-    for &(_, name, severity) in DTC_FLAGS_2_FAULTS.iter() {
-        if fault_name.contains(name) {
-            return severity;
-        }
+}
+
+// Human-readable fault name for the fault panel (falls back to the raw signal name)
+pub fn get_fault_display_name(message_name: &str, signal_name: &str) -> String {
+    match message_name {
+        "MotorController_1" | "MotorController_2" => MC_ERR_FAULTS
+            .iter()
+            .find(|(signal, _, _)| *signal == signal_name)
+            .map(|(signal, description, _)| format!("{} ({})", description, signal))
+            .unwrap_or_else(|| signal_name.to_string()),
+        _ => signal_name.to_string(),
     }
-    
-    // Default severity for unknown DTC faults
-    FaultSeverity::Error
 }
 
 // Helper function to determine fault severity for non-DTC faults
 pub fn get_fault_severity(message_name: &str, signal_name: &str) -> FaultSeverity {
     match message_name {
-        "BMS_DTC" => get_dtc_fault_severity(signal_name),
         "MotorController_1" | "MotorController_2" => {
-            // Motor controller faults are typically critical
-            FaultSeverity::Critical
+            // Per-bit severity, unknown motor controller faults default to critical
+            MC_ERR_FAULTS
+                .iter()
+                .find(|(signal, _, _)| *signal == signal_name)
+                .map(|(_, _, severity)| *severity)
+                .unwrap_or(FaultSeverity::Critical)
         }
         "BPS_System" => {
             // BPS faults are safety-critical
@@ -364,3 +882,95 @@ pub fn get_fault_severity(message_name: &str, signal_name: &str) -> FaultSeverit
         _ => FaultSeverity::Error, // Default for unknown message types
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // WCAG 2 contrast ratio between two opaque colors
+    fn contrast_ratio(a: Color, b: Color) -> f32 {
+        let luminance = |c: Color| {
+            let linear = |v: f32| {
+                if v <= 0.03928 {
+                    v / 12.92
+                } else {
+                    ((v + 0.055) / 1.055).powf(2.4)
+                }
+            };
+            0.2126 * linear(c.r) + 0.7152 * linear(c.g) + 0.0722 * linear(c.b)
+        };
+        let (la, lb) = (luminance(a), luminance(b));
+        (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+    }
+
+    #[test]
+    fn test_signal_mappings_file() {
+        // Exported defaults load back unchanged
+        let builtin = SignalMappings::builtin();
+        let exported = SignalMappings::from_toml_str(&builtin.to_toml_string().unwrap()).unwrap();
+        assert_eq!(exported.values, builtin.values);
+        assert_eq!(exported.faults, builtin.faults);
+        assert_eq!(exported.bit_faults, builtin.bit_faults);
+        assert_eq!(exported.range_checks, builtin.range_checks);
+
+        // A values section replaces the built-in values; the missing faults section keeps them
+        let remapped = SignalMappings::from_toml_str(
+            r#"
+            [values.BMS_Power]
+            Pack_Current = ["BatteryCurrent", "BatteryCharge"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(remapped.values.len(), 1);
+        assert_eq!(
+            remapped.values["BMS_Power"]["Pack_Current"],
+            [GuiValueType::BatteryCurrent, GuiValueType::BatteryCharge]
+        );
+        assert_eq!(remapped.faults, builtin.faults);
+        assert_eq!(remapped.range_checks, builtin.range_checks);
+
+        let checked =
+            SignalMappings::from_toml_str("[range_checks]\nBMS_Power = [\"Pack_Current\"]")
+                .unwrap();
+        assert_eq!(checked.range_checks.len(), 1);
+        assert_eq!(checked.range_checks["BMS_Power"], ["Pack_Current"]);
+
+        assert!(
+            SignalMappings::from_toml_str("[values.BMS_Power]\nPack_Current = [\"Nope\"]").is_err()
+        );
+        assert!(SignalMappings::from_toml_str("[fault]\nMPPT1 = []").is_err());
+
+        // A bit_faults table replaces the BMS DTC words; flags past byte 7 are rejected
+        let bit_fault = |byte: u8, mask: u32| {
+            format!(
+                "[[bit_faults]]\nid = 0x0CF11E05\nbyte = {}\nmask = 0x{:X}\n\
+                 prefix = \"MC1_ERR\"\nname = \"Overcurrent\"\nseverity = \"Critical\"",
+                byte, mask
+            )
+        };
+        let motor = SignalMappings::from_toml_str(&bit_fault(6, 0x0100)).unwrap();
+        assert_eq!(motor.bit_faults.len(), 1);
+        assert_eq!(motor.bit_faults[0].frame_bits(), 1 << 56);
+        assert_eq!(motor.bit_faults[0].signal_name(), "MC1_ERR_Overcurrent");
+        assert!(SignalMappings::from_toml_str(&bit_fault(7, 0x0100)).is_err());
+        assert!(SignalMappings::from_toml_str(&bit_fault(0, 0)).is_err());
+    }
+
+    #[test]
+    fn test_palette_text_contrast() {
+        for name in ["default", "colorblind"] {
+            let palette = FaultPalette::from_name(name).unwrap();
+            for severity in [
+                FaultSeverity::Warning,
+                FaultSeverity::Error,
+                FaultSeverity::Critical,
+            ] {
+                let colors = palette.colors(severity);
+                // WCAG large-text threshold; white on the default pure red header is ~4:1
+                let ratio = contrast_ratio(colors.text, colors.background);
+                assert!(ratio >= 3.0, "{} {:?}: {:.2}", name, severity, ratio);
+            }
+        }
+        assert!(FaultPalette::from_name("neon").is_err());
+    }
+}
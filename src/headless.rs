@@ -0,0 +1,72 @@
+// Headless JSON-lines mode (--json): read can0 and print one JSON object per decoded frame to
+// stdout, without starting the GUI, for piping into jq and scripts:
+//
+//   {"timestamp_ms": 1700000000000, "can_id": 800, "message": "MSGID_0X320",
+//    "signals": [{"name": "Pack_Current", "raw": -123, "value": -12.3, "label": null,
+//                 "unit": "A", "out_of_range": false}, ...]}
+//
+// Signals match the WebSocket "telemetry" event (see ws.rs). Unlike the other sinks, which use
+// DecodedMessage::export_name, `message` is the DBC message name even for mapped messages, so
+// scripts can match it against the DBC; bit-fault-only IDs have none and keep their GUI name.
+// Frames the DBC can't decode are skipped. Status and errors go to stderr so
+// stdout carries only frames.
+use crate::can::{build_id_filters, CanDecoder, DecodedSignal};
+use crate::health::now_ms;
+use log::info;
+use serde::Serialize;
+use socketcan::{CanSocket, EmbeddedFrame, Socket, SocketOptions};
+use std::io::{self, Write};
+
+#[derive(Serialize)]
+struct JsonFrame<'a> {
+    timestamp_ms: u64,
+    can_id: u32,
+    message: &'a str,
+    signals: &'a [DecodedSignal],
+}
+
+// Runs until the CAN socket fails or stdout is closed (e.g. `| head`), which is a clean exit
+pub fn run_json(
+    decoder: &CanDecoder,
+    iface: &str,
+    id_allowlist: Option<&[u32]>,
+) -> Result<(), String> {
+    let socket = CanSocket::open(iface)
+        .map_err(|e| format!("Failed to open CAN socket {}: {}", iface, e))?;
+    if let Some(ids) = id_allowlist {
+        socket
+            .set_filters(&build_id_filters(ids))
+            .map_err(|e| format!("Failed to apply CAN ID filters: {}", e))?;
+    }
+    info!("Printing decoded frames from {} as JSON lines", iface);
+
+    let mut stdout = io::stdout().lock();
+    loop {
+        let frame = socket
+            .read_frame()
+            .map_err(|e| format!("Failed to read from {}: {}", iface, e))?;
+        let Some(decoded) = decoder.decode(frame) else {
+            continue;
+        };
+
+        let raw_id = match frame.id() {
+            socketcan::Id::Standard(std_id) => std_id.as_raw() as u32,
+            socketcan::Id::Extended(ext_id) => ext_id.as_raw(),
+        };
+        let line = serde_json::to_string(&JsonFrame {
+            timestamp_ms: now_ms(),
+            can_id: raw_id,
+            // DBC name first, not export_name (see the header)
+            message: decoded.dbc_name.as_ref().unwrap_or(&decoded.message_name),
+            signals: &decoded.signals,
+        })
+        .map_err(|e| format!("Failed to serialize frame: {}", e))?;
+
+        // Flush per line so pipelines see frames as they arrive
+        match writeln!(stdout, "{}", line).and_then(|()| stdout.flush()) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+            Err(e) => return Err(format!("Failed to write to stdout: {}", e)),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+// Liveness tracking for the background subsystems (CAN subscription, scan thread, batch thread)
+// and for the individual CAN IDs on the bus
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// Weight of the newest interval in the smoothed rate (lower = steadier, slower to follow)
+const RATE_SMOOTHING: f64 = 0.2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubsystemStatus {
+    Ok,
+    Stalled, // Running but hasn't ticked within its stall window
+    Stopped, // Not running, or never ticked
+}
+
+impl SubsystemStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SubsystemStatus::Ok => "OK",
+            SubsystemStatus::Stalled => "STALLED",
+            SubsystemStatus::Stopped => "STOPPED",
+        }
+    }
+}
+
+// Shared last-tick timestamp (milliseconds since UNIX epoch, 0 = never ticked)
+#[derive(Debug, Clone, Default)]
+pub struct Heartbeat {
+    last_tick_ms: Arc<AtomicU64>,
+}
+
+impl Heartbeat {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn beat(&self) {
+        self.last_tick_ms.store(now_ms(), Ordering::Relaxed);
+    }
+
+    pub fn last_tick_ms(&self) -> Option<u64> {
+        match self.last_tick_ms.load(Ordering::Relaxed) {
+            0 => None,
+            ms => Some(ms),
+        }
+    }
+
+    pub fn status(&self, running: bool, stall_after: Duration) -> SubsystemStatus {
+        if !running {
+            return SubsystemStatus::Stopped;
+        }
+
+        match self.last_tick_ms() {
+            Some(last) if now_ms().saturating_sub(last) <= stall_after.as_millis() as u64 => {
+                SubsystemStatus::Ok
+            }
+            Some(_) => SubsystemStatus::Stalled,
+            None => SubsystemStatus::Stopped,
+        }
+    }
+}
+
+// Receive rate and last-seen time of one CAN ID
+pub struct RateTracker {
+    pub message_name: String,
+    pub last_seen_ms: u64,
+    last_seen: Instant,
+    mean_interval_secs: Option<f64>, // Smoothed time between frames (None until the second)
+}
+
+impl RateTracker {
+    pub fn new(message_name: &str) -> Self {
+        Self::started_at(message_name, Instant::now())
+    }
+
+    fn started_at(message_name: &str, now: Instant) -> Self {
+        Self {
+            message_name: message_name.to_string(),
+            last_seen_ms: now_ms(),
+            last_seen: now,
+            mean_interval_secs: None,
+        }
+    }
+
+    pub fn record(&mut self, message_name: &str) {
+        self.record_at(message_name, Instant::now());
+    }
+
+    fn record_at(&mut self, message_name: &str, now: Instant) {
+        let interval = now.duration_since(self.last_seen).as_secs_f64();
+        self.mean_interval_secs = Some(match self.mean_interval_secs {
+            Some(mean) => mean + RATE_SMOOTHING * (interval - mean),
+            None => interval,
+        });
+        self.last_seen = now;
+        self.last_seen_ms = now_ms();
+        if self.message_name != message_name {
+            self.message_name = message_name.to_string();
+        }
+    }
+
+    // Measured frames per second (0 until two frames have arrived)
+    pub fn hz(&self) -> f64 {
+        match self.mean_interval_secs {
+            Some(mean) if mean > 0.0 => 1.0 / mean,
+            _ => 0.0,
+        }
+    }
+
+    pub fn is_stale(&self, stale_after: Duration) -> bool {
+        self.last_seen.elapsed() > stale_after
+    }
+}
+
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_tracker_follows_message_rate() {
+        let start = Instant::now();
+        let mut tracker = RateTracker::started_at("BMS_Power", start);
+        assert_eq!(tracker.hz(), 0.0);
+
+        // 10 Hz settles immediately, then a switch to 50 Hz is followed within a few frames
+        for i in 1..=10 {
+            tracker.record_at("BMS_Power", start + Duration::from_millis(100 * i));
+        }
+        assert!((tracker.hz() - 10.0).abs() < 1e-6);
+        for i in 1..=40 {
+            tracker.record_at("BMS_Power", start + Duration::from_millis(1000 + 20 * i));
+        }
+        assert!((tracker.hz() - 50.0).abs() < 0.5, "{}", tracker.hz());
+
+        assert!(!tracker.is_stale(Duration::from_secs(3600)));
+    }
+}
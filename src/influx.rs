@@ -0,0 +1,179 @@
+// Optional InfluxDB sink (--influx-url) for long-term storage.
+//
+// Each decoded frame becomes one line-protocol point: the GUI message name (the DBC one for
+// messages the GUI doesn't map) is the measurement, the CAN ID a tag, and every signal a float
+// field, timestamped in nanoseconds:
+//
+//   BMS_Power,can_id=0x320 Pack_Current=-12.3,Pack_Voltage=101.2 1700000000000000000
+//
+// Lines are queued on a bounded channel and POSTed in batches by a background thread, either
+// when `batch_size` lines are waiting or every `flush_interval`. When the database is slow or
+// down the queue fills and new points are dropped, so CAN reading never waits on HTTP.
+use crate::can::DecodedSignal;
+use log::warn;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, SyncSender};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// Batches' worth of points buffered while a POST is in flight or failing
+const QUEUE_BATCHES: usize = 4;
+const HTTP_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone)]
+pub struct InfluxConfig {
+    pub url: String, // Full write URL, e.g. http://host:8086/api/v2/write?bucket=car
+    pub token: Option<String>, // Sent as "Authorization: Token <token>"
+    pub batch_size: usize,
+    pub flush_interval: Duration,
+}
+
+pub struct InfluxWriter {
+    sender: SyncSender<String>,
+    dropped: AtomicU64,
+}
+
+impl InfluxWriter {
+    pub fn start(config: InfluxConfig) -> Result<Self, String> {
+        let batch_size = config.batch_size.max(1);
+        let (sender, receiver) = mpsc::sync_channel::<String>(batch_size * QUEUE_BATCHES);
+
+        std::thread::Builder::new()
+            .name("influx".to_string())
+            .spawn(move || {
+                let mut batch: Vec<String> = Vec::with_capacity(batch_size);
+                let mut next_flush = Instant::now() + config.flush_interval;
+                loop {
+                    let wait = next_flush.saturating_duration_since(Instant::now());
+                    let disconnected = match receiver.recv_timeout(wait) {
+                        Ok(line) => {
+                            batch.push(line);
+                            false
+                        }
+                        Err(RecvTimeoutError::Timeout) => false,
+                        Err(RecvTimeoutError::Disconnected) => true,
+                    };
+
+                    if batch.len() >= batch_size || Instant::now() >= next_flush || disconnected {
+                        if !batch.is_empty() {
+                            if let Err(e) = post_batch(&config, &batch) {
+                                warn!("InfluxDB write of {} points failed: {}", batch.len(), e);
+                            }
+                            batch.clear();
+                        }
+                        next_flush = Instant::now() + config.flush_interval;
+                    }
+                    if disconnected {
+                        break;
+                    }
+                }
+            })
+            .map_err(|e| format!("Failed to spawn InfluxDB thread: {}", e))?;
+
+        Ok(Self {
+            sender,
+            dropped: AtomicU64::new(0),
+        })
+    }
+
+    // Queue one point for the frame without blocking
+    pub fn write(&self, message_name: &str, can_id: u32, signals: &[DecodedSignal]) {
+        let Some(line) = line_protocol(message_name, can_id, signals, now_ns()) else {
+            return;
+        };
+        if self.sender.try_send(line).is_err() {
+            // Report the first drop and then every thousandth, not every frame
+            let dropped = self.dropped.fetch_add(1, Ordering::Relaxed);
+            if dropped.is_multiple_of(1000) {
+                warn!("InfluxDB queue full, dropped {} points", dropped + 1);
+            }
+        }
+    }
+}
+
+fn post_batch(config: &InfluxConfig, batch: &[String]) -> Result<(), String> {
+    let mut request = ureq::post(&config.url)
+        .timeout(HTTP_TIMEOUT)
+        .set("Content-Type", "text/plain; charset=utf-8");
+    if let Some(token) = &config.token {
+        request = request.set("Authorization", &format!("Token {}", token));
+    }
+    request
+        .send_string(&batch.join("\n"))
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+fn now_ns() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0)
+}
+
+// Line-protocol point for one frame; None when no signal has a finite value
+fn line_protocol(
+    message_name: &str,
+    can_id: u32,
+    signals: &[DecodedSignal],
+    timestamp_ns: u128,
+) -> Option<String> {
+    let fields: Vec<String> = signals
+        .iter()
+        .filter(|signal| signal.value.is_finite())
+        .map(|signal| format!("{}={}", escape(&signal.name, ",= "), signal.value))
+        .collect();
+    if fields.is_empty() {
+        return None;
+    }
+
+    Some(format!(
+        "{},can_id=0x{:X} {} {}",
+        escape(message_name, ", "),
+        can_id,
+        fields.join(","),
+        timestamp_ns
+    ))
+}
+
+// Backslash-escape the characters line protocol treats as separators in this position
+fn escape(name: &str, special: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for c in name.chars() {
+        if special.contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signal(name: &str, value: f64) -> DecodedSignal {
+        DecodedSignal {
+            name: name.to_string(),
+            raw: 0,
+            value,
+            label: None,
+            unit: String::new(),
+            out_of_range: false,
+        }
+    }
+
+    #[test]
+    fn test_line_protocol_point() {
+        let signals = [
+            signal("Pack_Current", -12.3),
+            signal("Pack Voltage", 101.0),
+            signal("Bad", f64::NAN),
+        ];
+        assert_eq!(
+            line_protocol("BMS_Power", 0x320, &signals, 1_700_000_000_000_000_000).unwrap(),
+            "BMS_Power,can_id=0x320 Pack_Current=-12.3,Pack\\ Voltage=101 1700000000000000000"
+        );
+        assert_eq!(escape("a,b=c d", ", "), "a\\,b=c\\ d");
+        assert!(line_protocol("BMS_Power", 0x320, &signals[2..], 0).is_none());
+    }
+}
@@ -1,54 +1,465 @@
-use chrono::Local;
+use crate::can::DecodedMessage;
+use crate::gui_modules::Fault;
+use chrono::{DateTime, Local};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use log::{error, info, warn};
 use socketcan::{CanFrame, EmbeddedFrame};
+use std::collections::HashMap;
 use std::fs::{self, File, OpenOptions};
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Bytes available to unprivileged users on the filesystem holding `path`
+pub fn free_disk_bytes(path: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path is a valid NUL-terminated string and stats is a properly sized out-param
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stats) } != 0 {
+        return None;
+    }
+
+    Some(stats.f_bavail as u64 * stats.f_frsize as u64)
+}
+
+// Save an RGBA8 window capture as screenshot_<timestamp>.png in `dir`
+pub fn write_screenshot(
+    dir: &Path,
+    width: u32,
+    height: u32,
+    rgba: &[u8],
+) -> Result<PathBuf, String> {
+    let timestamp = Local::now().format("%Y%m%d_%H%M%S");
+    let path = dir.join(format!("screenshot_{}.png", timestamp));
+    write_png(&path, width, height, rgba)?;
+    Ok(path)
+}
+
+fn write_png(path: &Path, width: u32, height: u32, rgba: &[u8]) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| format!("Failed to create {:?}: {}", path, e))?;
+    let mut encoder = png::Encoder::new(io::BufWriter::new(file), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(rgba))
+        .map_err(|e| format!("Failed to write {:?}: {}", path, e))
+}
+
+// Path of the log currently being written, so the panic hook can annotate it
+static ACTIVE_LOG_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+// Record panics in the active CAN log before the default hook prints and unwinds.
+// Frames are flushed as they are written, so only the panic itself needs appending.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+            .unwrap_or_else(|| "unknown location".to_string());
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "non-string panic payload".to_string());
+
+        error!("FATAL: panic at {}: {}", location, message);
+
+        // try_lock: never deadlock if the panic happened while the path was being updated
+        if let Some(path) = ACTIVE_LOG_PATH.try_lock().ok().and_then(|p| p.clone()) {
+            if let Ok(mut file) = OpenOptions::new().append(true).open(&path) {
+                let _ = writeln!(
+                    file,
+                    "# PANIC {} at {}: {}",
+                    Local::now(),
+                    location,
+                    message
+                );
+                let _ = file.sync_all();
+            }
+        }
+
+        default_hook(info);
+    }));
+}
+
+// On-disk line format. Custom is what replay reads; CandumpLog matches `candump -l`
+// ("(1700000000.123456) can0 123#AABBCC") so can-utils and other tooling can ingest it.
+// DecodedCsv writes one "timestamp,message_name,signal_name,value" row per decoded signal
+// for spreadsheets; frames the DBC can't decode produce no rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    #[default]
+    Custom,
+    CandumpLog,
+    DecodedCsv,
+}
+
+impl LogFormat {
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.trim() {
+            "custom" => Ok(Self::Custom),
+            "candump" => Ok(Self::CandumpLog),
+            "csv" => Ok(Self::DecodedCsv),
+            other => Err(format!(
+                "unknown log format '{}' (expected custom, candump or csv)",
+                other
+            )),
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Custom | Self::CandumpLog => "txt",
+            Self::DecodedCsv => "csv",
+        }
+    }
+}
+
+// Start a new log file once the active one reaches this size; the closed file is gzipped
+const ROTATE_LOG_BYTES: u64 = 256 * 1024 * 1024;
+
+// Log files this logger created, in any format, compressed or not
+fn is_log_file(filename: &str) -> bool {
+    let name = filename.strip_suffix(".gz").unwrap_or(filename);
+    filename.starts_with("log_") && (name.ends_with(".txt") || name.ends_with(".csv"))
+}
+
+// Gzip `path` to `path.gz` and remove the original. The original is only removed once the
+// archive is complete, so an interrupted run just compresses it again next time.
+fn compress_log(path: &Path) -> Result<PathBuf, io::Error> {
+    let mut gz_name = path.as_os_str().to_owned();
+    gz_name.push(".gz");
+    let gz_path = PathBuf::from(gz_name);
+
+    let mut encoder = GzEncoder::new(File::create(&gz_path)?, Compression::default());
+    io::copy(&mut File::open(path)?, &mut encoder)?;
+    encoder.finish()?.sync_all()?;
+    fs::remove_file(path)?;
+    Ok(gz_path)
+}
+
+// Render one frame as log lines (without trailing newlines); only CSV can produce
+// more than one, and none for an undecoded frame
+fn format_frame(
+    format: LogFormat,
+    interface: &str,
+    timestamp: DateTime<Local>,
+    frame: &CanFrame,
+    decoded: &DecodedMessage,
+) -> Vec<String> {
+    let line = match format {
+        LogFormat::Custom => {
+            let id = match frame.id() {
+                socketcan::Id::Standard(std_id) => format!("0x{:03X}", std_id.as_raw()),
+                socketcan::Id::Extended(ext_id) => format!("0x{:08X}", ext_id.as_raw()),
+            };
+
+            let data_hex = frame
+                .data()
+                .iter()
+                .map(|b| format!("{:02X}", b))
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            format!(
+                "{} {} {}",
+                timestamp.format("%Y-%m-%d %H:%M:%S.%3f"),
+                id,
+                data_hex
+            )
+        }
+        LogFormat::DecodedCsv => {
+            let timestamp = timestamp.format("%Y-%m-%d %H:%M:%S%.3f");
+            return decoded
+                .signals
+                .iter()
+                .map(|signal| {
+                    format!(
+                        "{},{},{},{}",
+                        timestamp, decoded.message_name, signal.name, signal.value
+                    )
+                })
+                .collect();
+        }
+        LogFormat::CandumpLog => {
+            let id = match frame.id() {
+                socketcan::Id::Standard(std_id) => format!("{:03X}", std_id.as_raw()),
+                socketcan::Id::Extended(ext_id) => format!("{:08X}", ext_id.as_raw()),
+            };
+
+            let data_hex: String = frame.data().iter().map(|b| format!("{:02X}", b)).collect();
+
+            format!(
+                "({}.{:06}) {} {}#{}",
+                timestamp.timestamp(),
+                timestamp.timestamp_subsec_micros(),
+                interface,
+                id,
+                data_hex
+            )
+        }
+    };
+    vec![line]
+}
 
 pub struct CanLogger {
     log_file: File,
     log_path: PathBuf,
+    log_dir: PathBuf,
+    format: LogFormat,
+
+    // Interface name written on candump lines
+    interface: String,
+
+    // Bytes in the active file, for size-based rotation
+    bytes_written: u64,
+
+    // --max-log-files cap, re-applied after every rotation
+    max_log_files: Option<usize>,
+
+    // Optional time-based rotation: start a new file once the active one is this old
+    rotate_every: Option<Duration>,
+    opened_at: Instant,
+
+    // Per-ID decimation: only every Nth frame of an ID is written (disk only, not RF)
+    decimation: HashMap<u32, u32>,
+    decimation_counters: HashMap<u32, u32>,
 }
 
 impl CanLogger {
-    pub fn new() -> Result<Self, std::io::Error> {
+    // `max_log_files` bounds how many log_*.txt/csv(.gz) files are kept (including the new
+    // one); it applies alongside the 10GB size cap, whichever removes more. Logs left
+    // uncompressed by earlier sessions are gzipped in the background.
+    pub fn new(max_log_files: Option<usize>, format: LogFormat) -> Result<Self, std::io::Error> {
+        Self::with_dir(std::env::current_dir()?, max_log_files, format)
+    }
+
+    // Log into `dir` (created if missing) instead of the working directory
+    pub fn with_dir(
+        dir: PathBuf,
+        max_log_files: Option<usize>,
+        format: LogFormat,
+    ) -> Result<Self, std::io::Error> {
+        fs::create_dir_all(&dir)?;
+
         // Clean up old logs if total size exceeds 10GB
-        Self::cleanup_logs_if_needed()?;
+        Self::cleanup_logs_if_needed(&dir)?;
+
+        let (file, log_path) = Self::open_log_file(&dir, format)?;
+
+        if let Some(max_files) = max_log_files {
+            Self::enforce_max_log_files(&dir, max_files, &log_path)?;
+        }
+
+        if let Ok(mut active) = ACTIVE_LOG_PATH.lock() {
+            *active = Some(log_path.clone());
+        }
 
+        let closed_logs: Vec<PathBuf> = Self::list_log_files(&dir)?
+            .into_iter()
+            .map(|(path, _)| path)
+            .filter(|path| path != &log_path && path.extension().is_some_and(|ext| ext != "gz"))
+            .collect();
+        Self::compress_in_background(&dir, closed_logs);
+
+        Ok(Self {
+            bytes_written: file.metadata()?.len(),
+            max_log_files,
+            rotate_every: None,
+            opened_at: Instant::now(),
+            log_file: file,
+            log_path,
+            log_dir: dir,
+            format,
+            interface: "can0".to_string(),
+            decimation: HashMap::new(),
+            decimation_counters: HashMap::new(),
+        })
+    }
+
+    // Create a new log_<timestamp> file and write the format's header
+    fn open_log_file(dir: &Path, format: LogFormat) -> Result<(File, PathBuf), std::io::Error> {
         let timestamp = Local::now().format("%Y%m%d_%H%M%S");
-        let filename = format!("log_{}.txt", timestamp);
-        let log_path = PathBuf::from(&filename);
+        let filename = format!("log_{}.{}", timestamp, format.extension());
+        let log_path = dir.join(filename);
 
         let mut file = OpenOptions::new()
             .create(true)
-            .write(true)
             .append(true)
             .open(&log_path)?;
 
-        // Write header
-        writeln!(file, "# CAN Log Started: {}", Local::now())?;
-        writeln!(file, "# Format: TIMESTAMP ARBITRATION_ID MESSAGE_DATA_HEX")?;
-        writeln!(file, "#")?;
+        // Write header (candump logs have none, so their readers see only frame lines)
+        match format {
+            LogFormat::Custom => {
+                writeln!(file, "# CAN Log Started: {}", Local::now())?;
+                writeln!(file, "# Format: TIMESTAMP ARBITRATION_ID MESSAGE_DATA_HEX")?;
+                writeln!(file, "#")?;
+            }
+            LogFormat::DecodedCsv => writeln!(file, "timestamp,message_name,signal_name,value")?,
+            LogFormat::CandumpLog => {}
+        }
 
-        Ok(Self {
-            log_file: file,
-            log_path,
-        })
+        Ok((file, log_path))
+    }
+
+    // Gzip closed logs off the GUI thread, then re-apply the size cap to the smaller files
+    fn compress_in_background(dir: &Path, paths: Vec<PathBuf>) {
+        if paths.is_empty() {
+            return;
+        }
+
+        let dir = dir.to_path_buf();
+        let spawned = std::thread::Builder::new()
+            .name("log-compress".to_string())
+            .spawn(move || {
+                for path in paths {
+                    match compress_log(&path) {
+                        Ok(gz_path) => info!("Compressed log file: {:?}", gz_path),
+                        Err(e) => warn!("Failed to compress {:?}: {}", path, e),
+                    }
+                }
+                let _ = Self::cleanup_logs_if_needed(&dir);
+            });
+        if let Err(e) = spawned {
+            warn!("Failed to start log compression: {}", e);
+        }
+    }
+
+    // Close the active log, continue in a fresh file and compress the closed one
+    fn rotate(&mut self) -> Result<(), std::io::Error> {
+        let (file, log_path) = Self::open_log_file(&self.log_dir, self.format)?;
+        self.bytes_written = file.metadata()?.len();
+        self.opened_at = Instant::now();
+        self.log_file = file;
+        let closed = std::mem::replace(&mut self.log_path, log_path);
+
+        if let Ok(mut active) = ACTIVE_LOG_PATH.lock() {
+            *active = Some(self.log_path.clone());
+        }
+        self.write_decimation_header();
+
+        info!("CAN log rotated: {:?}", self.log_path);
+        if let Some(max_files) = self.max_log_files {
+            Self::enforce_max_log_files(&self.log_dir, max_files, &self.log_path)?;
+        }
+        // The cap may already have removed the closed log
+        if closed.exists() {
+            Self::compress_in_background(&self.log_dir, vec![closed]);
+        }
+        Ok(())
+    }
+
+    // Interface name recorded on candump lines (defaults to can0)
+    pub fn with_interface(mut self, interface: &str) -> Self {
+        self.interface = interface.to_string();
+        self
+    }
+
+    // Also roll over to a fresh file every `interval`, independent of the size limit
+    pub fn with_rotation_interval(mut self, interval: Duration) -> Self {
+        self.rotate_every = Some(interval);
+        self
+    }
+
+    // Log only every Nth frame for the given IDs (N <= 1 logs every frame)
+    pub fn with_decimation(mut self, decimation: HashMap<u32, u32>) -> Self {
+        self.decimation = decimation;
+        self.write_decimation_header();
+        self
     }
 
-    fn cleanup_logs_if_needed() -> Result<(), std::io::Error> {
+    fn write_decimation_header(&mut self) {
+        if !self.decimation.is_empty() && self.format == LogFormat::Custom {
+            let _ = writeln!(
+                self.log_file,
+                "# Decimated IDs (every Nth frame logged): {}",
+                self.decimation
+                    .iter()
+                    .map(|(id, n)| format!("0x{:X}={}", id, n))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            );
+        }
+    }
+
+    // Advance the decimation counter for `raw_id`; true if this frame should be written
+    fn should_log(&mut self, raw_id: u32) -> bool {
+        let Some(&every_nth) = self.decimation.get(&raw_id) else {
+            return true;
+        };
+
+        let counter = self.decimation_counters.entry(raw_id).or_insert(0);
+        let log_this = *counter == 0;
+        *counter = (*counter + 1) % every_nth.max(1);
+        log_this
+    }
+
+    // Collect log files in `dir` with their modification times (oldest first)
+    fn list_log_files(dir: &Path) -> Result<Vec<(PathBuf, std::time::SystemTime)>, std::io::Error> {
+        let mut log_files = Vec::new();
+
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
+                if is_log_file(filename) {
+                    log_files.push((path, entry.metadata()?.modified()?));
+                }
+            }
+        }
+
+        log_files.sort_by_key(|(_, modified)| *modified);
+        Ok(log_files)
+    }
+
+    // Delete the oldest log files so at most `max_files` remain; the active log is never removed
+    fn enforce_max_log_files(
+        dir: &Path,
+        max_files: usize,
+        current_log: &Path,
+    ) -> Result<(), std::io::Error> {
+        let current_name = current_log.file_name();
+        let older_logs: Vec<PathBuf> = Self::list_log_files(dir)?
+            .into_iter()
+            .map(|(path, _)| path)
+            .filter(|path| path.file_name() != current_name)
+            .collect();
+
+        // The active log takes one of the slots
+        let keep = max_files.saturating_sub(1);
+        let excess = older_logs.len().saturating_sub(keep);
+
+        for path in older_logs.into_iter().take(excess) {
+            if fs::remove_file(&path).is_ok() {
+                info!("Removed old log file: {:?}", path);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn cleanup_logs_if_needed(log_dir: &Path) -> Result<(), std::io::Error> {
         const MAX_SIZE_BYTES: u64 = 10 * 1024 * 1024 * 1024; // 10GB
 
-        let current_dir = std::env::current_dir()?;
         let mut log_files = Vec::new();
         let mut total_size = 0u64;
 
         // Find all log files and calculate total size
-        for entry in fs::read_dir(&current_dir)? {
+        for entry in fs::read_dir(log_dir)? {
             let entry = entry?;
             let path = entry.path();
 
             if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-                if filename.starts_with("log_") && filename.ends_with(".txt") {
+                if is_log_file(filename) {
                     let metadata = entry.metadata()?;
                     let size = metadata.len();
                     total_size += size;
@@ -79,28 +490,239 @@ impl CanLogger {
         Ok(())
     }
 
-    pub fn log_frame(&mut self, frame: &CanFrame) -> Result<(), std::io::Error> {
-        let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S.%3f");
-
-        let id = match frame.id() {
-            socketcan::Id::Standard(std_id) => format!("0x{:03X}", std_id.as_raw()),
-            socketcan::Id::Extended(ext_id) => format!("0x{:08X}", ext_id.as_raw()),
+    // `decoded` is the decoder's output for `frame`; only the CSV format uses it
+    pub fn log_frame(
+        &mut self,
+        frame: &CanFrame,
+        decoded: &DecodedMessage,
+    ) -> Result<(), std::io::Error> {
+        let raw_id = match frame.id() {
+            socketcan::Id::Standard(std_id) => std_id.as_raw() as u32,
+            socketcan::Id::Extended(ext_id) => ext_id.as_raw(),
         };
+        if !self.should_log(raw_id) {
+            return Ok(());
+        }
 
-        let data_hex = frame
-            .data()
-            .iter()
-            .map(|b| format!("{:02X}", b))
-            .collect::<Vec<_>>()
-            .join(" ");
-
-        writeln!(self.log_file, "{} {} {}", timestamp, id, data_hex)?;
+        for line in format_frame(self.format, &self.interface, Local::now(), frame, decoded) {
+            writeln!(self.log_file, "{}", line)?;
+            self.bytes_written += line.len() as u64 + 1;
+        }
         self.log_file.flush()?;
 
+        let interval_elapsed = self
+            .rotate_every
+            .is_some_and(|interval| self.opened_at.elapsed() >= interval);
+        if self.bytes_written >= ROTATE_LOG_BYTES || interval_elapsed {
+            self.rotate()?;
+        }
+
         Ok(())
     }
 
     pub fn get_log_path(&self) -> &PathBuf {
         &self.log_path
     }
+
+    // Lines are flushed as they are written; this also waits for them to reach the disk
+    pub fn sync(&mut self) -> Result<(), std::io::Error> {
+        self.log_file.flush()?;
+        self.log_file.sync_all()
+    }
+
+    pub fn log_dir(&self) -> &Path {
+        &self.log_dir
+    }
+}
+
+// Whether a fault became active (or changed severity) or went away
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultEvent {
+    Set,
+    Cleared,
+}
+
+// One fault timeline line: time, SET/CLEAR, severity, source message, fault, value
+fn format_fault_event(timestamp: DateTime<Local>, event: FaultEvent, fault: &Fault) -> String {
+    format!(
+        "{} | {} | {:?} | {} | {} | {}",
+        timestamp.format("%Y-%m-%d %H:%M:%S%.3f"),
+        match event {
+            FaultEvent::Set => "SET",
+            FaultEvent::Cleared => "CLEAR",
+        },
+        fault.severity,
+        fault.message_name,
+        fault.name,
+        fault.value
+    )
+}
+
+// Fault history for post-run review, written as faults_<timestamp>.txt next to the CAN logs.
+// Unlike the CAN log it is neither rotated nor compressed; it stays small.
+pub struct FaultLogger {
+    log_file: File,
+    log_path: PathBuf,
+}
+
+impl FaultLogger {
+    pub fn with_dir(dir: &Path) -> Result<Self, std::io::Error> {
+        fs::create_dir_all(dir)?;
+        let timestamp = Local::now().format("%Y%m%d_%H%M%S");
+        let log_path = dir.join(format!("faults_{}.txt", timestamp));
+
+        let mut log_file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)?;
+        writeln!(log_file, "# Fault Log Started: {}", Local::now())?;
+        writeln!(
+            log_file,
+            "# Format: TIMESTAMP | SET/CLEAR | SEVERITY | MESSAGE | FAULT | VALUE"
+        )?;
+        writeln!(log_file, "#")?;
+
+        Ok(Self { log_file, log_path })
+    }
+
+    // `fault` is the fault as raised, or as it last was when cleared
+    pub fn log_event(&mut self, event: FaultEvent, fault: &Fault) -> Result<(), std::io::Error> {
+        writeln!(
+            self.log_file,
+            "{}",
+            format_fault_event(Local::now(), event, fault)
+        )?;
+        self.log_file.flush()
+    }
+
+    pub fn get_log_path(&self) -> &PathBuf {
+        &self.log_path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::can::DecodedSignal;
+    use chrono::TimeZone;
+    use socketcan::{ExtendedId, StandardId};
+
+    #[test]
+    fn test_candump_line_format() {
+        let timestamp = Local.timestamp_opt(1_700_000_000, 123_456_000).unwrap();
+        let standard = CanFrame::new(StandardId::new(0x123).unwrap(), &[0xAA, 0xBB, 0xCC]).unwrap();
+        let extended = CanFrame::new(ExtendedId::new(0x0CF11E05).unwrap(), &[0x01]).unwrap();
+        let unknown = DecodedMessage::unknown();
+        let lines = |format, frame| format_frame(format, "can0", timestamp, frame, &unknown);
+
+        assert_eq!(
+            lines(LogFormat::CandumpLog, &standard),
+            ["(1700000000.123456) can0 123#AABBCC"]
+        );
+        assert_eq!(
+            lines(LogFormat::CandumpLog, &extended),
+            ["(1700000000.123456) can0 0CF11E05#01"]
+        );
+        assert!(lines(LogFormat::Custom, &standard)[0].ends_with(" 0x123 AA BB CC"));
+    }
+
+    #[test]
+    fn test_csv_rows_per_signal() {
+        let timestamp = Local.timestamp_opt(1_700_000_000, 0).unwrap();
+        let frame = CanFrame::new(StandardId::new(0x320).unwrap(), &[0; 4]).unwrap();
+        let signal = |name: &str, value: f64| DecodedSignal {
+            name: name.to_string(),
+            raw: 0,
+            value,
+            label: None,
+            unit: String::new(),
+            out_of_range: false,
+        };
+        let decoded = DecodedMessage {
+            message_name: "BMS_Power".to_string(),
+            decoded: true,
+            dbc_name: Some("MSGID_0X320".to_string()),
+            signals: vec![signal("Pack_Voltage", 101.5), signal("Pack_Current", -12.3)],
+        };
+
+        let rows = format_frame(LogFormat::DecodedCsv, "can0", timestamp, &frame, &decoded);
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].ends_with(".000,BMS_Power,Pack_Voltage,101.5"));
+        assert!(rows[1].ends_with(",BMS_Power,Pack_Current,-12.3"));
+
+        let unknown = DecodedMessage::unknown();
+        let rows = format_frame(LogFormat::DecodedCsv, "can0", timestamp, &frame, &unknown);
+        assert!(rows.is_empty());
+    }
+
+    #[test]
+    fn test_compress_log_round_trip() {
+        use flate2::read::GzDecoder;
+        use std::io::Read;
+
+        let path = std::env::temp_dir().join(format!("log_test_{}.txt", std::process::id()));
+        fs::write(&path, "2025-01-02 03:04:05.678 0x300 01 02\n").unwrap();
+
+        let gz_path = compress_log(&path).unwrap();
+        assert!(!path.exists());
+        assert!(is_log_file(gz_path.file_name().unwrap().to_str().unwrap()));
+
+        let mut contents = String::new();
+        GzDecoder::new(File::open(&gz_path).unwrap())
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert_eq!(contents, "2025-01-02 03:04:05.678 0x300 01 02\n");
+        fs::remove_file(gz_path).unwrap();
+    }
+
+    #[test]
+    fn test_rotation_applies_max_log_files() {
+        let dir = std::env::temp_dir().join(format!("log_rotate_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut logger = CanLogger::with_dir(dir.clone(), Some(2), LogFormat::Custom).unwrap();
+        for i in 0..3 {
+            fs::write(dir.join(format!("log_20000101_00000{}.txt", i)), "").unwrap();
+        }
+
+        // Log names have one-second resolution
+        std::thread::sleep(Duration::from_millis(1100));
+        logger.rotate().unwrap();
+        assert_eq!(CanLogger::list_log_files(&dir).unwrap().len(), 2);
+        assert!(logger.log_path.exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_screenshot_png_round_trip() {
+        let path = std::env::temp_dir().join(format!("screenshot_test_{}.png", std::process::id()));
+        let rgba = [255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 0, 0, 0, 0];
+        write_png(&path, 2, 2, &rgba).unwrap();
+
+        let mut reader = png::Decoder::new(File::open(&path).unwrap())
+            .read_info()
+            .unwrap();
+        let mut decoded = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut decoded).unwrap();
+        assert_eq!((info.width, info.height), (2, 2));
+        assert_eq!(&decoded[..info.buffer_size()], &rgba);
+        assert!(write_png(&path, 3, 3, &rgba).is_err()); // Buffer doesn't match the size
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_fault_event_line() {
+        let timestamp = Local.with_ymd_and_hms(2025, 1, 2, 3, 4, 5).unwrap();
+        let fault = Fault {
+            name: "Log disk low".to_string(),
+            timestamp: chrono::Utc::now(),
+            value: "120 MB free".to_string(),
+            message_name: "Logger".to_string(),
+            severity: crate::gui_modules::FaultSeverity::Warning,
+        };
+
+        assert_eq!(
+            format_fault_event(timestamp, FaultEvent::Cleared, &fault),
+            "2025-01-02 03:04:05.000 | CLEAR | Warning | Logger | Log disk low | 120 MB free"
+        );
+    }
 }
@@ -1,37 +1,832 @@
+mod alarm;
+mod busload;
 mod can;
+mod config;
 mod gui;
 mod gui_modules;
+mod headless;
+mod health;
+mod influx;
 mod logger;
+mod mqtt;
+mod odometer;
 mod proto;
+mod replay;
 mod serial;
+mod session;
+mod simulate;
+mod udp;
+mod ws;
 
+use can::CanDecoder;
 use clap::{Arg, Command};
+use config::{
+    parse_can_id_list, parse_current_limit, parse_decimation_list, parse_hour_window,
+    parse_percent, parse_replay_speed, parse_wheel_diameter, AppConfig,
+};
 use gui::TelemetryGui;
+use gui_modules::{FaultPalette, SignalMappings};
 use iced::{Application, Settings};
+use influx::InfluxConfig;
+use log::{error, info, warn};
+use logger::LogFormat;
+use serial::{BatchConfig, ChecksumMode, OverflowPolicy, DEFAULT_PRESERVED_PER_ID};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+// Log to stderr at info (or -v debug, -vv trace) for this crate and warn for dependencies,
+// so wgpu/winit stay quiet; a RUST_LOG filter replaces this default entirely
+fn init_logging(verbosity: u8) {
+    let level = match verbosity {
+        0 => "info",
+        1 => "debug",
+        _ => "trace",
+    };
+    let default_filter = format!("warn,telemetry_rs={}", level);
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(default_filter))
+        .init();
+}
 
 fn main() -> iced::Result {
+    logger::install_panic_hook();
+
     let matches = Command::new("telemetry-rs")
         .version("0.1.0")
         .author("Your Name")
         .about("Telemetry application with CAN bus and radio support")
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .action(clap::ArgAction::Count)
+                .help("Log more detail: -v for debug, -vv for trace (RUST_LOG overrides)"),
+        )
         .arg(
             Arg::new("disable-rfd")
                 .long("disable-rfd")
                 .help("Disable RFD 900x2 modem")
                 .action(clap::ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("rfd-baud")
+                .long("rfd-baud")
+                .value_name("BAUD")
+                .value_parser(clap::value_parser!(u32).range(1..))
+                .default_value("57600")
+                .help("Serial baud rate of the RFD 900x2 modem"),
+        )
+        .arg(
+            Arg::new("rfd-port")
+                .long("rfd-port")
+                .value_name("PORT")
+                .help("Serial port of the RFD modem (e.g. /dev/ttyUSB1) instead of scanning"),
+        )
+        .arg(
+            Arg::new("no-rfd-scan")
+                .long("no-rfd-scan")
+                .help("Never probe serial ports for the RFD (only --rfd-port is used)")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("scan-all-ports")
+                .long("scan-all-ports")
+                .help("Probe every serial port for modems, not only known USB adapters")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("lora")
+                .long("lora")
+                .help("Also transmit over a LoRa modem (backup link alongside the RFD)")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("can-ids")
+                .long("can-ids")
+                .value_name("IDS")
+                .value_parser(parse_can_id_list)
+                .help(
+                    "Comma-separated CAN IDs to receive (e.g. 0x300,0x0CF11E05); \
+                     all other frames are dropped by the kernel and never logged",
+                ),
+        )
+        .arg(
+            Arg::new("promiscuous")
+                .long("promiscuous")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with("can-ids")
+                .help(
+                    "Receive every CAN ID instead of only those in the DBC \
+                     (for finding undecoded IDs)",
+                ),
+        )
+        .arg(
+            Arg::new("tx-ids")
+                .long("tx-ids")
+                .value_name("IDS")
+                .value_parser(parse_can_id_list)
+                .help(
+                    "Comma-separated CAN IDs to transmit over RFD; \
+                     other frames are still displayed and logged but never sent",
+                ),
+        )
+        .arg(
+            Arg::new("known-unknown-ids")
+                .long("known-unknown-ids")
+                .value_name("IDS")
+                .value_parser(parse_can_id_list)
+                .help(
+                    "Comma-separated CAN IDs expected on the bus but not in the DBC; \
+                     they are not reported as undecoded",
+                ),
+        )
+        .arg(
+            Arg::new("critical-ids")
+                .long("critical-ids")
+                .value_name("IDS")
+                .value_parser(parse_can_id_list)
+                .help("Comma-separated CAN IDs to transmit at Critical priority over RF"),
+        )
+        .arg(
+            Arg::new("preserve-ids")
+                .long("preserve-ids")
+                .value_name("IDS")
+                .value_parser(parse_can_id_list)
+                .help(
+                    "Comma-separated CAN IDs (e.g. 0x300,0x776,0x777) whose frames are all \
+                     sent over RF instead of only the latest one per batch. At most \
+                     --preserve-depth frames per ID are queued; older ones are dropped",
+                ),
+        )
+        .arg(
+            Arg::new("preserve-depth")
+                .long("preserve-depth")
+                .value_name("FRAMES")
+                .value_parser(clap::builder::RangedU64ValueParser::<usize>::new().range(1..))
+                .default_value("4")
+                .help("Most queued frames per --preserve-ids ID before the oldest is dropped"),
+        )
+        .arg(
+            Arg::new("can-bitrate")
+                .long("can-bitrate")
+                .value_name("BPS")
+                .value_parser(clap::value_parser!(u32).range(1..))
+                .default_value("500000")
+                .help("Nominal CAN bus bitrate used for load and latency estimates"),
+        )
+        .arg(
+            Arg::new("wheel-diameter")
+                .long("wheel-diameter")
+                .value_name("INCHES")
+                .value_parser(parse_wheel_diameter)
+                .default_value("23.5")
+                .help("Wheel diameter used to convert motor RPM to speed and distance"),
+        )
+        .arg(
+            Arg::new("data-stale-ms")
+                .long("data-stale-ms")
+                .value_name("MS")
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .default_value("2000")
+                .help("Gray out battery/MPPT/BPS values not updated for this long"),
+        )
+        .arg(
+            Arg::new("current-warn")
+                .long("current-warn")
+                .value_name("AMPS")
+                .value_parser(parse_current_limit)
+                .default_value("70")
+                .help("Battery current that shows the slow-down warning"),
+        )
+        .arg(
+            Arg::new("current-critical")
+                .long("current-critical")
+                .value_name("AMPS")
+                .value_parser(parse_current_limit)
+                .help("Battery current that turns the warning critical (above --current-warn)"),
+        )
+        .arg(
+            Arg::new("can-stale-ms")
+                .long("can-stale-ms")
+                .value_name("MS")
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .default_value("2000")
+                .help("Re-open the CAN socket after MS without frames (recovers from link flaps)"),
+        )
+        .arg(
+            Arg::new("can-retry-ms")
+                .long("can-retry-ms")
+                .value_name("MS")
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .default_value("1000")
+                .help("Delay between attempts to re-open the CAN interface"),
+        )
+        .arg(
+            Arg::new("can-online-frames")
+                .long("can-online-frames")
+                .value_name("N")
+                .value_parser(clap::value_parser!(u32).range(1..))
+                .default_value("3")
+                .help("Consecutive frames required before the CAN indicator shows ONLINE again"),
+        )
+        .arg(
+            Arg::new("range-faults")
+                .long("range-faults")
+                .help(
+                    "Raise warnings when range-checked signals decode outside their DBC min/max \
+                     (signals are listed in the [range_checks] table of --mappings)",
+                )
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("clamp-signals")
+                .long("clamp-signals")
+                .help("Clip decoded signal values into their DBC min/max to hide bus glitches")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("windowed")
+                .long("windowed")
+                .help("Stay in a window instead of switching to fullscreen")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("fullscreen-delay")
+                .long("fullscreen-delay")
+                .value_name("MS")
+                .value_parser(clap::value_parser!(u64))
+                .default_value("500")
+                .help("Delay before going fullscreen so the window manager can place the window"),
+        )
+        .arg(
+            Arg::new("faults-per-page")
+                .long("faults-per-page")
+                .value_name("N")
+                .value_parser(clap::value_parser!(u32).range(1..))
+                .default_value("3")
+                .help("Faults shown per page of the fault panel"),
+        )
+        .arg(
+            Arg::new("fault-cycle-ms")
+                .long("fault-cycle-ms")
+                .value_name("MS")
+                .value_parser(clap::value_parser!(u64).range(100..))
+                .default_value("2000")
+                .help("How long each fault page shows when there are more faults than fit"),
+        )
+        .arg(
+            Arg::new("auto-dim")
+                .long("auto-dim")
+                .value_name("START-END")
+                .value_parser(parse_hour_window)
+                .help("Dim the display between these local hours, e.g. 20-6 (toggle with 'n')"),
+        )
+        .arg(
+            Arg::new("palette")
+                .long("palette")
+                .value_name("NAME")
+                .value_parser(FaultPalette::from_name)
+                .help("Fault severity colors: default (yellow/red) or colorblind (yellow/orange/blue)"),
+        )
+        .arg(
+            Arg::new("max-log-files")
+                .long("max-log-files")
+                .value_name("N")
+                .value_parser(clap::value_parser!(usize))
+                .help("Keep only the newest N log files (applies alongside the 10GB size cap)"),
+        )
+        .arg(
+            Arg::new("log-format")
+                .long("log-format")
+                .value_name("FORMAT")
+                .value_parser(LogFormat::from_name)
+                .help("CAN log format: custom (default, replayable), candump (candump -l) or csv (decoded)"),
+        )
+        .arg(
+            Arg::new("log-dir")
+                .long("log-dir")
+                .value_name("DIR")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Write CAN logs and session summaries to DIR (created if missing)"),
+        )
+        .arg(
+            Arg::new("log-rotate-minutes")
+                .long("log-rotate-minutes")
+                .value_name("N")
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Start a new CAN log file every N minutes"),
+        )
+        .arg(
+            Arg::new("low-disk-mb")
+                .long("low-disk-mb")
+                .value_name("MB")
+                .value_parser(clap::value_parser!(u64))
+                .default_value("500")
+                .help("Raise a warning when the log directory has less than MB free"),
+        )
+        .arg(
+            Arg::new("log-decimate")
+                .long("log-decimate")
+                .value_name("ID=N,...")
+                .value_parser(parse_decimation_list)
+                .help("Log only every Nth frame of the given IDs, e.g. 0x0CF11E05=4 (disk only)"),
+        )
+        .arg(
+            Arg::new("sim-loss")
+                .long("sim-loss")
+                .value_name("PCT")
+                .value_parser(parse_percent)
+                .help("Drop PCT% of RFD batches to test link resilience"),
+        )
+        .arg(
+            Arg::new("sim-corrupt")
+                .long("sim-corrupt")
+                .value_name("PCT")
+                .value_parser(parse_percent)
+                .help("Flip a bit in PCT% of transmitted RFD bytes to test CRC detection"),
+        )
+        .arg(
+            Arg::new("sim-seed")
+                .long("sim-seed")
+                .value_name("SEED")
+                .value_parser(clap::value_parser!(u64))
+                .default_value("0")
+                .help("RNG seed for --sim-loss/--sim-corrupt so runs are reproducible"),
+        )
+        .arg(
+            Arg::new("batch-size")
+                .long("batch-size")
+                .value_name("FRAMES")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("8")
+                .help("Most frames per RF batch (routine lane)"),
+        )
+        .arg(
+            Arg::new("batch-bytes")
+                .long("batch-bytes")
+                .value_name("BYTES")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("100")
+                .help("Most encoded frame bytes per RF batch (routine lane)"),
+        )
+        .arg(
+            Arg::new("batch-timeout-ms")
+                .long("batch-timeout-ms")
+                .value_name("MS")
+                .value_parser(clap::value_parser!(u64))
+                .default_value("20")
+                .help("Send a partial RF batch after MS milliseconds (routine lane)"),
+        )
+        .arg(
+            Arg::new("batch-timeout-max-ms")
+                .long("batch-timeout-max-ms")
+                .value_name("MS")
+                .value_parser(clap::value_parser!(u64))
+                .default_value("200")
+                .help(
+                    "Longest the routine-lane batch timeout stretches to while RF writes fail \
+                     (set to --batch-timeout-ms to keep it fixed)",
+                ),
+        )
+        .arg(
+            Arg::new("overflow-policy")
+                .long("overflow-policy")
+                .value_name("POLICY")
+                .value_parser(OverflowPolicy::from_name)
+                .help(
+                    "When the radio can't keep up: drop-oldest (default), drop-lowest-priority \
+                     or block (wait for the port, delaying CAN reads)",
+                ),
+        )
+        .arg(
+            Arg::new("crc16")
+                .long("crc16")
+                .help("Use CRC16 instead of CRC32 frame/batch checksums to save RF bandwidth")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("ws-port")
+                .long("ws-port")
+                .value_name("PORT")
+                .value_parser(clap::value_parser!(u16).range(1..))
+                .help("Stream live telemetry JSON to WebSocket clients on PORT (off by default)"),
+        )
+        .arg(
+            Arg::new("mqtt-host")
+                .long("mqtt-host")
+                .value_name("HOST")
+                .help("Publish every decoded signal to the MQTT broker at HOST (off by default)"),
+        )
+        .arg(
+            Arg::new("mqtt-port")
+                .long("mqtt-port")
+                .value_name("PORT")
+                .value_parser(clap::value_parser!(u16).range(1..))
+                .default_value("1883")
+                .requires("mqtt-host")
+                .help("MQTT broker port"),
+        )
+        .arg(
+            Arg::new("influx-url")
+                .long("influx-url")
+                .value_name("URL")
+                .help("POST decoded frames as InfluxDB line protocol to this write URL (off by default)"),
+        )
+        .arg(
+            Arg::new("influx-token")
+                .long("influx-token")
+                .value_name("TOKEN")
+                .requires("influx-url")
+                .help("InfluxDB API token, sent as \"Authorization: Token TOKEN\""),
+        )
+        .arg(
+            Arg::new("influx-batch")
+                .long("influx-batch")
+                .value_name("POINTS")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("500")
+                .requires("influx-url")
+                .help("Most points per InfluxDB write"),
+        )
+        .arg(
+            Arg::new("influx-interval-ms")
+                .long("influx-interval-ms")
+                .value_name("MS")
+                .value_parser(clap::value_parser!(u64).range(100..))
+                .default_value("1000")
+                .requires("influx-url")
+                .help("Write pending InfluxDB points at least this often"),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .action(clap::ArgAction::SetTrue)
+                .help("Skip the GUI and print each decoded can0 frame to stdout as a JSON line"),
+        )
+        .arg(
+            Arg::new("export-schema")
+                .long("export-schema")
+                .value_name("FILE")
+                .help("Write the DBC-decoded signal schema as JSON to FILE and exit"),
+        )
+        .arg(
+            Arg::new("mappings")
+                .long("mappings")
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help(
+                    "TOML file mapping DBC signals to GUI values and faults \
+                     (default: signal_mappings.toml if present)",
+                ),
+        )
+        .arg(
+            Arg::new("export-mappings")
+                .long("export-mappings")
+                .value_name("FILE")
+                .help("Write the built-in signal mappings as TOML to FILE and exit"),
+        )
+        .arg(
+            Arg::new("replay-to-can")
+                .long("replay-to-can")
+                .value_name("IFACE")
+                .requires("replay-log")
+                .help("Replay --replay-log onto IFACE (e.g. vcan0) at original timing and exit"),
+        )
+        .arg(
+            Arg::new("replay-log")
+                .long("replay-log")
+                .value_name("FILE")
+                .help("CAN log file (log_*.txt) to replay"),
+        )
+        .arg(
+            Arg::new("replay")
+                .long("replay")
+                .value_name("FILE")
+                .conflicts_with("replay-to-can")
+                .help("Run the GUI on a recorded CAN log (log_*.txt) instead of can0"),
+        )
+        .arg(
+            Arg::new("allow-can-inject")
+                .long("allow-can-inject")
+                .action(clap::ArgAction::SetTrue)
+                .help("Transmit CAN frames requested over the RF link onto can0"),
+        )
+        .arg(
+            Arg::new("no-audio")
+                .long("no-audio")
+                .action(clap::ArgAction::SetTrue)
+                .help("Don't beep when a critical fault is raised (bench testing)"),
+        )
+        .arg(
+            Arg::new("simulate")
+                .long("simulate")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with_all(["replay", "replay-to-can"])
+                .help("Run the GUI on simulated BMS/MPPT/motor traffic instead of can0"),
+        )
+        .arg(
+            Arg::new("udp-broadcast")
+                .long("udp-broadcast")
+                .value_name("ADDR:PORT")
+                .value_parser(clap::value_parser!(SocketAddr))
+                .help("Send every received CAN frame over UDP to ADDR:PORT (e.g. 192.168.1.255:5555)"),
+        )
+        .arg(
+            Arg::new("udp-listen")
+                .long("udp-listen")
+                .value_name("PORT")
+                .value_parser(clap::value_parser!(u16).range(1..))
+                .conflicts_with_all(["replay", "replay-to-can", "simulate", "udp-broadcast"])
+                .help("Run the GUI on CAN frames from a --udp-broadcast feed instead of can0"),
+        )
+        .arg(
+            Arg::new("replay-speed")
+                .long("replay-speed")
+                .value_name("X")
+                .value_parser(parse_replay_speed)
+                .default_value("1")
+                .requires("replay")
+                .help("Replay --replay at X times the recorded speed"),
+        )
         .get_matches();
 
-    let rfd_enabled = !matches.get_flag("disable-rfd");
+    init_logging(matches.get_count("verbose"));
+
+    if let Some(path) = matches.get_one::<String>("export-schema") {
+        let decoder = CanDecoder::new("telemetry.dbc");
+        let result = decoder
+            .export_schema_json()
+            .map_err(|e| e.to_string())
+            .and_then(|json| std::fs::write(path, json).map_err(|e| e.to_string()));
+
+        match result {
+            Ok(()) => {
+                info!("Signal schema exported to {}", path);
+                return Ok(());
+            }
+            Err(e) => {
+                error!("Failed to export signal schema: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if let Some(path) = matches.get_one::<String>("export-mappings") {
+        let result = SignalMappings::builtin()
+            .to_toml_string()
+            .and_then(|toml| std::fs::write(path, toml).map_err(|e| e.to_string()));
+
+        match result {
+            Ok(()) => {
+                info!("Signal mappings exported to {}", path);
+                return Ok(());
+            }
+            Err(e) => {
+                error!("Failed to export signal mappings: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if let Some(iface) = matches.get_one::<String>("replay-to-can") {
+        let log_path = matches.get_one::<String>("replay-log").unwrap();
+        info!("Replaying {} onto {}", log_path, iface);
 
-    println!("Starting Telemetry Application");
-    println!(
+        match replay::replay_to_can(log_path, iface) {
+            Ok(count) => {
+                info!("Replay complete: {} frames written to {}", count, iface);
+                return Ok(());
+            }
+            Err(e) => {
+                error!("Replay failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if matches.get_flag("json") {
+        let mut decoder = CanDecoder::new("telemetry.dbc");
+        decoder.set_clamp_to_range(matches.get_flag("clamp-signals"));
+        let mappings = matches.get_one::<PathBuf>("mappings");
+        decoder.set_bit_faults(
+            SignalMappings::load_or_builtin(mappings.map(PathBuf::as_path)).bit_faults,
+        );
+        // Same kernel filter default as the GUI: DBC IDs unless --can-ids or --promiscuous
+        let id_allowlist = match matches.get_one::<Vec<u32>>("can-ids") {
+            Some(ids) => Some(ids.clone()),
+            None if matches.get_flag("promiscuous") => None,
+            None => Some(decoder.receive_ids()),
+        };
+
+        match headless::run_json(&decoder, "can0", id_allowlist.as_deref()) {
+            Ok(()) => return Ok(()),
+            Err(e) => {
+                error!("{}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let batch_config = BatchConfig::new(
+        *matches.get_one::<usize>("batch-size").unwrap(),
+        *matches.get_one::<usize>("batch-bytes").unwrap(),
+        *matches.get_one::<u64>("batch-timeout-ms").unwrap(),
+        *matches.get_one::<u64>("batch-timeout-max-ms").unwrap(),
+    )
+    .unwrap_or_else(|e| {
+        error!("{}", e);
+        std::process::exit(1);
+    });
+
+    let influx = matches
+        .get_one::<String>("influx-url")
+        .map(|url| InfluxConfig {
+            url: url.clone(),
+            token: matches.get_one::<String>("influx-token").cloned(),
+            batch_size: *matches.get_one::<usize>("influx-batch").unwrap(),
+            flush_interval: Duration::from_millis(
+                *matches.get_one::<u64>("influx-interval-ms").unwrap(),
+            ),
+        });
+
+    let current_warn_amps = *matches.get_one::<f64>("current-warn").unwrap();
+    let current_critical_amps = matches.get_one::<f64>("current-critical").copied();
+    if current_critical_amps.is_some_and(|critical| critical <= current_warn_amps) {
+        error!("--current-critical must be above --current-warn");
+        std::process::exit(1);
+    }
+
+    let config = AppConfig {
+        rfd_enabled: !matches.get_flag("disable-rfd"),
+        rfd_baud_rate: *matches.get_one::<u32>("rfd-baud").unwrap(),
+        rfd_port: matches.get_one::<String>("rfd-port").cloned(),
+        rfd_auto_scan: !matches.get_flag("no-rfd-scan"),
+        scan_all_ports: matches.get_flag("scan-all-ports"),
+        lora_enabled: matches.get_flag("lora"),
+        batch_config,
+        overflow_policy: matches
+            .get_one::<OverflowPolicy>("overflow-policy")
+            .copied()
+            .unwrap_or_default(),
+        checksum_mode: if matches.get_flag("crc16") {
+            ChecksumMode::Crc16
+        } else {
+            ChecksumMode::Crc32
+        },
+        can_inject: matches.get_flag("allow-can-inject"),
+        can_id_allowlist: matches.get_one::<Vec<u32>>("can-ids").cloned(),
+        can_promiscuous: matches.get_flag("promiscuous"),
+        tx_id_allowlist: matches.get_one::<Vec<u32>>("tx-ids").cloned(),
+        known_unknown_ids: matches
+            .get_one::<Vec<u32>>("known-unknown-ids")
+            .cloned()
+            .unwrap_or_default(),
+        can_bitrate: *matches.get_one::<u32>("can-bitrate").unwrap(),
+        can_stale_ms: *matches.get_one::<u64>("can-stale-ms").unwrap(),
+        can_retry_ms: *matches.get_one::<u64>("can-retry-ms").unwrap(),
+        can_online_frames: *matches.get_one::<u32>("can-online-frames").unwrap(),
+        data_stale_ms: *matches.get_one::<u64>("data-stale-ms").unwrap(),
+        wheel_diameter_inches: *matches.get_one::<f64>("wheel-diameter").unwrap(),
+        current_warn_amps,
+        current_critical_amps,
+        audio_alarm: !matches.get_flag("no-audio"),
+        range_faults_enabled: matches.get_flag("range-faults"),
+        clamp_signals: matches.get_flag("clamp-signals"),
+        windowed: matches.get_flag("windowed"),
+        fullscreen_delay_ms: *matches.get_one::<u64>("fullscreen-delay").unwrap(),
+        auto_dim_hours: matches.get_one::<(u32, u32)>("auto-dim").copied(),
+        mappings_file: matches.get_one::<PathBuf>("mappings").cloned(),
+        fault_palette: matches
+            .get_one::<FaultPalette>("palette")
+            .copied()
+            .unwrap_or_default(),
+        faults_per_page: *matches.get_one::<u32>("faults-per-page").unwrap() as usize,
+        fault_cycle_ms: *matches.get_one::<u64>("fault-cycle-ms").unwrap(),
+        max_log_files: matches.get_one::<usize>("max-log-files").copied(),
+        log_format: matches
+            .get_one::<LogFormat>("log-format")
+            .copied()
+            .unwrap_or_default(),
+        log_dir: matches.get_one::<PathBuf>("log-dir").cloned(),
+        log_rotate_minutes: matches.get_one::<u64>("log-rotate-minutes").copied(),
+        sim_loss: matches.get_one::<f64>("sim-loss").copied().unwrap_or(0.0),
+        sim_corrupt: matches
+            .get_one::<f64>("sim-corrupt")
+            .copied()
+            .unwrap_or(0.0),
+        sim_seed: *matches.get_one::<u64>("sim-seed").unwrap(),
+        low_disk_mb: *matches.get_one::<u64>("low-disk-mb").unwrap(),
+        ws_port: matches.get_one::<u16>("ws-port").copied(),
+        mqtt_host: matches.get_one::<String>("mqtt-host").cloned(),
+        mqtt_port: *matches.get_one::<u16>("mqtt-port").unwrap(),
+        influx,
+        replay_log: matches.get_one::<String>("replay").cloned(),
+        replay_speed: *matches.get_one::<f64>("replay-speed").unwrap(),
+        simulate: matches.get_flag("simulate"),
+        udp_broadcast: matches.get_one::<SocketAddr>("udp-broadcast").copied(),
+        udp_listen_port: matches.get_one::<u16>("udp-listen").copied(),
+        log_decimation: matches
+            .get_one::<HashMap<u32, u32>>("log-decimate")
+            .cloned()
+            .unwrap_or_default(),
+        critical_ids: matches
+            .get_one::<Vec<u32>>("critical-ids")
+            .cloned()
+            .unwrap_or_default(),
+        preserve_ids: matches
+            .get_one::<Vec<u32>>("preserve-ids")
+            .cloned()
+            .unwrap_or_default(),
+        preserve_depth: matches
+            .get_one::<usize>("preserve-depth")
+            .copied()
+            .unwrap_or(DEFAULT_PRESERVED_PER_ID),
+    };
+
+    info!("Starting Telemetry Application");
+    info!(
         "RFD 900x2 modem: {}",
-        if rfd_enabled { "ENABLED" } else { "DISABLED" }
+        if config.rfd_enabled {
+            "ENABLED"
+        } else {
+            "DISABLED"
+        }
     );
+    if let Some(port) = &config.rfd_port {
+        info!("RFD port: {} (not scanned for)", port);
+    } else if !config.rfd_auto_scan {
+        info!("RFD port scanning: DISABLED");
+    }
+    if config.lora_enabled {
+        info!("LoRa modem: ENABLED");
+    }
+    if let Some(ids) = &config.can_id_allowlist {
+        info!(
+            "CAN socket filter: {} IDs (other frames are not received or logged)",
+            ids.len()
+        );
+    } else if config.can_promiscuous {
+        info!("CAN socket filter: DISABLED (receiving every ID)");
+    }
+    if let Some(ids) = &config.tx_id_allowlist {
+        info!("RFD transmit allowlist: {} IDs", ids.len());
+    }
+    if config.can_inject {
+        info!("Remote CAN inject: ENABLED (frames requested over RF are sent on can0)");
+    }
+    if !config.critical_ids.is_empty() {
+        info!(
+            "Critical priority override: {} IDs",
+            config.critical_ids.len()
+        );
+    }
+    if !config.preserve_ids.is_empty() {
+        info!(
+            "Frames not coalesced over RF: {} IDs (up to {} queued per ID)",
+            config.preserve_ids.len(),
+            config.preserve_depth
+        );
+    }
+    if config.checksum_mode == ChecksumMode::Crc16 {
+        info!("RF checksums: CRC16");
+    }
+    if config.overflow_policy != OverflowPolicy::default() {
+        info!("RF overflow policy: {:?}", config.overflow_policy);
+    }
+    if config.sim_loss > 0.0 || config.sim_corrupt > 0.0 {
+        info!(
+            "Simulated RFD link: {:.1}% batch loss, {:.1}% byte corruption (seed {})",
+            config.sim_loss * 100.0,
+            config.sim_corrupt * 100.0,
+            config.sim_seed
+        );
+    }
+    if config.range_faults_enabled {
+        info!("Signal range faults: ENABLED");
+    }
+    if config.clamp_signals {
+        info!("Signal range clamping: ENABLED");
+    }
+
+    // Ctrl-C closes the window on the next tick, so logs are flushed and the last batch is sent
+    // as on a normal close; a second Ctrl-C exits immediately in case that hangs
+    if let Err(e) = ctrlc::set_handler(|| {
+        if gui::SHUTDOWN_REQUESTED.swap(true, Ordering::Relaxed) {
+            std::process::exit(130);
+        }
+    }) {
+        warn!("Failed to install Ctrl-C handler: {}", e);
+    }
 
     let settings = Settings {
-        flags: rfd_enabled,
+        flags: config,
+        // Closing goes through Message::CloseRequested so the session summary gets written
+        window: iced::window::Settings {
+            exit_on_close_request: false,
+            ..iced::window::Settings::default()
+        },
         ..Settings::default()
     };
 
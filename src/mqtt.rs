@@ -0,0 +1,109 @@
+// Optional MQTT publisher (--mqtt-host) for strategy tooling.
+//
+// Every decoded signal is published to `telemetry/<message>/<signal>` with the scaled value as
+// a plain-text payload (QoS 0, not retained). `<message>` is the GUI message name, or the DBC
+// one for messages the GUI doesn't map. Publishing only queues into rumqttc's bounded request
+// channel; a background thread drives the connection and reconnects after errors. While the
+// broker is unreachable and the queue is full, new values are dropped rather than blocking
+// the GUI.
+use crate::can::DecodedSignal;
+use log::{info, warn};
+use rumqttc::{Client, Event, MqttOptions, Packet, QoS};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub const DEFAULT_MQTT_PORT: u16 = 1883;
+const TOPIC_PREFIX: &str = "telemetry";
+
+// Publishes queued before new values are dropped
+const REQUEST_QUEUE: usize = 1024;
+const KEEP_ALIVE: Duration = Duration::from_secs(5);
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+pub struct MqttPublisher {
+    client: Client,
+    connected: Arc<AtomicBool>,
+    dropped: AtomicU64,
+}
+
+impl MqttPublisher {
+    // Start connecting to `host`:`port` on a background thread; returns immediately
+    pub fn start(host: &str, port: u16) -> Result<Self, String> {
+        let mut options =
+            MqttOptions::new(format!("telemetry-rs-{}", std::process::id()), host, port);
+        options.set_keep_alive(KEEP_ALIVE);
+        let (client, mut connection) = Client::new(options, REQUEST_QUEUE);
+
+        let connected = Arc::new(AtomicBool::new(false));
+        let thread_connected = connected.clone();
+        let broker = format!("{}:{}", host, port);
+        std::thread::Builder::new()
+            .name("mqtt".to_string())
+            .spawn(move || {
+                for notification in connection.iter() {
+                    match notification {
+                        Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                            info!("MQTT connected to {}", broker);
+                            thread_connected.store(true, Ordering::Relaxed);
+                        }
+                        Ok(_) => {}
+                        Err(e) => {
+                            // The next iteration reconnects; don't spin while the broker is down
+                            if thread_connected.swap(false, Ordering::Relaxed) {
+                                warn!("MQTT connection to {} lost: {}", broker, e);
+                            }
+                            std::thread::sleep(RECONNECT_DELAY);
+                        }
+                    }
+                }
+            })
+            .map_err(|e| format!("Failed to spawn MQTT thread: {}", e))?;
+
+        Ok(Self {
+            client,
+            connected,
+            dropped: AtomicU64::new(0),
+        })
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    // Queue one publish per signal without blocking
+    pub fn publish(&self, message_name: &str, signals: &[DecodedSignal]) {
+        for signal in signals {
+            let result = self.client.try_publish(
+                signal_topic(message_name, &signal.name),
+                QoS::AtMostOnce,
+                false,
+                signal.value.to_string(),
+            );
+            if result.is_err() {
+                // Report the first drop and then every thousandth, not every frame
+                let dropped = self.dropped.fetch_add(1, Ordering::Relaxed);
+                if dropped.is_multiple_of(1000) {
+                    warn!("MQTT queue full, dropped {} values", dropped + 1);
+                }
+            }
+        }
+    }
+}
+
+fn signal_topic(message_name: &str, signal_name: &str) -> String {
+    format!("{}/{}/{}", TOPIC_PREFIX, message_name, signal_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signal_topic() {
+        assert_eq!(
+            signal_topic("BMS_Power", "Pack_Current"),
+            "telemetry/BMS_Power/Pack_Current"
+        );
+    }
+}
@@ -0,0 +1,137 @@
+// Session and lifetime distance, integrated from vehicle speed and persisted across restarts
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+pub const ODOMETER_STATE_FILE: &str = "odometer_state.json";
+const SAVE_INTERVAL: Duration = Duration::from_secs(10);
+// Ignore gaps longer than this (app stalled or suspended) rather than integrating a huge step
+const MAX_INTEGRATION_STEP: Duration = Duration::from_secs(1);
+
+#[derive(Serialize, Deserialize, Default)]
+struct OdometerState {
+    lifetime_miles: f64,
+}
+
+pub struct Odometer {
+    session_miles: f64,
+    lifetime_miles: f64,
+    state_path: PathBuf,
+    last_sample: Option<Instant>,
+    last_save: Instant,
+    dirty: bool,
+}
+
+impl Odometer {
+    // Load the lifetime total from `state_path`; a missing or unreadable file starts at zero
+    // (with a warning if the file exists but is corrupt)
+    pub fn load(state_path: &str) -> Self {
+        let state_path = PathBuf::from(state_path);
+        let state = match fs::read_to_string(&state_path) {
+            Ok(content) => serde_json::from_str::<OdometerState>(&content).unwrap_or_else(|e| {
+                warn!(
+                    "Odometer state {} is unreadable ({}); lifetime distance starts at zero",
+                    state_path.display(),
+                    e
+                );
+                OdometerState::default()
+            }),
+            Err(_) => OdometerState::default(),
+        };
+
+        Self {
+            session_miles: 0.0,
+            lifetime_miles: state.lifetime_miles,
+            state_path,
+            last_sample: None,
+            last_save: Instant::now(),
+            dirty: false,
+        }
+    }
+
+    // Integrate one speed sample. Pass `fresh = false` when the speed is stale so the
+    // gap is skipped instead of being integrated at the last known speed.
+    pub fn integrate(&mut self, speed_mph: f64, fresh: bool) {
+        let now = Instant::now();
+        let elapsed = self.last_sample.map(|last| now.duration_since(last));
+        self.last_sample = Some(now);
+
+        if let Some(elapsed) = elapsed {
+            if fresh && elapsed <= MAX_INTEGRATION_STEP {
+                let miles = speed_mph.abs() * elapsed.as_secs_f64() / 3600.0;
+                self.session_miles += miles;
+                self.lifetime_miles += miles;
+                self.dirty |= miles > 0.0;
+            }
+        }
+
+        if self.dirty && self.last_save.elapsed() >= SAVE_INTERVAL {
+            self.save();
+        }
+    }
+
+    pub fn reset_session(&mut self) {
+        self.session_miles = 0.0;
+    }
+
+    pub fn session_miles(&self) -> f64 {
+        self.session_miles
+    }
+
+    pub fn lifetime_miles(&self) -> f64 {
+        self.lifetime_miles
+    }
+
+    // Written to a temporary file and renamed over the state file, so a power cut mid-save
+    // leaves the previous total rather than a truncated file
+    pub fn save(&mut self) {
+        let state = OdometerState {
+            lifetime_miles: self.lifetime_miles,
+        };
+
+        match serde_json::to_string(&state)
+            .map_err(|e| e.to_string())
+            .and_then(|json| self.write_state(&json).map_err(|e| e.to_string()))
+        {
+            Ok(()) => self.dirty = false,
+            Err(e) => warn!("Failed to save odometer state: {}", e),
+        }
+        self.last_save = Instant::now();
+    }
+
+    fn write_state(&self, json: &str) -> io::Result<()> {
+        let mut temp_path = self.state_path.clone().into_os_string();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+
+        let mut file = File::create(&temp_path)?;
+        file.write_all(json.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&temp_path, &self.state_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_replaces_state_file() {
+        let path = std::env::temp_dir().join(format!("odometer_test_{}.json", std::process::id()));
+        fs::write(&path, "not json").unwrap();
+
+        // A corrupt file starts from zero, and the next save replaces it
+        let path_str = path.to_str().unwrap();
+        let mut odometer = Odometer::load(path_str);
+        assert_eq!(odometer.lifetime_miles(), 0.0);
+        odometer.lifetime_miles = 12.5;
+        odometer.save();
+
+        assert_eq!(Odometer::load(path_str).lifetime_miles(), 12.5);
+        assert!(!path.with_extension("json.tmp").exists());
+        let _ = fs::remove_file(&path);
+    }
+}
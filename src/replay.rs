@@ -0,0 +1,140 @@
+// Replay CanLogger output at the original timing, onto a (virtual) CAN interface or into the GUI
+use chrono::NaiveDateTime;
+use socketcan::{CanFrame, CanSocket, EmbeddedFrame, ExtendedId, Socket, StandardId};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines};
+use std::time::{Duration, Instant};
+
+const LOG_TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
+
+// Parse one "TIMESTAMP ARBITRATION_ID MESSAGE_DATA_HEX" line; comments and malformed lines yield None.
+// Standard IDs are logged as 0x + 3 hex digits, extended IDs as 0x + 8.
+pub fn parse_log_line(line: &str) -> Option<(NaiveDateTime, CanFrame)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let mut parts = line.split_whitespace();
+    let date = parts.next()?;
+    let time = parts.next()?;
+    let timestamp =
+        NaiveDateTime::parse_from_str(&format!("{} {}", date, time), LOG_TIMESTAMP_FORMAT).ok()?;
+
+    let id_hex = parts.next()?.strip_prefix("0x")?;
+    let raw_id = u32::from_str_radix(id_hex, 16).ok()?;
+
+    let data = parts
+        .map(|byte| u8::from_str_radix(byte, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+
+    let frame = if id_hex.len() > 3 {
+        CanFrame::new(ExtendedId::new(raw_id)?, &data)?
+    } else {
+        CanFrame::new(StandardId::new(raw_id as u16)?, &data)?
+    };
+
+    Some((timestamp, frame))
+}
+
+// Reads frames from a custom-format log along with when each should be replayed
+pub struct LogReplay {
+    path: String,
+    lines: Lines<BufReader<File>>,
+    speed: f64,
+    first_timestamp: Option<NaiveDateTime>,
+}
+
+impl LogReplay {
+    // `speed` scales playback: 2.0 replays twice as fast as recorded
+    pub fn open(path: &str, speed: f64) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+        Ok(Self {
+            path: path.to_string(),
+            lines: BufReader::new(file).lines(),
+            speed,
+            first_timestamp: None,
+        })
+    }
+
+    // Next frame and its offset from the first frame (scaled by the speed), or None at the end.
+    // Callers schedule against a fixed start so timing errors don't accumulate.
+    pub fn next_frame(&mut self) -> Result<Option<(Duration, CanFrame)>, String> {
+        for line in self.lines.by_ref() {
+            let line = line.map_err(|e| format!("Failed to read {}: {}", self.path, e))?;
+            let Some((timestamp, frame)) = parse_log_line(&line) else {
+                continue;
+            };
+
+            let first = *self.first_timestamp.get_or_insert(timestamp);
+            let offset = (timestamp - first).to_std().unwrap_or(Duration::ZERO);
+            return Ok(Some((offset.div_f64(self.speed), frame)));
+        }
+        Ok(None)
+    }
+}
+
+// Write every frame in `log_path` to `iface`, sleeping to reproduce the logged inter-frame gaps.
+// Returns the number of frames written.
+pub fn replay_to_can(log_path: &str, iface: &str) -> Result<usize, String> {
+    let mut replay = LogReplay::open(log_path, 1.0)?;
+    let socket = CanSocket::open(iface)
+        .map_err(|e| format!("Failed to open CAN socket {}: {}", iface, e))?;
+
+    let started = Instant::now();
+    let mut frames_written = 0;
+
+    while let Some((offset, frame)) = replay.next_frame()? {
+        if let Some(wait) = offset.checked_sub(started.elapsed()) {
+            std::thread::sleep(wait);
+        }
+
+        socket
+            .write_frame(&frame)
+            .map_err(|e| format!("Failed to write frame to {}: {}", iface, e))?;
+        frames_written += 1;
+    }
+
+    Ok(frames_written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_line() {
+        let (_, standard) = parse_log_line("2025-01-02 03:04:05.678 0x300 01 02 03 04").unwrap();
+        assert!(!standard.is_extended());
+        assert_eq!(standard.data(), &[1, 2, 3, 4]);
+
+        let (_, extended) = parse_log_line("2025-01-02 03:04:05.700 0x0CF11E05 AA BB").unwrap();
+        assert!(extended.is_extended());
+        assert_eq!(extended.data(), &[0xAA, 0xBB]);
+
+        assert!(parse_log_line("# CAN Log Started: 2025-01-02").is_none());
+        assert!(parse_log_line("2025-01-02 03:04:05.678 0x300 ZZ").is_none());
+    }
+
+    #[test]
+    fn test_replay_offsets_scale_with_speed() {
+        let path = std::env::temp_dir().join(format!("replay_test_{}.txt", std::process::id()));
+        std::fs::write(
+            &path,
+            "# CAN Log Started\n\
+             2025-01-02 03:04:05.000 0x300 01\n\
+             2025-01-02 03:04:06.000 0x301 02\n",
+        )
+        .unwrap();
+
+        let mut replay = LogReplay::open(path.to_str().unwrap(), 2.0).unwrap();
+        let (first, _) = replay.next_frame().unwrap().unwrap();
+        let (second, frame) = replay.next_frame().unwrap().unwrap();
+        assert_eq!(first, Duration::ZERO);
+        assert_eq!(second, Duration::from_millis(500));
+        assert_eq!(frame.data(), &[0x02]);
+        assert!(replay.next_frame().unwrap().is_none());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,41 +1,137 @@
 // Simple RFD transmission without complex framing
 use serialport::{SerialPort, SerialPortType};
-use std::collections::{HashMap, VecDeque};
-use std::io::Write;
-use std::sync::{Arc, Mutex};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::{self, Read, Write};
+use std::sync::{mpsc, Arc, Mutex, MutexGuard};
 use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 use crc32fast::Hasher;
+use log::{debug, info, trace, warn};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
-// Simple batch configuration for reliable transmission
-const MAX_BATCH_SIZE: usize = 8; // Smaller batches = better reliability  
-const MAX_BATCH_BYTES: usize = 100; // Conservative byte limit
-const BATCH_TIMEOUT_MS: u64 = 20; // Longer timeout for stability
+use crate::health::{now_ms, Heartbeat};
+
+// Simple batch configuration for reliable transmission (defaults for BatchConfig)
+pub const MAX_BATCH_SIZE: usize = 8; // Smaller batches = better reliability
+pub const MAX_BATCH_BYTES: usize = 100; // Conservative byte limit
+pub const BATCH_TIMEOUT_MS: u64 = 20; // Longer timeout for stability
 const MIN_BATCH_SIZE: usize = 1; // Always send at least 1 frame
 
+// Ceiling the routine timeout stretches to while the modem's writes keep failing
+pub const MAX_BATCH_TIMEOUT_MS: u64 = 200;
+
+// Queued frames kept per preserve-all ID by default (--preserve-depth); past the limit the
+// oldest is dropped after all
+pub const DEFAULT_PRESERVED_PER_ID: usize = 4;
+
+// Express lane for Critical/High frames - small batches sent almost immediately
+const EXPRESS_MAX_BATCH_SIZE: usize = 4;
+const EXPRESS_MAX_BATCH_BYTES: usize = 100;
+const EXPRESS_BATCH_TIMEOUT_MS: u64 = 2;
+
 // Simple frame markers for synchronization
 const FRAME_START: &[u8] = b"\xAA\xBB\xCC\xDD";
 const FRAME_END: &[u8] = b"\xDD\xCC\xBB\xAA";
 
 // Radio configuration constants
-const RFD_BAUD_RATE: u32 = 57600; // RFD modem standard baud rate
+pub const DEFAULT_RFD_BAUD_RATE: u32 = 57600; // RFD modem standard baud rate
 const CONNECTION_CHECK_INTERVAL_MS: u64 = 10000; // Slower connection checks
 const TRANSMISSION_TIMEOUT_MS: u64 = 200; // Much longer timeout for reliability
 const CONNECTION_GRACE_PERIOD_MS: u64 = 30000;
 const RFD_SCAN_INTERVAL_MS: u64 = 5000;
+const LORA_BAUD_RATE: u32 = 9600; // LoRa UART modules' factory default
+
+// USB (VID, PID) of the serial adapters our modems use; the scanner skips other devices
+// unless told to try every port (set_scan_all_ports)
+pub const KNOWN_MODEM_USB_IDS: &[(u16, u16)] = &[
+    (0x0403, 0x6001), // FTDI FT232R (RFD900x)
+    (0x0403, 0x6015), // FTDI FT231X (RFD900x2)
+    (0x10C4, 0xEA60), // SiLabs CP210x
+    (0x1A86, 0x7523), // WCH CH340 (LoRa UART boards)
+];
+
+// Prefix of ModemStatus::error_message while available_ports() itself is failing
+const PORT_ENUMERATION_ERROR: &str =
+    "cannot enumerate serial ports (permissions? check dialout group membership)";
+
+// Errors from the modem links and batching, for callers to match on
+#[derive(Debug, thiserror::Error)]
+pub enum SerialError {
+    #[error("modem port busy")]
+    PortBusy, // Another thread holds the port; the frame was not sent
+    #[error("{0} port not open")]
+    NotConnected(&'static str),
+    #[error("no modems available for transmission")]
+    NoModems,
+    #[error("failed to write to {modem} port: {source}")]
+    WriteFailed {
+        modem: &'static str,
+        #[source]
+        source: io::Error,
+    },
+    #[error("{0} batch overflow")]
+    BatchOverflow(&'static str),
+    #[error("port {0} is in use by the LoRa modem")]
+    PortInUse(String),
+    #[error("failed to open port {port}: {source}")]
+    OpenFailed {
+        port: String,
+        #[source]
+        source: serialport::Error,
+    },
+    #[error("failed to verify {modem} modem on port {port}: {source}")]
+    VerifyFailed {
+        modem: &'static str,
+        port: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("{}: {}", PORT_ENUMERATION_ERROR, .0)]
+    PortEnumeration(#[source] serialport::Error),
+    #[error("{0} already running")]
+    AlreadyRunning(&'static str),
+    #[error("invalid batch settings: {0}")]
+    InvalidBatchConfig(String),
+}
+
+// Round-trip probes: a reserved extended ID carrying the send time in microseconds (u64 BE).
+// The ground station echoes the frame back unchanged in a normal batch.
+pub const RTT_PROBE_ID: u32 = 0x1FFF_FFF0;
+const RTT_PROBE_INTERVAL_MS: u64 = 5000;
+
+// Remote CAN injection: a base station asks the car to put a frame on its bus by sending this
+// reserved extended ID. Payload: target wire ID (u32 BE, bit 31 = extended), then the data.
+pub const CAN_INJECT_ID: u32 = 0x1FFF_FFF1;
+const TX_RATE_WINDOW: Duration = Duration::from_secs(10); // get_tx_stats rates cover this long
+const RX_BUFFER_LIMIT: usize = 4096; // Received bytes held while waiting for a batch to complete
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ModemType {
     Rfd900x,
+    Lora, // Backup link, runs alongside the RFD when enabled
+}
+
+impl ModemType {
+    fn label(&self) -> &'static str {
+        match self {
+            ModemType::Rfd900x => "RFD",
+            ModemType::Lora => "LoRa",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct CanFrameData {
     pub id: u32,
+    pub is_extended: bool, // 29-bit ID on the bus; IDs <= 0x7FF can be either format
     pub data: Vec<u8>,
     pub timestamp: Instant,
     pub sequence_number: u64,
     pub priority: MessagePriority,
+    // Capture time (Unix ms) carried by a received batch; None for local frames and for
+    // batches sent without timestamps (use `timestamp` for those)
+    pub captured_at_ms: Option<u64>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -46,19 +142,117 @@ pub enum MessagePriority {
     Low = 3,       // Monitoring messages (limits, capacity)
 }
 
+// Set in the serialized ID for extended frames (same bit as the kernel's CAN_EFF_FLAG)
+const EXTENDED_ID_FLAG: u32 = 0x8000_0000;
+
+// Serialized size of a frame's fields other than data and CRC (ID, length, sequence number)
+const FRAME_FIELDS_LEN: usize = 13;
+
+// Largest payload a frame can carry (CAN FD; classic CAN frames carry up to 8 bytes)
+pub const MAX_FRAME_DATA_LEN: usize = 64;
+
+// Every lane's default byte limit must fit at least one full CAN FD frame
+const _: () = assert!(MAX_BATCH_BYTES >= FRAME_FIELDS_LEN + 4 + MAX_FRAME_DATA_LEN);
+const _: () = assert!(EXPRESS_MAX_BATCH_BYTES >= FRAME_FIELDS_LEN + 4 + MAX_FRAME_DATA_LEN);
+
+// Set in a batch's frame count when its frame and batch checksums are CRC16 rather than CRC32
+const CRC16_BATCH_FLAG: u16 = 0x8000;
+
+// Set in a batch's frame count when the header carries capture timestamps (always, when
+// sending; batches from older senders don't have them)
+const TIMESTAMPED_BATCH_FLAG: u16 = 0x4000;
+
+// Frames one batch can declare (the count's top two bits are flags)
+pub const MAX_FRAMES_PER_BATCH: usize = (TIMESTAMPED_BATCH_FLAG - 1) as usize;
+
+// Timestamp header: batch creation time (Unix ms, u64 BE), then per frame its age at that
+// moment (ms, u16 BE, saturating)
+const BATCH_TIME_LEN: usize = 8;
+const FRAME_AGE_LEN: usize = 2;
+
+const CRC16: crc::Crc<u16> = crc::Crc::<u16>::new(&crc::CRC_16_IBM_3740);
+
+// Width of the per-frame and per-batch checksums. CRC16 saves 2 bytes per frame (and per
+// batch) on slow links; receivers detect the mode from the batch header either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChecksumMode {
+    #[default]
+    Crc32,
+    Crc16,
+}
+
+impl ChecksumMode {
+    pub fn crc_len(self) -> usize {
+        match self {
+            ChecksumMode::Crc32 => 4,
+            ChecksumMode::Crc16 => 2,
+        }
+    }
+
+    // Serialized size of a frame with no data bytes
+    fn min_frame_len(self) -> usize {
+        FRAME_FIELDS_LEN + self.crc_len()
+    }
+
+    fn checksum(self, bytes: &[u8]) -> u32 {
+        match self {
+            ChecksumMode::Crc32 => {
+                let mut hasher = Hasher::new();
+                hasher.update(bytes);
+                hasher.finalize()
+            }
+            ChecksumMode::Crc16 => CRC16.checksum(bytes) as u32,
+        }
+    }
+
+    // Append the big-endian checksum of `bytes[covered]`
+    fn append_checksum(self, bytes: &mut Vec<u8>, covered: std::ops::Range<usize>) {
+        let crc = self.checksum(&bytes[covered]);
+        match self {
+            ChecksumMode::Crc32 => bytes.extend_from_slice(&crc.to_be_bytes()),
+            ChecksumMode::Crc16 => bytes.extend_from_slice(&(crc as u16).to_be_bytes()),
+        }
+    }
+
+    // Big-endian checksum stored at the start of `bytes` (which must hold crc_len() bytes)
+    fn read_checksum(self, bytes: &[u8]) -> u32 {
+        bytes[..self.crc_len()]
+            .iter()
+            .fold(0, |crc, byte| (crc << 8) | *byte as u32)
+    }
+
+    // Mode a batch was framed with, from the flag in its frame count (None if too short)
+    fn of_batch(batch: &[u8]) -> Option<Self> {
+        let header = batch.get(FRAME_START.len()..FRAME_START.len() + 2)?;
+        if u16::from_be_bytes([header[0], header[1]]) & CRC16_BATCH_FLAG != 0 {
+            Some(ChecksumMode::Crc16)
+        } else {
+            Some(ChecksumMode::Crc32)
+        }
+    }
+}
+
 static SEQUENCE_COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
 
 // Simple frame utilities - no escaping needed for basic transmission
 
 impl CanFrameData {
+    // Infers the frame format from the ID (test convenience; live frames know their format)
+    #[cfg(test)]
     pub fn new(id: u32, data: &[u8]) -> Self {
+        Self::new_with_format(id, data, id > 0x7FF)
+    }
+
+    pub fn new_with_format(id: u32, data: &[u8], is_extended: bool) -> Self {
         let sequence_number = SEQUENCE_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
         Self {
             id,
+            is_extended,
             data: data.to_vec(),
             timestamp: Instant::now(),
             sequence_number,
             priority: Self::get_priority_for_id(id),
+            captured_at_ms: None,
         }
     }
 
@@ -87,36 +281,45 @@ impl CanFrameData {
         }
     }
 
-    // Enhanced serialization with CRC32 validation and sequence number
-    pub fn to_bytes(&self) -> Vec<u8> {
-        // Validate data length (CAN max is 8 bytes)
-        let data_len = std::cmp::min(self.data.len(), 8);
+    // Exact size `to_bytes` produces; batch limits are accounted in these bytes
+    pub fn encoded_len(&self, mode: ChecksumMode) -> usize {
+        mode.min_frame_len() + std::cmp::min(self.data.len(), MAX_FRAME_DATA_LEN)
+    }
+
+    // Enhanced serialization with CRC validation and sequence number
+    pub fn to_bytes(&self, mode: ChecksumMode) -> Vec<u8> {
+        // Validate data length (CAN FD max is 64 bytes)
+        let data_len = std::cmp::min(self.data.len(), MAX_FRAME_DATA_LEN);
 
-        let mut bytes = Vec::with_capacity(17 + data_len);
-        bytes.extend_from_slice(&self.id.to_be_bytes()); // 4 bytes ID (big-endian)
+        let mut bytes = Vec::with_capacity(self.encoded_len(mode));
+        let wire_id = if self.is_extended {
+            self.id | EXTENDED_ID_FLAG
+        } else {
+            self.id
+        };
+        bytes.extend_from_slice(&wire_id.to_be_bytes()); // 4 bytes ID (big-endian, bit 31 = extended)
         bytes.push(data_len as u8); // 1 byte length
         bytes.extend_from_slice(&self.data[..data_len]); // data (validated length)
         bytes.extend_from_slice(&self.sequence_number.to_be_bytes()); // 8 bytes sequence number
         
-        // Calculate CRC32 of the payload
-        let mut hasher = Hasher::new();
-        hasher.update(&bytes);
-        let crc = hasher.finalize();
-        bytes.extend_from_slice(&crc.to_be_bytes()); // 4 bytes CRC32
+        // CRC of the payload (4 bytes CRC32 or 2 bytes CRC16)
+        mode.append_checksum(&mut bytes, 0..FRAME_FIELDS_LEN + data_len);
         
         bytes
     }
 
-    // Deserialize from bytes with CRC32 validation and sequence number
-    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
-        if bytes.len() < 17 {
+    // Deserialize from bytes with CRC validation and sequence number
+    pub fn from_bytes(bytes: &[u8], mode: ChecksumMode) -> Option<Self> {
+        if bytes.len() < mode.min_frame_len() {
             return None;
         }
 
-        let id = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let wire_id = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let is_extended = wire_id & EXTENDED_ID_FLAG != 0;
+        let id = wire_id & !EXTENDED_ID_FLAG;
         let data_len = bytes[4] as usize;
 
-        if bytes.len() < 17 + data_len || data_len > 8 {
+        if bytes.len() < mode.min_frame_len() + data_len || data_len > MAX_FRAME_DATA_LEN {
             return None;
         }
 
@@ -132,30 +335,50 @@ impl CanFrameData {
             bytes[12 + data_len],
         ]);
         
-        // Verify CRC32
-        let expected_crc = u32::from_be_bytes([
-            bytes[13 + data_len],
-            bytes[14 + data_len],
-            bytes[15 + data_len],
-            bytes[16 + data_len],
-        ]);
-        
-        let mut hasher = Hasher::new();
-        hasher.update(&bytes[..13 + data_len]);
-        let actual_crc = hasher.finalize();
+        // Verify CRC
+        let expected_crc = mode.read_checksum(&bytes[13 + data_len..]);
+        let actual_crc = mode.checksum(&bytes[..13 + data_len]);
         
         if actual_crc != expected_crc {
-            println!("CRC32 validation failed for CAN ID 0x{:X}: expected 0x{:X}, got 0x{:X}", 
-                     id, expected_crc, actual_crc);
+            warn!(
+                "{:?} validation failed for CAN ID 0x{:X}: expected 0x{:X}, got 0x{:X}",
+                mode, id, expected_crc, actual_crc
+            );
             return None;
         }
 
         Some(Self {
             id,
+            is_extended,
             data,
             timestamp: Instant::now(),
             sequence_number,
             priority: Self::get_priority_for_id(id),
+            captured_at_ms: None,
+        })
+    }
+}
+
+// A frame the base station wants transmitted on the car's CAN bus
+#[derive(Debug, Clone, PartialEq)]
+pub struct InjectRequest {
+    pub id: u32,
+    pub is_extended: bool,
+    pub data: Vec<u8>,
+}
+
+impl InjectRequest {
+    // Parse a received RF frame; None unless it is a well-formed inject request
+    pub fn from_frame(frame: &CanFrameData) -> Option<Self> {
+        if frame.id != CAN_INJECT_ID || !frame.is_extended || frame.data.len() < 4 {
+            return None;
+        }
+        let (wire_id, data) = frame.data.split_at(4);
+        let wire_id = u32::from_be_bytes([wire_id[0], wire_id[1], wire_id[2], wire_id[3]]);
+        Some(Self {
+            id: wire_id & !EXTENDED_ID_FLAG,
+            is_extended: wire_id & EXTENDED_ID_FLAG != 0,
+            data: data.to_vec(),
         })
     }
 }
@@ -164,6 +387,7 @@ impl CanFrameData {
 pub struct FrameFilter {
     last_transmission: HashMap<u32, Instant>,
     min_intervals: HashMap<u32, Duration>,
+    filtered_count: HashMap<u32, u64>, // Frames dropped per ID for arriving too soon
 }
 
 impl FrameFilter {
@@ -171,6 +395,7 @@ impl FrameFilter {
         let mut filter = Self {
             last_transmission: HashMap::new(),
             min_intervals: HashMap::new(),
+            filtered_count: HashMap::new(),
         };
 
         // Conservative transmission intervals for reliability (reverted)
@@ -240,6 +465,7 @@ impl FrameFilter {
         if let Some(min_interval) = self.min_intervals.get(&can_id) {
             if let Some(last_time) = self.last_transmission.get(&can_id) {
                 if now.duration_since(*last_time) < *min_interval {
+                    *self.filtered_count.entry(can_id).or_insert(0) += 1;
                     return false; // Too soon, filter out
                 }
             }
@@ -249,6 +475,7 @@ impl FrameFilter {
             
             if let Some(last_time) = self.last_transmission.get(&can_id) {
                 if now.duration_since(*last_time) < default_interval {
+                    *self.filtered_count.entry(can_id).or_insert(0) += 1;
                     return false;
                 }
             }
@@ -258,6 +485,174 @@ impl FrameFilter {
         self.last_transmission.insert(can_id, now);
         true
     }
+
+    pub fn filtered_count(&self) -> &HashMap<u32, u64> {
+        &self.filtered_count
+    }
+
+    pub fn frames_filtered(&self) -> u64 {
+        self.filtered_count.values().sum()
+    }
+}
+
+// Limits of one batcher lane, tunable at runtime for different link conditions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatchConfig {
+    pub max_batch_size: usize,  // Frames per batch
+    pub max_batch_bytes: usize, // Encoded frame bytes per batch (framing excluded)
+    pub batch_timeout_ms: u64,  // Send a partial batch after this long
+    // Upper bound for the timeout while the link fails writes; batch_timeout_ms disables that
+    pub max_batch_timeout_ms: u64,
+}
+
+impl BatchConfig {
+    pub fn new(
+        max_batch_size: usize,
+        max_batch_bytes: usize,
+        batch_timeout_ms: u64,
+        max_batch_timeout_ms: u64,
+    ) -> Result<Self, SerialError> {
+        let config = Self {
+            max_batch_size,
+            max_batch_bytes,
+            batch_timeout_ms,
+            max_batch_timeout_ms,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    // Batcher for the Critical/High express lane
+    pub fn express() -> Self {
+        Self {
+            max_batch_size: EXPRESS_MAX_BATCH_SIZE,
+            max_batch_bytes: EXPRESS_MAX_BATCH_BYTES,
+            batch_timeout_ms: EXPRESS_BATCH_TIMEOUT_MS,
+            max_batch_timeout_ms: EXPRESS_BATCH_TIMEOUT_MS,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), SerialError> {
+        let invalid = |reason: String| Err(SerialError::InvalidBatchConfig(reason));
+        if self.max_batch_size == 0 {
+            return invalid("batch size must be at least 1 frame".to_string());
+        }
+        // The frame count goes on the wire in 14 bits (the top two are flags)
+        if self.max_batch_size > MAX_FRAMES_PER_BATCH {
+            return invalid(format!(
+                "batch size {} exceeds {} frames",
+                self.max_batch_size, MAX_FRAMES_PER_BATCH
+            ));
+        }
+        if self.max_batch_bytes == 0 {
+            return invalid("batch byte limit must be non-zero".to_string());
+        }
+        if self.max_batch_timeout_ms < self.batch_timeout_ms {
+            return invalid(format!(
+                "maximum batch timeout {} ms is below the {} ms timeout",
+                self.max_batch_timeout_ms, self.batch_timeout_ms
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: MAX_BATCH_SIZE,
+            max_batch_bytes: MAX_BATCH_BYTES,
+            batch_timeout_ms: BATCH_TIMEOUT_MS,
+            max_batch_timeout_ms: MAX_BATCH_TIMEOUT_MS,
+        }
+    }
+}
+
+// What a lane sheds when its batch is still full after trying to send it (the radio or port
+// can't keep up). DropOldest and DropLowestPriority evict a queued frame to make room;
+// DropLowestPriority drops the new frame instead when everything queued outranks it. Block
+// waits for the port rather than skipping a busy one, stalling the caller to keep every frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    #[default]
+    DropOldest,
+    DropLowestPriority,
+    Block,
+}
+
+impl OverflowPolicy {
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.trim() {
+            "drop-oldest" => Ok(Self::DropOldest),
+            "drop-lowest-priority" => Ok(Self::DropLowestPriority),
+            "block" => Ok(Self::Block),
+            other => Err(format!(
+                "unknown overflow policy '{}' (expected drop-oldest, drop-lowest-priority or block)",
+                other
+            )),
+        }
+    }
+}
+
+// Frame a set of CAN frames for the radio link:
+// FRAME_START | count (u16 BE) | created (u64 BE) | ages (u16 BE each) | frames... |
+// FRAME_END | CRC of count..frames (BE)
+// The count's top bit is set when the frame and batch CRCs are CRC16 instead of CRC32; the
+// next one marks the timestamp header, which lets the receiver recover capture times.
+fn encode_batch(frames: &[CanFrameData], mode: ChecksumMode) -> Vec<u8> {
+    let created = Instant::now();
+    let created_ms = now_ms();
+    let count = frames.len().min(MAX_FRAMES_PER_BATCH) as u16;
+    let header = match mode {
+        ChecksumMode::Crc32 => count | TIMESTAMPED_BATCH_FLAG,
+        ChecksumMode::Crc16 => count | TIMESTAMPED_BATCH_FLAG | CRC16_BATCH_FLAG,
+    };
+
+    let mut batch = Vec::new();
+    batch.extend_from_slice(FRAME_START);
+    batch.extend_from_slice(&header.to_be_bytes());
+    batch.extend_from_slice(&created_ms.to_be_bytes());
+    for frame in &frames[..count as usize] {
+        let age_ms = created
+            .saturating_duration_since(frame.timestamp)
+            .as_millis();
+        batch.extend_from_slice(&(age_ms.min(u16::MAX as u128) as u16).to_be_bytes());
+    }
+
+    for frame in &frames[..count as usize] {
+        batch.extend_from_slice(&frame.to_bytes(mode));
+    }
+
+    batch.extend_from_slice(FRAME_END);
+
+    // CRC for the entire batch (excluding markers)
+    let payload_start = FRAME_START.len();
+    let payload_end = batch.len() - FRAME_END.len();
+    mode.append_checksum(&mut batch, payload_start..payload_end);
+
+    batch
+}
+
+// Lock `mutex` even if a thread panicked while holding it, clearing the poison so one panic
+// doesn't fail every later lock (the data is used as the panicking thread left it)
+fn lock_recovering<'a, T>(mutex: &'a Mutex<T>, what: &str) -> MutexGuard<'a, T> {
+    mutex.lock().unwrap_or_else(|poisoned| {
+        warn!("{} mutex poisoned, recovering", what);
+        mutex.clear_poison();
+        poisoned.into_inner()
+    })
+}
+
+// Lock a batcher lane. A panic mid-update may have left its queue half-written, so a
+// poisoned lane drops its queued frames and carries on with the next ones.
+fn lock_lane(lane: &Mutex<ImprovedFrameBatcher>) -> MutexGuard<'_, ImprovedFrameBatcher> {
+    lane.lock().unwrap_or_else(|poisoned| {
+        warn!("Batcher mutex poisoned, discarding queued frames");
+        lane.clear_poison();
+        let mut batcher = poisoned.into_inner();
+        batcher.clear_pending();
+        batcher
+    })
 }
 
 pub struct ImprovedFrameBatcher {
@@ -265,26 +660,63 @@ pub struct ImprovedFrameBatcher {
     latest_frames: HashMap<u32, CanFrameData>,
     // Maintain insertion order for same-priority messages
     frame_order: VecDeque<u32>,
+    // IDs whose queued frames are all sent rather than coalesced to the latest
+    // (--preserve-ids), and their frames, oldest first, up to max_preserved_per_id per ID
+    preserve_ids: HashSet<u32>,
+    preserved_frames: Vec<CanFrameData>,
+    max_preserved_per_id: usize,
+    preserved_dropped: u64, // Since startup, pushed out by newer frames of the same ID
     last_send: Instant,
     total_bytes: usize,
     frame_filter: FrameFilter,
     batch_count: u64,
+    // Batch limits (routine lane vs express lane)
+    config: BatchConfig,
     // Statistics
     total_frames_added: u64,
     frames_replaced: u64,
+    frames_transmitted: u64, // Frames actually packed into batches (survived filtering and replacement)
+    // Next per-ID sequence number stamped on transmitted frames, so the receiver's
+    // SequenceTracker sees a gap only when the link loses a frame
+    tx_sequence: HashMap<u32, u64>,
+    bytes_sent: u64, // Encoded batch bytes, framing included
+    // Frames shed because the batch stayed full after a send attempt, and how they're chosen
+    frames_overflowed: u64,
+    overflow_policy: OverflowPolicy,
+    // Checksums this lane's batches are framed with (set_checksum_mode)
+    checksum_mode: ChecksumMode,
+    // Partial-batch timeout in use, adapted within the config's bounds, and the modem
+    // counters it last adapted to
+    timeout_ms: u64,
+    seen_write_failures: u64,
+    seen_success: Option<Instant>,
 }
 
 impl ImprovedFrameBatcher {
-    pub fn new() -> Self {
+    pub fn new(config: BatchConfig) -> Self {
         Self {
             latest_frames: HashMap::new(),
             frame_order: VecDeque::new(),
+            preserve_ids: HashSet::new(),
+            preserved_frames: Vec::new(),
+            max_preserved_per_id: DEFAULT_PRESERVED_PER_ID,
+            preserved_dropped: 0,
             last_send: Instant::now(),
             total_bytes: 0,
             frame_filter: FrameFilter::new(),
             batch_count: 0,
+            config,
             total_frames_added: 0,
             frames_replaced: 0,
+            frames_transmitted: 0,
+            tx_sequence: HashMap::new(),
+            bytes_sent: 0,
+            frames_overflowed: 0,
+            overflow_policy: OverflowPolicy::default(),
+            checksum_mode: ChecksumMode::default(),
+            timeout_ms: config.batch_timeout_ms,
+            seen_write_failures: 0,
+            seen_success: None,
         }
     }
 
@@ -294,11 +726,19 @@ impl ImprovedFrameBatcher {
             return true; // Frame filtered out, but don't report as error
         }
 
-        let frame_size = 13 + std::cmp::min(frame.data.len(), 8); // Updated size with sequence number
-        let can_id = frame.id;
-        
         self.total_frames_added += 1;
-        
+        self.enqueue(frame)
+    }
+
+    // Queue a frame that already passed the rate filter; false when the batch is full
+    fn enqueue(&mut self, frame: CanFrameData) -> bool {
+        let frame_size = frame.encoded_len(self.checksum_mode);
+        let can_id = frame.id;
+
+        if self.preserve_ids.contains(&can_id) {
+            return self.add_preserved_frame(frame, frame_size);
+        }
+
         // Check if we already have this message ID
         if let Some(existing_frame) = self.latest_frames.get(&can_id) {
             let old_seq = existing_frame.sequence_number;
@@ -308,14 +748,17 @@ impl ImprovedFrameBatcher {
                (new_seq == old_seq && frame.timestamp > existing_frame.timestamp) {
                 self.latest_frames.insert(can_id, frame);
                 self.frames_replaced += 1;
-                println!("Replaced message 0x{:X} with newer version (seq: {} -> {})", 
-                         can_id, old_seq, new_seq);
+                trace!(
+                    "Replaced message 0x{:X} with newer version (seq: {} -> {})",
+                    can_id,
+                    old_seq,
+                    new_seq
+                );
             }
             return true; // Always succeed when replacing
         }
         
-        // Check batch limits for new messages
-        if self.latest_frames.len() >= MAX_BATCH_SIZE || self.get_total_bytes() + frame_size > MAX_BATCH_BYTES {
+        if self.is_full(frame_size) {
             return false; // Batch is full
         }
 
@@ -325,85 +768,139 @@ impl ImprovedFrameBatcher {
         true
     }
 
+    // Queue alongside earlier frames of the same ID instead of replacing them
+    fn add_preserved_frame(&mut self, frame: CanFrameData, frame_size: usize) -> bool {
+        let queued = self
+            .preserved_frames
+            .iter()
+            .filter(|queued| queued.id == frame.id)
+            .count();
+        if queued >= self.max_preserved_per_id {
+            let oldest = self
+                .preserved_frames
+                .iter()
+                .position(|queued| queued.id == frame.id)
+                .unwrap();
+            self.preserved_frames.remove(oldest);
+            self.frames_replaced += 1;
+            self.preserved_dropped += 1;
+        } else if self.is_full(frame_size) {
+            return false;
+        }
+        self.preserved_frames.push(frame);
+        true
+    }
+
+    // Make room for `incoming` in a batch that stayed full after a send attempt, by the
+    // lane's overflow policy. Returns false when `incoming` is the frame to shed instead.
+    fn shed_for(&mut self, incoming: &CanFrameData) -> bool {
+        self.frames_overflowed += 1;
+        let queued = self.latest_frames.values().chain(&self.preserved_frames);
+        let victim = match self.overflow_policy {
+            OverflowPolicy::DropOldest => queued.min_by_key(|frame| frame.timestamp),
+            OverflowPolicy::DropLowestPriority => queued
+                .filter(|frame| frame.priority >= incoming.priority)
+                .max_by_key(|frame| (frame.priority, std::cmp::Reverse(frame.timestamp))),
+            OverflowPolicy::Block => None,
+        };
+        let Some((id, sequence_number)) = victim.map(|frame| (frame.id, frame.sequence_number))
+        else {
+            return false;
+        };
+
+        if let Some(index) = self
+            .preserved_frames
+            .iter()
+            .position(|frame| frame.id == id && frame.sequence_number == sequence_number)
+        {
+            self.preserved_frames.remove(index);
+        } else {
+            self.latest_frames.remove(&id);
+            self.frame_order.retain(|queued_id| *queued_id != id);
+        }
+        true
+    }
+
+    // Check batch limits for a new frame. An empty batch takes any frame, so a CAN FD
+    // frame larger than a tight --batch-bytes still goes out (alone).
+    fn is_full(&self, frame_size: usize) -> bool {
+        !self.is_empty()
+            && (self.get_queue_size() >= self.config.max_batch_size
+                || self.get_total_bytes() + frame_size > self.config.max_batch_bytes)
+    }
+
     pub fn should_send(&self) -> bool {
-        if self.latest_frames.is_empty() {
+        if self.is_empty() {
             return false;
         }
 
         // Send conditions
-        self.latest_frames.len() >= MAX_BATCH_SIZE
-            || self.get_total_bytes() >= MAX_BATCH_BYTES
-            || (self.latest_frames.len() >= MIN_BATCH_SIZE
-                && self.last_send.elapsed().as_millis() >= BATCH_TIMEOUT_MS as u128)
+        self.get_queue_size() >= self.config.max_batch_size
+            || self.get_total_bytes() >= self.config.max_batch_bytes
+            || (self.get_queue_size() >= MIN_BATCH_SIZE
+                && self.last_send.elapsed().as_millis() >= self.timeout_ms as u128)
     }
     
     fn get_total_bytes(&self) -> usize {
         self.latest_frames.values()
-            .map(|frame| 17 + std::cmp::min(frame.data.len(), 8))  // Updated for CRC32
+            .chain(&self.preserved_frames)
+            .map(|frame| frame.encoded_len(self.checksum_mode))
             .sum()
     }
 
+    // Drop queued frames, keeping config, filter state and counters
+    fn clear_pending(&mut self) {
+        self.latest_frames.clear();
+        self.frame_order.clear();
+        self.preserved_frames.clear();
+        self.total_bytes = 0;
+    }
+
     pub fn create_batch(&mut self) -> Vec<u8> {
-        if self.latest_frames.is_empty() {
+        if self.is_empty() {
             return Vec::new();
         }
 
-        let mut batch = Vec::new();
-
-        // Add start marker
-        batch.extend_from_slice(FRAME_START);
-
-        // Collect frames and sort by priority (critical first, then by timestamp)
-        let mut frames_to_send: Vec<_> = self.latest_frames.values().cloned().collect();
-        frames_to_send.sort_by(|a, b| {
-            // First sort by priority (critical messages first)
-            match a.priority.cmp(&b.priority) {
-                std::cmp::Ordering::Equal => {
-                    // Within same priority, prefer more recent messages
-                    b.timestamp.cmp(&a.timestamp)
-                }
-                other => other,
-            }
+        // Collect frames and sort by priority (critical first), then IDs updated most recently
+        // first. Several frames of one preserved ID stay together, oldest first, so the
+        // receiver ends up showing the newest.
+        let mut frames_to_send: Vec<_> = self
+            .latest_frames
+            .values()
+            .chain(&self.preserved_frames)
+            .cloned()
+            .collect();
+        let mut newest: HashMap<u32, Instant> = HashMap::new();
+        for frame in &frames_to_send {
+            let time = newest.entry(frame.id).or_insert(frame.timestamp);
+            *time = (*time).max(frame.timestamp);
+        }
+        frames_to_send.sort_by_key(|frame| {
+            (
+                frame.priority,
+                std::cmp::Reverse(newest[&frame.id]),
+                frame.id,
+                frame.timestamp,
+            )
         });
 
         // Limit to batch size
-        let frame_count = std::cmp::min(frames_to_send.len(), MAX_BATCH_SIZE);
-        batch.extend_from_slice(&(frame_count as u16).to_be_bytes());
-
-        // Add frames (priority-ordered)
-        let mut actual_count = 0;
-        for frame in frames_to_send.iter().take(frame_count) {
-            let frame_bytes = frame.to_bytes();
-            batch.extend_from_slice(&frame_bytes);
-            actual_count += 1;
-        }
-
-        // Update frame count if different
-        if actual_count != frame_count {
-            let count_bytes = &(actual_count as u16).to_be_bytes();
-            batch[FRAME_START.len()] = count_bytes[0];
-            batch[FRAME_START.len() + 1] = count_bytes[1];
+        let actual_count = std::cmp::min(frames_to_send.len(), self.config.max_batch_size);
+        for frame in &mut frames_to_send[..actual_count] {
+            let next = self.tx_sequence.entry(frame.id).or_insert(0);
+            frame.sequence_number = *next;
+            *next += 1;
         }
-
-        // Add end marker
-        batch.extend_from_slice(FRAME_END);
-
-        // Calculate CRC32 for the entire batch (excluding markers)
-        let payload_start = FRAME_START.len();
-        let payload_end = batch.len() - FRAME_END.len();
-        let mut hasher = Hasher::new();
-        hasher.update(&batch[payload_start..payload_end]);
-        let crc = hasher.finalize();
-        batch.extend_from_slice(&crc.to_be_bytes());
+        let batch = encode_batch(&frames_to_send[..actual_count], self.checksum_mode);
 
         // Clear sent frames
-        self.latest_frames.clear();
-        self.frame_order.clear();
-        self.total_bytes = 0;
+        self.clear_pending();
         self.last_send = Instant::now();
         self.batch_count += 1;
+        self.frames_transmitted += actual_count as u64;
+        self.bytes_sent += batch.len() as u64;
 
-        println!(
+        trace!(
             "Created simple batch #{}: {} frames, {} bytes total (replaced: {})",
             self.batch_count,
             actual_count,
@@ -418,18 +915,102 @@ impl ImprovedFrameBatcher {
     }
 
     pub fn is_empty(&self) -> bool {
-        self.latest_frames.is_empty()
+        self.latest_frames.is_empty() && self.preserved_frames.is_empty()
+    }
+
+    // New limits apply from the next add_frame; frames already queued stay queued
+    pub fn set_config(&mut self, config: BatchConfig) {
+        self.config = config;
+        self.timeout_ms = self
+            .timeout_ms
+            .clamp(config.batch_timeout_ms, config.max_batch_timeout_ms);
+    }
+
+    // Stretch the partial-batch timeout while the lane's modem fails writes, so a marginal
+    // link gets fewer, fuller batches, and ease it back toward the configured timeout as
+    // writes succeed again
+    pub fn adapt_timeout(&mut self, status: &ModemStatus) {
+        let previous = self.timeout_ms;
+        if status.write_failures > self.seen_write_failures && status.consecutive_failures > 0 {
+            self.timeout_ms = (self.timeout_ms.max(1) * 2).min(self.config.max_batch_timeout_ms);
+        } else if status.last_success != self.seen_success {
+            self.timeout_ms -= (self.timeout_ms - self.config.batch_timeout_ms).div_ceil(4);
+        }
+        self.seen_write_failures = status.write_failures;
+        self.seen_success = status.last_success;
+
+        if self.timeout_ms != previous {
+            debug!(
+                "Batch timeout {} -> {} ms ({} consecutive write failures)",
+                previous, self.timeout_ms, status.consecutive_failures
+            );
+        }
     }
 
     pub fn get_queue_size(&self) -> usize {
-        self.latest_frames.len()
+        self.latest_frames.len() + self.preserved_frames.len()
     }
     
-    pub fn get_stats(&self) -> (u64, u64, usize) {
-        (self.total_frames_added, self.frames_replaced, self.latest_frames.len())
+    pub fn frames_transmitted(&self) -> u64 {
+        self.frames_transmitted
+    }
+
+    pub fn batch_count(&self) -> u64 {
+        self.batch_count
+    }
+
+    pub fn bytes_sent(&self) -> u64 {
+        self.bytes_sent
+    }
+
+    pub fn frames_filtered(&self) -> u64 {
+        self.frame_filter.frames_filtered()
+    }
+
+    pub fn preserved_dropped(&self) -> u64 {
+        self.preserved_dropped
+    }
+
+    pub fn frames_overflowed(&self) -> u64 {
+        self.frames_overflowed
+    }
+
+    pub fn set_overflow_policy(&mut self, policy: OverflowPolicy) {
+        self.overflow_policy = policy;
+    }
+
+    // Applies to frames queued from now on; `max_per_id` is at least 1
+    pub fn set_preserve_ids(&mut self, ids: HashSet<u32>, max_per_id: usize) {
+        self.preserve_ids = ids;
+        self.max_preserved_per_id = max_per_id.max(1);
+    }
+
+    // Frames already queued are re-measured in the new mode
+    pub fn set_checksum_mode(&mut self, mode: ChecksumMode) {
+        self.checksum_mode = mode;
+    }
+
+    pub fn filtered_count(&self) -> &HashMap<u32, u64> {
+        self.frame_filter.filtered_count()
     }
 }
 
+// RFD transmit throughput (both lanes); rates cover the last TX_RATE_WINDOW
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TxStats {
+    pub frames_per_sec: f64,
+    pub bytes_per_sec: f64,
+    pub batches_sent: u64,    // Since startup
+    pub frames_filtered: u64, // Since startup, dropped by the per-ID rate filter
+    // Since startup, shed because a lane stayed full (see OverflowPolicy)
+    pub frames_overflowed: u64,
+    // Since startup, preserve-all frames dropped past the --preserve-depth limit
+    pub preserved_dropped: u64,
+}
+
+// Running totals sampled by get_tx_stats: (when, frames, bytes)
+type TxSample = (Instant, u64, u64);
+
 #[derive(Debug, Clone)]
 pub struct ModemStatus {
     pub connected: bool,
@@ -438,6 +1019,12 @@ pub struct ModemStatus {
     pub error_message: Option<String>,
     pub last_transmission_attempt: Option<Instant>,
     pub consecutive_failures: u32,
+    pub write_failures: u64, // Total failed writes since startup
+    pub connections: u32,    // Times a modem was found and verified (first connect included)
+    // Most recent echoed RTT probe round trip, and probe counts since startup
+    pub last_rtt: Option<Duration>,
+    pub probes_sent: u64,
+    pub probes_echoed: u64,
 }
 
 impl ModemStatus {
@@ -449,7 +1036,45 @@ impl ModemStatus {
             error_message: None,
             last_transmission_attempt: None,
             consecutive_failures: 0,
+            write_failures: 0,
+            connections: 0,
+            last_rtt: None,
+            probes_sent: 0,
+            probes_echoed: 0,
+        }
+    }
+}
+
+// Simulated radio impairments applied just before bytes hit the port (--sim-loss / --sim-corrupt).
+// Seeded so a given run drops and corrupts the same batches every time.
+pub struct LinkSimulator {
+    drop_rate: f64,    // Probability a whole batch is silently lost
+    corrupt_rate: f64, // Per-byte probability of flipping one bit
+    rng: StdRng,
+}
+
+impl LinkSimulator {
+    pub fn new(drop_rate: f64, corrupt_rate: f64, seed: u64) -> Self {
+        Self {
+            drop_rate: drop_rate.clamp(0.0, 1.0),
+            corrupt_rate: corrupt_rate.clamp(0.0, 1.0),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    // Returns the bytes as the receiver would see them, or None if the batch was lost
+    pub fn apply(&mut self, batch: &[u8]) -> Option<Vec<u8>> {
+        if self.rng.random_bool(self.drop_rate) {
+            return None;
+        }
+
+        let mut bytes = batch.to_vec();
+        for byte in bytes.iter_mut() {
+            if self.rng.random_bool(self.corrupt_rate) {
+                *byte ^= 1 << self.rng.random_range(0..8);
+            }
         }
+        Some(bytes)
     }
 }
 
@@ -457,6 +1082,7 @@ struct ModemConnection {
     port: Option<Box<dyn SerialPort>>,
     modem_type: ModemType,
     last_health_check: Instant,
+    simulator: Option<LinkSimulator>,
 }
 
 pub struct SerialManager {
@@ -468,8 +1094,43 @@ pub struct SerialManager {
 
     // Enhanced batching fields
     rfd_batcher: Arc<Mutex<ImprovedFrameBatcher>>,
+    rfd_express_batcher: Arc<Mutex<ImprovedFrameBatcher>>, // Critical/High frames only
     batch_thread: Option<JoinHandle<()>>,
+    batch_running: Arc<Mutex<bool>>, // Cleared by stop_batching (and Drop) to end the thread
     batching_enabled: Arc<Mutex<bool>>,
+    // Checksums of everything this side transmits (e.g. --crc16); the lanes keep a copy
+    checksum_mode: Arc<Mutex<ChecksumMode>>,
+    // Operator priority overrides (e.g. --critical-ids), merged over the built-in mapping
+    priority_overrides: Arc<Mutex<HashMap<u32, MessagePriority>>>,
+
+    // Liveness heartbeats bumped by the background threads
+    scan_heartbeat: Heartbeat,
+    // Only touched by get_tx_stats, never on the transmit path
+    tx_samples: Arc<Mutex<VecDeque<TxSample>>>,
+    batch_heartbeat: Heartbeat,
+
+    // Receive side (start_receiving); RTT probe timestamps are relative to probe_epoch
+    rx_thread: Option<JoinHandle<()>>,
+    receiving: Arc<Mutex<bool>>,
+    probe_epoch: Instant,
+    rx_sequence: Arc<Mutex<SequenceTracker>>,
+
+    // LoRa backup link: its own port, status and single-lane batcher, off unless enabled
+    lora_connection: Arc<Mutex<ModemConnection>>,
+    pub lora_status: Arc<Mutex<ModemStatus>>,
+    lora_enabled: Arc<Mutex<bool>>,
+    lora_batcher: Arc<Mutex<ImprovedFrameBatcher>>,
+
+    // Baud rate the scanner opens the RFD at (set_rfd_baud_rate)
+    rfd_baud_rate: Arc<Mutex<u32>>,
+
+    // Port chosen with connect_rfd_port (replaces scanning), and whether scanning is allowed
+    rfd_port: Arc<Mutex<Option<String>>>,
+    rfd_auto_scan: Arc<Mutex<bool>>,
+    scan_all_ports: Arc<Mutex<bool>>, // Also probe non-USB/unknown adapters (set_scan_all_ports)
+
+    // Where received inject requests go (accept_inject_requests); None drops them
+    inject_sender: Arc<Mutex<Option<mpsc::Sender<InjectRequest>>>>,
 }
 
 impl SerialManager {
@@ -478,6 +1139,7 @@ impl SerialManager {
             port: None,
             modem_type: ModemType::Rfd900x,
             last_health_check: Instant::now(),
+            simulator: None,
         }));
 
         Self {
@@ -486,9 +1148,40 @@ impl SerialManager {
             scan_thread: None,
             scan_running: Arc::new(Mutex::new(false)),
             rfd_enabled: Arc::new(Mutex::new(true)),
-            rfd_batcher: Arc::new(Mutex::new(ImprovedFrameBatcher::new())),
+            rfd_batcher: Arc::new(Mutex::new(
+                ImprovedFrameBatcher::new(BatchConfig::default()),
+            )),
+            rfd_express_batcher: Arc::new(Mutex::new(ImprovedFrameBatcher::new(
+                BatchConfig::express(),
+            ))),
             batch_thread: None,
+            batch_running: Arc::new(Mutex::new(false)),
             batching_enabled: Arc::new(Mutex::new(true)),
+            checksum_mode: Arc::new(Mutex::new(ChecksumMode::default())),
+            priority_overrides: Arc::new(Mutex::new(HashMap::new())),
+            scan_heartbeat: Heartbeat::new(),
+            tx_samples: Arc::new(Mutex::new(VecDeque::new())),
+            batch_heartbeat: Heartbeat::new(),
+            rx_thread: None,
+            receiving: Arc::new(Mutex::new(false)),
+            probe_epoch: Instant::now(),
+            rx_sequence: Arc::new(Mutex::new(SequenceTracker::new())),
+            lora_connection: Arc::new(Mutex::new(ModemConnection {
+                port: None,
+                modem_type: ModemType::Lora,
+                last_health_check: Instant::now(),
+                simulator: None,
+            })),
+            lora_status: Arc::new(Mutex::new(ModemStatus::new())),
+            lora_enabled: Arc::new(Mutex::new(false)),
+            lora_batcher: Arc::new(Mutex::new(
+                ImprovedFrameBatcher::new(BatchConfig::default()),
+            )),
+            rfd_baud_rate: Arc::new(Mutex::new(DEFAULT_RFD_BAUD_RATE)),
+            rfd_port: Arc::new(Mutex::new(None)),
+            rfd_auto_scan: Arc::new(Mutex::new(true)),
+            scan_all_ports: Arc::new(Mutex::new(false)),
+            inject_sender: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -502,86 +1195,273 @@ impl SerialManager {
             conn.port = None;
             status.connected = false;
             status.port_name = None;
-            println!("RFD 900x2 modem disabled");
+            info!("RFD 900x2 modem disabled");
         } else {
-            println!("RFD 900x2 modem enabled");
+            info!("RFD 900x2 modem enabled");
+        }
+    }
+
+    // Change the RFD baud rate. An open connection is dropped so the scanner reconnects at
+    // the new rate.
+    pub fn set_rfd_baud_rate(&self, baud_rate: u32) {
+        let previous = std::mem::replace(&mut *self.rfd_baud_rate.lock().unwrap(), baud_rate);
+        if previous == baud_rate {
+            return;
+        }
+
+        let mut conn = self.rfd_connection.lock().unwrap();
+        let mut status = self.rfd_status.lock().unwrap();
+        if status.connected {
+            conn.port = None;
+            status.connected = false;
+            status.port_name = None;
+            info!("RFD baud rate changed to {}, reconnecting", baud_rate);
+        }
+    }
+
+    // Limits for the routine lanes (RFD and LoRa); the express lane keeps its own
+    pub fn set_batch_config(&self, config: BatchConfig) -> Result<(), SerialError> {
+        config.validate()?;
+        lock_lane(&self.rfd_batcher).set_config(config);
+        lock_lane(&self.lora_batcher).set_config(config);
+        Ok(())
+    }
+
+    // How every lane sheds frames when its modem can't keep up
+    pub fn set_overflow_policy(&self, policy: OverflowPolicy) {
+        for lane in [
+            &self.rfd_batcher,
+            &self.rfd_express_batcher,
+            &self.lora_batcher,
+        ] {
+            lock_lane(lane).set_overflow_policy(policy);
+        }
+    }
+
+    // Checksum mode for everything transmitted from now on: batches, single frames and probes
+    pub fn set_checksum_mode(&self, mode: ChecksumMode) {
+        *lock_recovering(&self.checksum_mode, "checksum mode") = mode;
+        for lane in [
+            &self.rfd_batcher,
+            &self.rfd_express_batcher,
+            &self.lora_batcher,
+        ] {
+            lock_lane(lane).set_checksum_mode(mode);
+        }
+    }
+
+    // IDs every lane sends all frames of instead of only the latest, keeping up to
+    // `max_per_id` of each queued
+    pub fn set_preserve_ids(&self, ids: HashSet<u32>, max_per_id: usize) {
+        for lane in [
+            &self.rfd_batcher,
+            &self.rfd_express_batcher,
+            &self.lora_batcher,
+        ] {
+            lock_lane(lane).set_preserve_ids(ids.clone(), max_per_id);
         }
     }
 
+    // Replace the priority overrides; frames sent from now on pick lanes by them
+    pub fn set_priority_overrides(&self, overrides: HashMap<u32, MessagePriority>) {
+        *lock_recovering(&self.priority_overrides, "priority overrides") = overrides;
+    }
+
+    // Install (or remove) simulated loss/corruption on the RFD link
+    pub fn set_link_simulator(&self, simulator: Option<LinkSimulator>) {
+        self.rfd_connection.lock().unwrap().simulator = simulator;
+    }
+
     pub fn is_rfd_enabled(&self) -> bool {
-        *self.rfd_enabled.lock().unwrap()
+        *lock_recovering(&self.rfd_enabled, "RFD enabled")
+    }
+
+    // RFD link status, usable even after a thread panicked while updating it
+    pub fn lock_rfd_status(&self) -> MutexGuard<'_, ModemStatus> {
+        lock_recovering(&self.rfd_status, "RFD status")
+    }
+
+    pub fn set_lora_enabled(&self, enabled: bool) {
+        *self.lora_enabled.lock().unwrap() = enabled;
+        if !enabled {
+            let mut conn = self.lora_connection.lock().unwrap();
+            let mut status = self.lora_status.lock().unwrap();
+            conn.port = None;
+            status.connected = false;
+            status.port_name = None;
+            info!("LoRa modem disabled");
+        } else {
+            info!("LoRa modem enabled");
+        }
+    }
+
+    pub fn is_lora_enabled(&self) -> bool {
+        *lock_recovering(&self.lora_enabled, "LoRa enabled")
     }
 
     // Optimized CAN frame sending with enhanced batching
-    pub fn send_can_frame(&self, can_id: u32, data: &[u8]) -> Result<(), String> {
-        let frame = CanFrameData::new(can_id, data);
+    pub fn send_can_frame(
+        &self,
+        can_id: u32,
+        is_extended: bool,
+        data: &[u8],
+    ) -> Result<(), SerialError> {
+        let frame = self.outgoing_frame(can_id, is_extended, data);
         let rfd_enabled = self.is_rfd_enabled();
-        let batching_enabled = *self.batching_enabled.lock().unwrap();
+        let batching_enabled = *lock_recovering(&self.batching_enabled, "batching enabled");
 
         if !batching_enabled {
             // Fall back to individual transmission
-            return self.send_can_frame_individual(can_id, data);
+            return self.send_can_frame_individual(frame);
         }
 
         let mut success_count = 0;
         let mut errors = Vec::new();
 
-        // Add to RFD batch
-        if rfd_enabled && self.rfd_status.lock().unwrap().connected {
-            let mut batcher = self.rfd_batcher.lock().unwrap();
-            if !batcher.add_frame(frame.clone()) {
-                // Batch is full, force send current batch and retry
-                drop(batcher);
-                self.force_send_rfd_batch();
-                let mut batcher = self.rfd_batcher.lock().unwrap();
-                if batcher.add_frame(frame) {
-                    success_count += 1;
-                } else {
-                    errors.push("RFD batch overflow".to_string());
-                }
-            } else {
-                success_count += 1;
+        // Add to RFD batch (Critical/High frames take the express lane)
+        if rfd_enabled && self.lock_rfd_status().connected {
+            let lane = self.batcher_for(frame.priority);
+            match self.queue_frame(lane, &self.rfd_connection, &self.rfd_status, frame.clone()) {
+                Ok(()) => success_count += 1,
+                Err(e) => errors.push(e),
             }
         }
 
-        if success_count > 0 {
-            Ok(())
-        } else if !errors.is_empty() {
-            Err(errors.join("; "))
-        } else {
-            Err("No modems available for batching".to_string())
+        // The LoRa link carries every frame too, through its single lane
+        if self.is_lora_enabled() && lock_recovering(&self.lora_status, "LoRa status").connected {
+            match self.queue_frame(
+                &self.lora_batcher,
+                &self.lora_connection,
+                &self.lora_status,
+                frame,
+            ) {
+                Ok(()) => success_count += 1,
+                Err(e) => errors.push(e),
+            }
+        }
+
+        // With both links failing, report the RFD's error (the LoRa one is usually the same)
+        match errors.into_iter().next() {
+            Some(e) if success_count == 0 => Err(e),
+            None if success_count == 0 => Err(SerialError::NoModems),
+            _ => Ok(()),
         }
     }
 
-    fn force_send_rfd_batch(&self) {
-        let batch_data = {
-            let mut batcher = self.rfd_batcher.lock().unwrap();
-            if batcher.is_empty() {
-                return;
-            }
-            batcher.create_batch()
-        };
+    // A frame to transmit, with its priority overridden if the operator asked for one
+    fn outgoing_frame(&self, can_id: u32, is_extended: bool, data: &[u8]) -> CanFrameData {
+        let mut frame = CanFrameData::new_with_format(can_id, data, is_extended);
+        if let Some(priority) =
+            lock_recovering(&self.priority_overrides, "priority overrides").get(&can_id)
+        {
+            frame.priority = *priority;
+        }
+        frame
+    }
 
-        Self::send_rfd_batch_improved(&self.rfd_connection, &self.rfd_status, &batch_data);
+    fn batcher_for(&self, priority: MessagePriority) -> &Arc<Mutex<ImprovedFrameBatcher>> {
+        if priority <= MessagePriority::High {
+            &self.rfd_express_batcher
+        } else {
+            &self.rfd_batcher
+        }
     }
 
-    // Enhanced RFD batch sending with proper framing and error handling
-    fn send_rfd_batch_improved(
+    // Add `frame` to `lane`; when the batch is full, send it to the lane's modem and retry.
+    // If it is still full (the port was busy), the lane's overflow policy sheds a frame.
+    fn queue_frame(
+        &self,
+        lane: &Arc<Mutex<ImprovedFrameBatcher>>,
         connection: &Arc<Mutex<ModemConnection>>,
         status: &Arc<Mutex<ModemStatus>>,
-        batch_data: &[u8],
-    ) {
-        if batch_data.is_empty() {
-            return;
+        frame: CanFrameData,
+    ) -> Result<(), SerialError> {
+        let mut batcher = lock_lane(lane);
+        if batcher.add_frame(frame.clone()) {
+            return Ok(());
         }
+        let wait = batcher.overflow_policy == OverflowPolicy::Block;
+        drop(batcher);
+        self.force_send_batch(lane, connection, status, wait);
+
+        // Retry without the rate filter, which already passed this frame
+        let mut batcher = lock_lane(lane);
+        while !batcher.enqueue(frame.clone()) {
+            if !batcher.shed_for(&frame) {
+                drop(batcher);
+                if wait {
+                    let modem = connection.lock().unwrap().modem_type.label();
+                    return Err(SerialError::BatchOverflow(modem));
+                }
+                trace!("Lane full, dropped frame 0x{:X}", frame.id);
+                return Ok(());
+            }
+        }
+        Ok(())
+    }
 
-        let mut conn = match connection.try_lock() {
-            Ok(guard) => guard,
-            Err(_) => {
-                println!("RFD port busy, skipping batch");
+    // Send whatever `lane` has queued. Unless `wait` is set a busy port is skipped and the
+    // frames stay queued, so they aren't lost to another thread holding the lock.
+    fn force_send_batch(
+        &self,
+        lane: &Arc<Mutex<ImprovedFrameBatcher>>,
+        connection: &Arc<Mutex<ModemConnection>>,
+        status: &Arc<Mutex<ModemStatus>>,
+        wait: bool,
+    ) {
+        let mut conn = if wait {
+            connection.lock().unwrap()
+        } else {
+            match connection.try_lock() {
+                Ok(guard) => guard,
+                Err(_) => {
+                    debug!("Modem port busy, keeping batch queued");
+                    return;
+                }
+            }
+        };
+        let batch_data = {
+            let mut batcher = lock_lane(lane);
+            if batcher.is_empty() {
                 return;
             }
+            batcher.create_batch()
+        };
+
+        Self::write_batch(&mut conn, status, &batch_data);
+    }
+
+    // Enhanced RFD batch sending with proper framing and error handling
+    fn send_rfd_batch_improved(
+        connection: &Arc<Mutex<ModemConnection>>,
+        status: &Arc<Mutex<ModemStatus>>,
+        batch_data: &[u8],
+    ) {
+        if batch_data.is_empty() {
+            return;
+        }
+
+        match connection.try_lock() {
+            Ok(mut conn) => Self::write_batch(&mut conn, status, batch_data),
+            Err(_) => debug!("Modem port busy, skipping batch"),
+        }
+    }
+
+    fn write_batch(
+        conn: &mut ModemConnection,
+        status: &Arc<Mutex<ModemStatus>>,
+        batch_data: &[u8],
+    ) {
+        let modem = conn.modem_type.label();
+        // Simulated impairments sit between the batcher and the port
+        let impaired = match conn.simulator.as_mut() {
+            Some(simulator) => match simulator.apply(batch_data) {
+                Some(bytes) => Some(bytes),
+                None => return, // Lost on the simulated link
+            },
+            None => None,
         };
+        let batch_data = impaired.as_deref().unwrap_or(batch_data);
 
         if let Some(port) = conn.port.as_mut() {
             // Set timeout for transmission
@@ -591,21 +1471,20 @@ impl SerialManager {
             match port.write_all(batch_data) {
                 Ok(_) => {
                     if let Err(e) = port.flush() {
-                        println!("RFD flush error: {}", e);
+                        warn!("{} flush error: {}", modem, e);
                         Self::update_transmission_status_static(status, false);
                     } else {
-                        // Uncomment for detailed logging:
-                        // println!("RFD batch sent: {} bytes", batch_data.len());
+                        trace!("{} batch sent: {} bytes", modem, batch_data.len());
                         Self::update_transmission_status_static(status, true);
                     }
                 }
                 Err(e) => {
-                    println!("RFD write error: {}", e);
+                    warn!("{} write error: {}", modem, e);
                     Self::update_transmission_status_static(status, false);
                 }
             }
         } else {
-            println!("RFD port not available");
+            debug!("{} port not available", modem);
             Self::update_transmission_status_static(status, false);
         }
     }
@@ -619,12 +1498,13 @@ impl SerialManager {
                 status.consecutive_failures = 0;
             } else {
                 status.consecutive_failures += 1;
+                status.write_failures += 1;
             }
         }
     }
 
     // Fallback individual transmission method (for compatibility)
-    fn send_can_frame_individual(&self, can_id: u32, data: &[u8]) -> Result<(), String> {
+    fn send_can_frame_individual(&self, frame: CanFrameData) -> Result<(), SerialError> {
         let rfd_connected = self.rfd_status.lock().unwrap().connected;
         let rfd_enabled = self.is_rfd_enabled();
 
@@ -632,56 +1512,74 @@ impl SerialManager {
         let mut success_count = 0;
 
         if rfd_enabled && rfd_connected {
-            match self.send_can_frame_rfd_fast(can_id, data) {
+            match self.send_can_frame_fast(&self.rfd_connection, &self.rfd_status, frame.clone()) {
                 Ok(_) => success_count += 1,
-                Err(e) => errors.push(format!("RFD error: {}", e)),
+                Err(e) => errors.push(e),
             }
         }
 
-        if success_count > 0 {
-            Ok(())
-        } else if !errors.is_empty() {
-            Err(errors.join("; "))
-        } else {
-            Err("No modems available for transmission".to_string())
+        if self.is_lora_enabled() && self.lora_status.lock().unwrap().connected {
+            match self.send_can_frame_fast(&self.lora_connection, &self.lora_status, frame) {
+                Ok(_) => success_count += 1,
+                Err(e) => errors.push(e),
+            }
+        }
+
+        match errors.into_iter().next() {
+            Some(e) if success_count == 0 => Err(e),
+            None if success_count == 0 => Err(SerialError::NoModems),
+            _ => Ok(()),
         }
     }
 
-    // Fast RFD transmission (individual frames)
-    fn send_can_frame_rfd_fast(&self, can_id: u32, data: &[u8]) -> Result<(), String> {
-        // For RFD 900x2, we send the raw CAN ID followed by the data
-        let mut payload = Vec::with_capacity(4 + data.len());
-        payload.extend_from_slice(&can_id.to_be_bytes());
-        payload.extend_from_slice(data);
+    // Fast transmission of individual frames to one modem
+    fn send_can_frame_fast(
+        &self,
+        connection: &Arc<Mutex<ModemConnection>>,
+        status: &Arc<Mutex<ModemStatus>>,
+        frame: CanFrameData,
+    ) -> Result<(), SerialError> {
+        // Send as a batch of one so the receiver parses both paths identically and a
+        // write that times out part-way is caught by the CRC instead of desyncing the stream
+        let mode = *lock_recovering(&self.checksum_mode, "checksum mode");
+        let payload = encode_batch(&[frame], mode);
 
         // Use try_lock to avoid blocking
-        let mut rfd_conn = match self.rfd_connection.try_lock() {
+        let mut conn = match connection.try_lock() {
             Ok(guard) => guard,
             Err(_) => {
-                // Port is busy - update status but don't fail
-                self.update_transmission_status(&self.rfd_status, false);
-                return Ok(()); // Return Ok to prevent error flooding
+                self.update_transmission_status(status, false);
+                return Err(SerialError::PortBusy);
             }
         };
+        let modem = conn.modem_type.label();
+
+        let payload = match conn.simulator.as_mut() {
+            Some(simulator) => match simulator.apply(&payload) {
+                Some(bytes) => bytes,
+                None => return Ok(()), // Lost on the simulated link
+            },
+            None => payload,
+        };
 
-        if let Some(port) = rfd_conn.port.as_mut() {
+        if let Some(port) = conn.port.as_mut() {
             // Set a short timeout for transmission
             let _ = port.set_timeout(Duration::from_millis(TRANSMISSION_TIMEOUT_MS));
 
             match port.write_all(&payload) {
                 Ok(_) => {
                     let _ = port.flush(); // Try to flush but don't fail if it doesn't work
-                    self.update_transmission_status(&self.rfd_status, true);
+                    self.update_transmission_status(status, true);
                     Ok(())
                 }
                 Err(e) => {
-                    self.update_transmission_status(&self.rfd_status, false);
-                    Err(format!("Failed to write to RFD port: {}", e))
+                    self.update_transmission_status(status, false);
+                    Err(SerialError::WriteFailed { modem, source: e })
                 }
             }
         } else {
-            self.update_transmission_status(&self.rfd_status, false);
-            Err("RFD port not open".to_string())
+            self.update_transmission_status(status, false);
+            Err(SerialError::NotConnected(modem))
         }
     }
 
@@ -695,25 +1593,98 @@ impl SerialManager {
                 status.consecutive_failures = 0;
             } else {
                 status.consecutive_failures += 1;
+                status.write_failures += 1;
             }
         }
     }
 
-    pub fn enable_batching(&self, enabled: bool) {
-        *self.batching_enabled.lock().unwrap() = enabled;
-        if enabled {
-            println!("Enhanced batching enabled");
-        } else {
-            println!("Enhanced batching disabled");
+    pub fn get_batch_stats(&self) -> usize {
+        lock_lane(&self.rfd_batcher).get_queue_size()
+            + lock_lane(&self.rfd_express_batcher).get_queue_size()
+    }
+
+    // Frames waiting in the LoRa batcher
+    pub fn get_lora_queue_size(&self) -> usize {
+        lock_lane(&self.lora_batcher).get_queue_size()
+    }
+
+    // Total frames sent over RF across both lanes since startup
+    pub fn get_frames_transmitted(&self) -> u64 {
+        let standard = lock_lane(&self.rfd_batcher).frames_transmitted();
+        let express = self
+            .rfd_express_batcher
+            .lock()
+            .unwrap()
+            .frames_transmitted();
+        standard + express
+    }
+
+    // Total batches created across both lanes since startup
+    pub fn get_batches_sent(&self) -> u64 {
+        let standard = lock_lane(&self.rfd_batcher).batch_count();
+        let express = lock_lane(&self.rfd_express_batcher).batch_count();
+        standard + express
+    }
+
+    // Throughput over the last TX_RATE_WINDOW, read from the batchers' own counters
+    pub fn get_tx_stats(&self) -> TxStats {
+        let mut stats = TxStats::default();
+        let (mut frames, mut bytes) = (0, 0);
+        for lane in [&self.rfd_batcher, &self.rfd_express_batcher] {
+            let batcher = lock_lane(lane);
+            frames += batcher.frames_transmitted();
+            bytes += batcher.bytes_sent();
+            stats.batches_sent += batcher.batch_count();
+            stats.frames_filtered += batcher.frames_filtered();
+            stats.frames_overflowed += batcher.frames_overflowed();
+            stats.preserved_dropped += batcher.preserved_dropped();
+        }
+
+        let now = Instant::now();
+        let mut samples = self.tx_samples.lock().unwrap();
+        samples.push_back((now, frames, bytes));
+        while samples.len() > 2 && now.duration_since(samples[0].0) > TX_RATE_WINDOW {
+            samples.pop_front();
+        }
+
+        let (since, first_frames, first_bytes) = samples[0];
+        let elapsed = now.duration_since(since).as_secs_f64();
+        if elapsed > 0.0 {
+            stats.frames_per_sec = (frames - first_frames) as f64 / elapsed;
+            stats.bytes_per_sec = (bytes - first_bytes) as f64 / elapsed;
         }
+        stats
     }
 
-    pub fn get_batch_stats(&self) -> usize {
-        self.rfd_batcher.lock().unwrap().get_queue_size()
+    // Frames dropped by the rate filter per CAN ID since startup, across both RFD lanes
+    pub fn get_filtered_counts(&self) -> HashMap<u32, u64> {
+        let mut counts = HashMap::new();
+        for lane in [&self.rfd_batcher, &self.rfd_express_batcher] {
+            for (id, count) in lock_lane(lane).filtered_count() {
+                *counts.entry(*id).or_insert(0) += count;
+            }
+        }
+        counts
+    }
+
+    pub fn scan_heartbeat(&self) -> &Heartbeat {
+        &self.scan_heartbeat
+    }
+
+    pub fn is_scanning(&self) -> bool {
+        *self.scan_running.lock().unwrap()
+    }
+
+    pub fn batch_heartbeat(&self) -> &Heartbeat {
+        &self.batch_heartbeat
+    }
+
+    pub fn is_batching(&self) -> bool {
+        *self.batching_enabled.lock().unwrap()
     }
 
     // Start scanning for modems in the background
-    pub fn start_background_scanning(&mut self) -> Result<(), String> {
+    pub fn start_background_scanning(&mut self) -> Result<(), SerialError> {
         // If a scan is already running, don't start another one
         let mut scan_running = self.scan_running.lock().unwrap();
         if *scan_running {
@@ -726,12 +1697,21 @@ impl SerialManager {
         let rfd_status = Arc::clone(&self.rfd_status);
         let scan_running = Arc::clone(&self.scan_running);
         let rfd_enabled = Arc::clone(&self.rfd_enabled);
+        let lora_connection = Arc::clone(&self.lora_connection);
+        let lora_status = Arc::clone(&self.lora_status);
+        let lora_enabled = Arc::clone(&self.lora_enabled);
+        let rfd_baud_rate = Arc::clone(&self.rfd_baud_rate);
+        let rfd_port = Arc::clone(&self.rfd_port);
+        let rfd_auto_scan = Arc::clone(&self.rfd_auto_scan);
+        let scan_all_ports = Arc::clone(&self.scan_all_ports);
+        let heartbeat = self.scan_heartbeat.clone();
 
         // Spawn a thread to perform scanning
         let scan_thread = thread::spawn(move || {
             let mut last_rfd_scan = Instant::now()
                 .checked_sub(Duration::from_millis(RFD_SCAN_INTERVAL_MS))
                 .unwrap_or_else(Instant::now);
+            let mut last_lora_scan = last_rfd_scan;
             let mut last_connection_check = Instant::now();
 
             loop {
@@ -739,6 +1719,7 @@ impl SerialManager {
                 if !*scan_running.lock().unwrap() {
                     break;
                 }
+                heartbeat.beat();
 
                 let now = Instant::now();
 
@@ -746,47 +1727,66 @@ impl SerialManager {
                 if now.duration_since(last_connection_check).as_millis()
                     >= CONNECTION_CHECK_INTERVAL_MS as u128
                 {
-                    // Check RFD connection health passively
+                    // Check RFD and LoRa connection health passively
                     if *rfd_enabled.lock().unwrap() {
-                        let mut status = rfd_status.lock().unwrap();
-                        if status.connected {
-                            let should_disconnect = if let Some(last_success) = status.last_success
-                            {
-                                now.duration_since(last_success).as_millis()
-                                    > CONNECTION_GRACE_PERIOD_MS as u128
-                            } else {
-                                true
-                            };
-
-                            if should_disconnect || status.consecutive_failures > 10 {
-                                status.connected = false;
-                                status.error_message =
-                                    Some("Connection health check failed".to_string());
-                                let mut conn = rfd_connection.lock().unwrap();
-                                conn.port = None;
-                                println!(
-                                    "RFD 900x2 connection marked as unhealthy, will reconnect"
-                                );
-                            }
-                        }
+                        Self::check_connection_health(&rfd_connection, &rfd_status, now);
+                    }
+                    if *lora_enabled.lock().unwrap() {
+                        Self::check_connection_health(&lora_connection, &lora_status, now);
                     }
 
                     last_connection_check = now;
                 }
 
-                // Scan for RFD devices if not connected and it's time to scan
+                // Scan for RFD devices if not connected and it's time to scan (or retry the
+                // pinned port, when one was chosen)
+                let pinned_port = rfd_port.lock().unwrap().clone();
                 if *rfd_enabled.lock().unwrap()
                     && !rfd_status.lock().unwrap().connected
                     && now.duration_since(last_rfd_scan).as_millis() >= RFD_SCAN_INTERVAL_MS as u128
+                {
+                    let baud_rate = *rfd_baud_rate.lock().unwrap();
+                    if let Some(port_name) = &pinned_port {
+                        if let Err(e) = Self::open_modem_port(
+                            &rfd_connection,
+                            &rfd_status,
+                            port_name,
+                            baud_rate,
+                            ModemType::Rfd900x,
+                            &Self::verify_rfd_connection,
+                        ) {
+                            warn!("{}", e);
+                        }
+                    } else if *rfd_auto_scan.lock().unwrap() {
+                        Self::scan_for_modem(
+                            &rfd_connection,
+                            &rfd_status,
+                            baud_rate,
+                            ModemType::Rfd900x,
+                            &Self::verify_rfd_connection,
+                            lora_status.lock().unwrap().port_name.clone(),
+                            *scan_all_ports.lock().unwrap(),
+                        );
+                    }
+                    last_rfd_scan = now;
+                }
+
+                // Same for LoRa, skipping whichever port the RFD holds or is pinned to
+                if *lora_enabled.lock().unwrap()
+                    && !lora_status.lock().unwrap().connected
+                    && now.duration_since(last_lora_scan).as_millis()
+                        >= RFD_SCAN_INTERVAL_MS as u128
                 {
                     Self::scan_for_modem(
-                        &rfd_connection,
-                        &rfd_status,
-                        RFD_BAUD_RATE,
-                        ModemType::Rfd900x,
-                        &Self::verify_rfd_connection,
+                        &lora_connection,
+                        &lora_status,
+                        LORA_BAUD_RATE,
+                        ModemType::Lora,
+                        &Self::verify_lora_connection,
+                        rfd_status.lock().unwrap().port_name.clone().or(pinned_port),
+                        *scan_all_ports.lock().unwrap(),
                     );
-                    last_rfd_scan = now;
+                    last_lora_scan = now;
                 }
 
                 // Sleep to avoid using too much CPU
@@ -809,17 +1809,72 @@ impl SerialManager {
         }
     }
 
-    // Scan for a specific modem type
+    // Passive health check based on transmission success/failure: drop a link with no
+    // successful write within the grace period, or too many failures, so it gets rescanned
+    fn check_connection_health(
+        connection: &Arc<Mutex<ModemConnection>>,
+        status: &Arc<Mutex<ModemStatus>>,
+        now: Instant,
+    ) {
+        let mut status = status.lock().unwrap();
+        if !status.connected {
+            return;
+        }
+
+        let should_disconnect = if let Some(last_success) = status.last_success {
+            now.duration_since(last_success).as_millis() > CONNECTION_GRACE_PERIOD_MS as u128
+        } else {
+            true
+        };
+
+        if should_disconnect || status.consecutive_failures > 10 {
+            status.connected = false;
+            status.error_message = Some("Connection health check failed".to_string());
+            let mut conn = connection.lock().unwrap();
+            conn.port = None;
+            warn!(
+                "{} connection marked as unhealthy, will reconnect",
+                conn.modem_type.label()
+            );
+        }
+    }
+
+    // Scan for a specific modem type; `other_port` (held by the other modem) is never tried
     fn scan_for_modem<F>(
         connection: &Arc<Mutex<ModemConnection>>,
         status: &Arc<Mutex<ModemStatus>>,
         baud_rate: u32,
         modem_type: ModemType,
         verify_fn: &F,
+        other_port: Option<String>,
+        all_ports: bool,
     ) where
-        F: Fn(&mut Box<dyn SerialPort>) -> Result<(), String>,
+        F: Fn(&mut Box<dyn SerialPort>) -> io::Result<()>,
     {
-        let ports = Self::list_available_ports();
+        let ports = match Self::list_available_ports(all_ports) {
+            Ok(ports) => {
+                let mut stat = status.lock().unwrap();
+                if stat
+                    .error_message
+                    .as_deref()
+                    .is_some_and(|e| e.starts_with(PORT_ENUMERATION_ERROR))
+                {
+                    info!("Serial port enumeration recovered");
+                    stat.error_message = None;
+                }
+                ports
+            }
+            Err(e) => {
+                // Log once rather than every scan interval while the error persists
+                let message = e.to_string();
+                let mut stat = status.lock().unwrap();
+                if stat.error_message.as_deref() != Some(message.as_str()) {
+                    warn!("{}", message);
+                    stat.error_message = Some(message);
+                }
+                return;
+            }
+        };
 
         // Keep track of current port name to avoid reconnecting to the same port
         let current_port_name = status.lock().unwrap().port_name.clone();
@@ -831,160 +1886,238 @@ impl SerialManager {
                     continue;
                 }
             }
+            if other_port.as_ref() == Some(&port_name) {
+                continue;
+            }
 
-            // Try to open the port
-            match serialport::new(&port_name, baud_rate)
-                .timeout(Duration::from_millis(1000))
-                .open()
-            {
-                Ok(mut port) => {
-                    // Try to verify the connected device
-                    match verify_fn(&mut port) {
-                        Ok(()) => {
-                            // Device verified! Update connection and status
-                            let mut conn = connection.lock().unwrap();
-                            let mut stat = status.lock().unwrap();
-
-                            conn.port = Some(port);
-                            conn.modem_type = modem_type.clone();
-                            conn.last_health_check = Instant::now();
-
-                            stat.connected = true;
-                            stat.port_name = Some(port_name.clone());
-                            stat.last_success = Some(Instant::now());
-                            stat.error_message = None;
-                            stat.consecutive_failures = 0;
-
-                            println!("{:?} modem connected on port {}", modem_type, port_name);
-                            break;
-                        }
-                        Err(e) => {
-                            // Not the right device, continue scanning
-                            println!(
-                                "Failed to verify {:?} on port {}: {}",
-                                modem_type, port_name, e
-                            );
-                            continue;
-                        }
-                    }
-                }
-                Err(e) => {
-                    // Couldn't open the port, try next
-                    println!("Failed to open port {}: {}", port_name, e);
-                    continue;
-                }
+            // Not the right device or couldn't open it, try the next port
+            match Self::open_modem_port(
+                connection,
+                status,
+                &port_name,
+                baud_rate,
+                modem_type.clone(),
+                verify_fn,
+            ) {
+                Ok(()) => break,
+                Err(e) => debug!("{}", e),
             }
         }
     }
 
+    // Open and verify one specific port, making it the modem's connection on success
+    fn open_modem_port<F>(
+        connection: &Arc<Mutex<ModemConnection>>,
+        status: &Arc<Mutex<ModemStatus>>,
+        port_name: &str,
+        baud_rate: u32,
+        modem_type: ModemType,
+        verify_fn: &F,
+    ) -> Result<(), SerialError>
+    where
+        F: Fn(&mut Box<dyn SerialPort>) -> io::Result<()>,
+    {
+        let mut port = serialport::new(port_name, baud_rate)
+            .timeout(Duration::from_millis(1000))
+            .open()
+            .map_err(|source| SerialError::OpenFailed {
+                port: port_name.to_string(),
+                source,
+            })?;
+        verify_fn(&mut port).map_err(|source| SerialError::VerifyFailed {
+            modem: modem_type.label(),
+            port: port_name.to_string(),
+            source,
+        })?;
+
+        // Device verified! Update connection and status
+        let mut conn = connection.lock().unwrap();
+        let mut stat = status.lock().unwrap();
+
+        conn.port = Some(port);
+        conn.modem_type = modem_type.clone();
+        conn.last_health_check = Instant::now();
+
+        stat.connected = true;
+        stat.port_name = Some(port_name.to_string());
+        stat.last_success = Some(Instant::now());
+        stat.error_message = None;
+        stat.consecutive_failures = 0;
+        stat.connections += 1;
+
+        info!("{:?} modem connected on port {}", modem_type, port_name);
+        Ok(())
+    }
+
+    // Use `port_name` for the RFD instead of scanning. The port stays pinned even if it
+    // can't be opened yet: the scanner then retries it (and only it) until it connects.
+    pub fn connect_rfd_port(&self, port_name: &str) -> Result<(), SerialError> {
+        *self.rfd_port.lock().unwrap() = Some(port_name.to_string());
+        if self.lora_status.lock().unwrap().port_name.as_deref() == Some(port_name) {
+            return Err(SerialError::PortInUse(port_name.to_string()));
+        }
+
+        let baud_rate = *self.rfd_baud_rate.lock().unwrap();
+        let result = Self::open_modem_port(
+            &self.rfd_connection,
+            &self.rfd_status,
+            port_name,
+            baud_rate,
+            ModemType::Rfd900x,
+            &Self::verify_rfd_connection,
+        );
+        if let Err(e) = &result {
+            self.rfd_status.lock().unwrap().error_message = Some(e.to_string());
+        }
+        result
+    }
+
+    // Whether the scanner may probe serial ports for the RFD (a pinned port is still retried)
+    pub fn set_rfd_auto_scan(&self, enabled: bool) {
+        *self.rfd_auto_scan.lock().unwrap() = enabled;
+    }
+
     // Simplified RFD verification - just check if we can open the port
-    fn verify_rfd_connection(port: &mut Box<dyn SerialPort>) -> Result<(), String> {
+    fn verify_rfd_connection(port: &mut Box<dyn SerialPort>) -> io::Result<()> {
         // For RFD, we'll use a simpler verification method to avoid interfering with transmission
         // Just try to set the timeout and flush - if this works, assume it's an RFD
 
         // Set timeout
-        port.set_timeout(Duration::from_millis(100))
-            .map_err(|e| format!("Failed to set timeout: {}", e))?;
+        port.set_timeout(Duration::from_millis(100))?;
 
         // Try to flush - this is a simple operation that should work on any serial device
-        port.flush()
-            .map_err(|e| format!("Failed to flush port: {}", e))?;
+        port.flush()?;
 
         // If we get here, the port is working
-        println!("RFD 900x2 verification successful (simple check)");
+        debug!("RFD 900x2 verification successful (simple check)");
+        Ok(())
+    }
+
+    // LoRa modules take the same simple check as the RFD; the first free port that opens wins
+    fn verify_lora_connection(port: &mut Box<dyn SerialPort>) -> io::Result<()> {
+        port.set_timeout(Duration::from_millis(100))?;
+        port.flush()?;
+
+        debug!("LoRa verification successful (simple check)");
         Ok(())
     }
 
     // Enhanced batch thread with better error handling and statistics
-    pub fn start_batching(&mut self) -> Result<(), String> {
+    pub fn start_batching(&mut self) -> Result<(), SerialError> {
         if self.batch_thread.is_some() {
             return Ok(()); // Already running
         }
+        *self.batch_running.lock().unwrap() = true;
 
         let rfd_batcher = Arc::clone(&self.rfd_batcher);
+        let rfd_express_batcher = Arc::clone(&self.rfd_express_batcher);
         let rfd_connection = Arc::clone(&self.rfd_connection);
         let rfd_status = Arc::clone(&self.rfd_status);
+        let batch_running = Arc::clone(&self.batch_running);
         let batching_enabled = Arc::clone(&self.batching_enabled);
         let rfd_enabled = Arc::clone(&self.rfd_enabled);
+        let heartbeat = self.batch_heartbeat.clone();
+        let receiving = Arc::clone(&self.receiving);
+        let probe_epoch = self.probe_epoch;
+        let inject_sender = Arc::clone(&self.inject_sender);
+        let rx_sequence = Arc::clone(&self.rx_sequence);
+        let lora_batcher = Arc::clone(&self.lora_batcher);
+        let lora_connection = Arc::clone(&self.lora_connection);
+        let lora_status = Arc::clone(&self.lora_status);
+        let lora_enabled = Arc::clone(&self.lora_enabled);
+        let checksum_mode = Arc::clone(&self.checksum_mode);
 
         let batch_thread = thread::spawn(move || {
             let mut last_stats = Instant::now();
+            let mut last_probe = Instant::now();
+            let mut reader = PortReader::new();
             let mut rfd_batch_count = 0u64;
+            let mut rfd_express_count = 0u64;
+            let mut lora_batch_count = 0u64;
 
-            println!("Enhanced batch thread started");
+            debug!("Enhanced batch thread started");
 
             loop {
-                if !*batching_enabled.lock().unwrap() {
+                if !*lock_recovering(&batch_running, "batch running")
+                    || !*lock_recovering(&batching_enabled, "batching enabled")
+                {
                     break;
                 }
+                heartbeat.beat();
 
                 let mut sent_batch = false;
 
                 // Check RFD batching
-                let rfd_enabled_guard = match rfd_enabled.lock() {
-                    Ok(guard) => guard,
-                    Err(_) => {
-                        println!("RFD enabled mutex poisoned, skipping batch");
-                        continue;
-                    }
-                };
-                let rfd_status_guard = match rfd_status.lock() {
-                    Ok(guard) => guard,
-                    Err(_) => {
-                        println!("RFD status mutex poisoned, skipping batch");
-                        continue;
-                    }
-                };
+                let rfd_enabled_guard = lock_recovering(&rfd_enabled, "RFD enabled");
+                let rfd_status_guard = lock_recovering(&rfd_status, "RFD status");
                 
                 if *rfd_enabled_guard && rfd_status_guard.connected {
+                    lock_lane(&rfd_batcher).adapt_timeout(&rfd_status_guard);
                     drop(rfd_enabled_guard);
                     drop(rfd_status_guard);
-                    
-                    let should_send = {
-                        match rfd_batcher.lock() {
-                            Ok(batcher) => batcher.should_send(),
-                            Err(_) => {
-                                println!("RFD batcher mutex poisoned, skipping batch");
-                                continue;
-                            }
-                        }
-                    };
-
-                    if should_send {
-                        let batch_data = {
-                            match rfd_batcher.lock() {
-                                Ok(mut batcher) => batcher.create_batch(),
-                                Err(_) => {
-                                    println!("RFD batcher mutex poisoned, skipping batch");
-                                    continue;
-                                }
+
+                    // Service the express lane first so Critical/High frames never
+                    // wait behind routine telemetry
+                    if Self::send_ready_batch(&rfd_express_batcher, &rfd_connection, &rfd_status) {
+                        sent_batch = true;
+                        rfd_batch_count += 1;
+                        rfd_express_count += 1;
+                    }
+
+                    if Self::send_ready_batch(&rfd_batcher, &rfd_connection, &rfd_status) {
+                        sent_batch = true;
+                        rfd_batch_count += 1;
+                    }
+
+                    if last_probe.elapsed() >= Duration::from_millis(RTT_PROBE_INTERVAL_MS) {
+                        let mode = *lock_recovering(&checksum_mode, "checksum mode");
+                        Self::send_rtt_probe(&rfd_connection, &rfd_status, probe_epoch, mode);
+                        last_probe = Instant::now();
+                    }
+
+                    // The receive thread, when running, reads the port and handles echoes
+                    // and inject requests
+                    if !*lock_recovering(&receiving, "receiving") {
+                        for frame in reader.poll(&rfd_connection, &rfd_status) {
+                            if !Self::record_probe_echo(&rfd_status, &frame, probe_epoch)
+                                && !Self::route_inject_request(&inject_sender, &frame)
+                            {
+                                lock_recovering(&rx_sequence, "RX sequence").record(&frame);
                             }
-                        };
-
-                        if !batch_data.is_empty() {
-                            Self::send_rfd_batch_improved(
-                                &rfd_connection,
-                                &rfd_status,
-                                &batch_data,
-                            );
-                            sent_batch = true;
-                            rfd_batch_count += 1;
                         }
                     }
                 }
 
+                // LoRa batches go out independently of the RFD link's state
+                let lora_active = *lock_recovering(&lora_enabled, "LoRa enabled")
+                    && lock_recovering(&lora_status, "LoRa status").connected;
+                if lora_active {
+                    let status = lock_recovering(&lora_status, "LoRa status");
+                    lock_lane(&lora_batcher).adapt_timeout(&status);
+                    drop(status);
+                    if Self::send_ready_batch(&lora_batcher, &lora_connection, &lora_status) {
+                        sent_batch = true;
+                        lora_batch_count += 1;
+                    }
+                }
+
                 // Print stats every 10 seconds
                 if last_stats.elapsed().as_secs() >= 10 {
-                    let rfd_queue = match rfd_batcher.lock() {
-                        Ok(batcher) => batcher.get_queue_size(),
-                        Err(_) => 0,
-                    };
-
-                    println!(
-                        "Batch stats (10s): RFD: {} batches ({} queued)",
-                        rfd_batch_count, rfd_queue
+                    let rfd_queue = [&rfd_batcher, &rfd_express_batcher]
+                        .iter()
+                        .map(|lane| lock_lane(lane).get_queue_size())
+                        .sum::<usize>();
+
+                    debug!(
+                        "Batch stats (10s): RFD: {} batches ({} express, {} queued)",
+                        rfd_batch_count, rfd_express_count, rfd_queue
                     );
+                    if *lock_recovering(&lora_enabled, "LoRa enabled") {
+                        debug!("Batch stats (10s): LoRa: {} batches", lora_batch_count);
+                    }
                     rfd_batch_count = 0;
+                    rfd_express_count = 0;
+                    lora_batch_count = 0;
                     last_stats = Instant::now();
                 }
 
@@ -996,31 +2129,236 @@ impl SerialManager {
                 }
             }
 
-            println!("Enhanced batch thread stopped");
+            debug!("Enhanced batch thread stopped");
         });
 
         self.batch_thread = Some(batch_thread);
         Ok(())
     }
 
-    // List available serial ports
-    pub fn list_available_ports() -> Vec<String> {
-        match serialport::available_ports() {
-            Ok(ports) => {
-                ports
-                    .iter()
-                    .filter_map(|port| {
-                        // Filter for USB serial devices if possible
-                        match &port.port_type {
-                            SerialPortType::UsbPort(_) => Some(port.port_name.clone()),
-                            _ => Some(port.port_name.clone()), // Include all ports for now
-                        }
-                    })
-                    .collect()
+    // Stop the batch thread and wait for it to exit (frames still queued are not sent)
+    pub fn stop_batching(&mut self) {
+        if let Ok(mut running) = self.batch_running.lock() {
+            *running = false;
+        }
+
+        if let Some(thread) = self.batch_thread.take() {
+            let _ = thread.join();
+        }
+    }
+
+    // Stop every thread, sending whatever is still queued as final batches (express first)
+    pub fn shutdown(&mut self) {
+        self.stop_background_scanning();
+        self.stop_batching();
+        self.force_send_batch(
+            &self.rfd_express_batcher,
+            &self.rfd_connection,
+            &self.rfd_status,
+            true,
+        );
+        self.force_send_batch(
+            &self.rfd_batcher,
+            &self.rfd_connection,
+            &self.rfd_status,
+            true,
+        );
+        self.force_send_batch(
+            &self.lora_batcher,
+            &self.lora_connection,
+            &self.lora_status,
+            true,
+        );
+        self.stop_receiving();
+    }
+
+    // Create and send a batch from `lane` if it is ready; returns true if a batch went out
+    fn send_ready_batch(
+        lane: &Arc<Mutex<ImprovedFrameBatcher>>,
+        connection: &Arc<Mutex<ModemConnection>>,
+        status: &Arc<Mutex<ModemStatus>>,
+    ) -> bool {
+        let batch_data = {
+            let mut batcher = lock_lane(lane);
+            if !batcher.should_send() {
+                return false;
+            }
+            batcher.create_batch()
+        };
+
+        if batch_data.is_empty() {
+            return false;
+        }
+
+        Self::send_rfd_batch_improved(connection, status, &batch_data);
+        true
+    }
+
+    // Send a probe stamped with the time since `epoch`; it bypasses the batchers so
+    // queueing delay doesn't count toward the measured round trip
+    fn send_rtt_probe(
+        connection: &Arc<Mutex<ModemConnection>>,
+        status: &Arc<Mutex<ModemStatus>>,
+        epoch: Instant,
+        mode: ChecksumMode,
+    ) {
+        let sent_us = epoch.elapsed().as_micros() as u64;
+        let probe = CanFrameData::new_with_format(RTT_PROBE_ID, &sent_us.to_be_bytes(), true);
+        let batch = encode_batch(&[probe], mode);
+        Self::send_rfd_batch_improved(connection, status, &batch);
+        if let Ok(mut status) = status.lock() {
+            status.probes_sent += 1;
+        }
+    }
+
+    // Record the round trip of an echoed probe; returns false for any other frame
+    fn record_probe_echo(
+        status: &Arc<Mutex<ModemStatus>>,
+        frame: &CanFrameData,
+        epoch: Instant,
+    ) -> bool {
+        if frame.id != RTT_PROBE_ID || !frame.is_extended || frame.data.len() != 8 {
+            return false;
+        }
+        let mut sent = [0u8; 8];
+        sent.copy_from_slice(&frame.data);
+        let sent_us = u64::from_be_bytes(sent);
+        let now_us = epoch.elapsed().as_micros() as u64;
+        if sent_us > now_us {
+            return true; // Echo of a probe from a previous run
+        }
+
+        if let Ok(mut status) = status.lock() {
+            status.last_rtt = Some(Duration::from_micros(now_us - sent_us));
+            status.probes_echoed += 1;
+        }
+        true
+    }
+
+    // Hand an inject request to accept_inject_requests' channel; returns false for any
+    // other frame. Requests are dropped while nothing accepts them.
+    fn route_inject_request(
+        inject_sender: &Arc<Mutex<Option<mpsc::Sender<InjectRequest>>>>,
+        frame: &CanFrameData,
+    ) -> bool {
+        let Some(request) = InjectRequest::from_frame(frame) else {
+            return false;
+        };
+        let mut sender = lock_recovering(inject_sender, "inject sender");
+        match sender.as_ref() {
+            Some(tx) => {
+                if tx.send(request).is_err() {
+                    *sender = None; // Receiver dropped
+                }
+            }
+            None => warn!(
+                "Dropping CAN inject request for 0x{:X} (injection disabled)",
+                request.id
+            ),
+        }
+        true
+    }
+
+    // Receive CAN inject requests arriving over the RFD link (car side). Until this is called
+    // they are dropped; calling it again replaces the previous channel.
+    pub fn accept_inject_requests(&self) -> mpsc::Receiver<InjectRequest> {
+        let (sender, receiver) = mpsc::channel();
+        *lock_recovering(&self.inject_sender, "inject sender") = Some(sender);
+        receiver
+    }
+
+    // Start a thread that reads frames arriving over the RFD link (e.g. on a base station).
+    // Echoed RTT probes and inject requests are consumed; every other frame is sent to the
+    // returned channel.
+    // Stops when the receiver is dropped or stop_receiving is called.
+    pub fn start_receiving(&mut self) -> Result<mpsc::Receiver<CanFrameData>, SerialError> {
+        let mut receiving = lock_recovering(&self.receiving, "receiving");
+        if *receiving {
+            return Err(SerialError::AlreadyRunning("RFD receive thread"));
+        }
+        *receiving = true;
+
+        let (sender, receiver) = mpsc::channel();
+        let rfd_connection = Arc::clone(&self.rfd_connection);
+        let rfd_status = Arc::clone(&self.rfd_status);
+        let receiving = Arc::clone(&self.receiving);
+        let probe_epoch = self.probe_epoch;
+        let inject_sender = Arc::clone(&self.inject_sender);
+        let rx_sequence = Arc::clone(&self.rx_sequence);
+
+        let rx_thread = thread::spawn(move || {
+            let mut reader = PortReader::new();
+            debug!("RFD receive thread started");
+
+            while *lock_recovering(&receiving, "receiving") {
+                let frames = reader.poll(&rfd_connection, &rfd_status);
+                if frames.is_empty() {
+                    thread::sleep(Duration::from_millis(5));
+                    continue;
+                }
+
+                for frame in frames {
+                    if Self::record_probe_echo(&rfd_status, &frame, probe_epoch)
+                        || Self::route_inject_request(&inject_sender, &frame)
+                    {
+                        continue;
+                    }
+                    lock_recovering(&rx_sequence, "RX sequence").record(&frame);
+                    if sender.send(frame).is_err() {
+                        *lock_recovering(&receiving, "receiving") = false; // Receiver dropped
+                        break;
+                    }
+                }
             }
-            Err(_) => Vec::new(),
+
+            debug!("RFD receive thread stopped");
+        });
+
+        self.rx_thread = Some(rx_thread);
+        Ok(receiver)
+    }
+
+    // Fraction of frames lost on the way in, from sequence gaps in frames read off the RFD port
+    // (by the receive thread or, while it isn't running, the batch thread)
+    pub fn packet_loss_estimate(&self) -> f64 {
+        lock_recovering(&self.rx_sequence, "RX sequence").packet_loss_estimate()
+    }
+
+    pub fn stop_receiving(&mut self) {
+        if let Ok(mut receiving) = self.receiving.lock() {
+            *receiving = false;
+        }
+
+        if let Some(thread) = self.rx_thread.take() {
+            let _ = thread.join();
         }
     }
+
+    // List serial ports worth probing for a modem: known USB adapters only, unless
+    // `all_ports` (then non-USB and unknown USB devices too). An error here usually means
+    // missing permissions, not that nothing is plugged in.
+    pub fn list_available_ports(all_ports: bool) -> Result<Vec<String>, SerialError> {
+        match serialport::available_ports() {
+            Ok(ports) => Ok(ports
+                .into_iter()
+                .filter(|port| all_ports || Self::is_known_modem_port(&port.port_type))
+                .map(|port| port.port_name)
+                .collect()),
+            Err(e) => Err(SerialError::PortEnumeration(e)),
+        }
+    }
+
+    fn is_known_modem_port(port_type: &SerialPortType) -> bool {
+        match port_type {
+            SerialPortType::UsbPort(usb) => KNOWN_MODEM_USB_IDS.contains(&(usb.vid, usb.pid)),
+            _ => false,
+        }
+    }
+
+    // Probe every serial port while scanning, not just known USB modem adapters
+    pub fn set_scan_all_ports(&self, all_ports: bool) {
+        *self.scan_all_ports.lock().unwrap() = all_ports;
+    }
 }
 
 impl Clone for SerialManager {
@@ -1032,8 +2370,195 @@ impl Clone for SerialManager {
             scan_running: Arc::clone(&self.scan_running),
             rfd_enabled: Arc::clone(&self.rfd_enabled),
             rfd_batcher: Arc::clone(&self.rfd_batcher),
+            rfd_express_batcher: Arc::clone(&self.rfd_express_batcher),
             batch_thread: None, // Don't clone the thread
+            batch_running: Arc::clone(&self.batch_running),
             batching_enabled: Arc::clone(&self.batching_enabled),
+            checksum_mode: Arc::clone(&self.checksum_mode),
+            priority_overrides: Arc::clone(&self.priority_overrides),
+            scan_heartbeat: self.scan_heartbeat.clone(),
+            tx_samples: Arc::clone(&self.tx_samples),
+            batch_heartbeat: self.batch_heartbeat.clone(),
+            rx_thread: None, // Don't clone the thread
+            receiving: Arc::clone(&self.receiving),
+            probe_epoch: self.probe_epoch,
+            rx_sequence: Arc::clone(&self.rx_sequence),
+            lora_connection: Arc::clone(&self.lora_connection),
+            lora_status: Arc::clone(&self.lora_status),
+            lora_enabled: Arc::clone(&self.lora_enabled),
+            lora_batcher: Arc::clone(&self.lora_batcher),
+            rfd_baud_rate: Arc::clone(&self.rfd_baud_rate),
+            rfd_port: Arc::clone(&self.rfd_port),
+            rfd_auto_scan: Arc::clone(&self.rfd_auto_scan),
+            scan_all_ports: Arc::clone(&self.scan_all_ports),
+            inject_sender: Arc::clone(&self.inject_sender),
+        }
+    }
+}
+
+// Clones share every flag but no JoinHandle, so only the manager that started a thread stops
+// it; dropping a clone leaves the threads running.
+impl Drop for SerialManager {
+    fn drop(&mut self) {
+        if self.scan_thread.is_some() {
+            self.stop_background_scanning();
+        }
+        if self.batch_thread.is_some() {
+            self.stop_batching();
+        }
+        if self.rx_thread.is_some() {
+            self.stop_receiving();
+        }
+    }
+}
+
+// Reads the modem through its own cloned handle, so receiving never holds the connection
+// lock the transmit path try_locks (a busy port makes it skip batches)
+struct PortReader {
+    port: Option<Box<dyn SerialPort>>,
+    generation: u32, // ModemStatus::connections when the handle was cloned
+    buffer: Vec<u8>, // Bytes of a batch still being received
+}
+
+impl PortReader {
+    fn new() -> Self {
+        Self {
+            port: None,
+            generation: 0,
+            buffer: Vec::new(),
+        }
+    }
+
+    // Read whatever has arrived and return the frames of every batch completed so far
+    fn poll(
+        &mut self,
+        connection: &Arc<Mutex<ModemConnection>>,
+        status: &Arc<Mutex<ModemStatus>>,
+    ) -> Vec<CanFrameData> {
+        let (connected, generation) = match status.lock() {
+            Ok(status) => (status.connected, status.connections),
+            Err(_) => return Vec::new(),
+        };
+        if !connected {
+            self.port = None;
+            return Vec::new();
+        }
+
+        // Re-clone after every reconnect so we never read a stale handle
+        if self.port.is_none() || self.generation != generation {
+            self.port = connection
+                .lock()
+                .ok()
+                .and_then(|conn| conn.port.as_ref().and_then(|port| port.try_clone().ok()));
+            self.generation = generation;
+            self.buffer.clear();
+        }
+        let Some(port) = self.port.as_mut() else {
+            return Vec::new();
+        };
+
+        let pending = port.bytes_to_read().unwrap_or(0) as usize;
+        if pending == 0 {
+            return Vec::new();
+        }
+        let mut chunk = vec![0u8; pending.min(RX_BUFFER_LIMIT)];
+        match port.read(&mut chunk) {
+            Ok(read) => self.buffer.extend_from_slice(&chunk[..read]),
+            Err(e) => {
+                warn!("RFD read error: {}", e);
+                self.port = None;
+                return Vec::new();
+            }
+        }
+
+        drain_received_frames(&mut self.buffer)
+    }
+}
+
+// Pull every complete batch out of a receive buffer, discarding noise before start markers.
+// A trailing partial batch stays in the buffer for the next read.
+fn drain_received_frames(buffer: &mut Vec<u8>) -> Vec<CanFrameData> {
+    let find = |haystack: &[u8], marker: &[u8]| {
+        haystack
+            .windows(marker.len())
+            .position(|window| window == marker)
+    };
+    let mut frames = Vec::new();
+
+    loop {
+        let Some(start) = find(buffer, FRAME_START) else {
+            // Keep just enough of the tail to hold a start marker split across reads
+            let keep = buffer.len().min(FRAME_START.len() - 1);
+            buffer.drain(..buffer.len() - keep);
+            break;
+        };
+        buffer.drain(..start);
+
+        // Payload bytes can contain the end marker, so try each one until the CRC passes
+        let mut consumed = None;
+        let crc_len = ChecksumMode::of_batch(buffer).map_or(usize::MAX, ChecksumMode::crc_len);
+        let mut search = FRAME_START.len();
+        while let Some(pos) = find(&buffer[search..], FRAME_END) {
+            let end = (search + pos + FRAME_END.len()).saturating_add(crc_len);
+            if end > buffer.len() {
+                break;
+            }
+            let batch = parse_can_batch(&buffer[..end]);
+            if !batch.is_empty() {
+                frames.extend(batch);
+                consumed = Some(end);
+                break;
+            }
+            search += pos + 1;
+        }
+
+        match consumed {
+            Some(end) => {
+                buffer.drain(..end);
+            }
+            // A later start marker means this batch was corrupted rather than incomplete
+            None if find(&buffer[1..], FRAME_START).is_some() || buffer.len() > RX_BUFFER_LIMIT => {
+                buffer.drain(..1);
+            }
+            None => break,
+        }
+    }
+
+    frames
+}
+
+// Receive-side link quality: per CAN ID, counts sequence numbers skipped between frames.
+// Senders number frames per ID as they are batched, so a skipped number is a frame the
+// link lost (frames sent with batching disabled are not numbered this way).
+#[derive(Debug, Default)]
+pub struct SequenceTracker {
+    last_seen: HashMap<u32, u64>,
+    received: u64,
+    missing: u64,
+}
+
+impl SequenceTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, frame: &CanFrameData) {
+        self.received += 1;
+        if let Some(last) = self.last_seen.insert(frame.id, frame.sequence_number) {
+            // A number at or below the last one means the sender restarted; start over
+            if frame.sequence_number > last {
+                self.missing += frame.sequence_number - last - 1;
+            }
+        }
+    }
+
+    // Fraction (0.0-1.0) of frames sent that never arrived
+    pub fn packet_loss_estimate(&self) -> f64 {
+        let expected = self.received + self.missing;
+        if expected == 0 {
+            0.0
+        } else {
+            self.missing as f64 / expected as f64
         }
     }
 }
@@ -1048,14 +2573,20 @@ pub fn parse_can_batch(batch_data: &[u8]) -> Vec<CanFrameData> {
 
     // Check for start marker
     if &batch_data[0..FRAME_START.len()] != FRAME_START {
-        println!("Invalid start marker in batch");
+        warn!("Invalid start marker in batch");
         return frames;
     }
 
-    // Find end marker position (should be before the CRC32)
-    let expected_end_pos = batch_data.len().saturating_sub(4 + FRAME_END.len()); // CRC32 + end marker
+    // The header says whether the frames and the batch carry CRC32 or CRC16
+    let Some(mode) = ChecksumMode::of_batch(batch_data) else {
+        return frames;
+    };
+    let crc_len = mode.crc_len();
+
+    // Find end marker position (should be before the CRC)
+    let expected_end_pos = batch_data.len().saturating_sub(crc_len + FRAME_END.len()); // CRC + end marker
     if expected_end_pos >= batch_data.len() || &batch_data[expected_end_pos..expected_end_pos + FRAME_END.len()] != FRAME_END {
-        println!("Invalid end marker in batch");
+        warn!("Invalid end marker in batch");
         return frames;
     }
 
@@ -1068,41 +2599,74 @@ pub fn parse_can_batch(batch_data: &[u8]) -> Vec<CanFrameData> {
         return frames; // Need at least frame count
     }
 
-    // Verify CRC32 of the payload
-    let mut hasher = Hasher::new();
-    hasher.update(payload);
-    let expected_crc = hasher.finalize();
+    // Verify CRC of the payload
+    let expected_crc = mode.checksum(payload);
     
     let crc_start = expected_end_pos + FRAME_END.len();
-    if crc_start + 4 > batch_data.len() {
-        println!("Batch too short for CRC32");
+    if crc_start + crc_len > batch_data.len() {
+        warn!("Batch too short for {:?}", mode);
         return frames;
     }
     
-    let actual_crc = u32::from_be_bytes([
-        batch_data[crc_start],
-        batch_data[crc_start + 1],
-        batch_data[crc_start + 2],
-        batch_data[crc_start + 3],
-    ]);
+    let actual_crc = mode.read_checksum(&batch_data[crc_start..]);
     
     if actual_crc != expected_crc {
-        println!("Batch CRC32 validation failed: expected 0x{:X}, got 0x{:X}", 
-                 expected_crc, actual_crc);
+        warn!(
+            "Batch {:?} validation failed: expected 0x{:X}, got 0x{:X}",
+            mode, expected_crc, actual_crc
+        );
+        return frames;
+    }
+
+    let header = u16::from_be_bytes([payload[0], payload[1]]);
+    let timestamped = header & TIMESTAMPED_BATCH_FLAG != 0;
+    let (fixed_len, per_frame_len) = if timestamped {
+        (2 + BATCH_TIME_LEN, FRAME_AGE_LEN + mode.min_frame_len())
+    } else {
+        (2, mode.min_frame_len())
+    };
+    if payload.len() < fixed_len {
+        warn!("Batch too short for its timestamp header");
         return frames;
     }
 
-    let frame_count = u16::from_be_bytes([payload[0], payload[1]]) as usize;
-    let mut offset = 2; // Skip frame count
+    // A corrupted or future-format count can't claim more frames than the payload can hold
+    let declared_count = (header & !(CRC16_BATCH_FLAG | TIMESTAMPED_BATCH_FLAG)) as usize;
+    let frame_count = declared_count.min((payload.len() - fixed_len) / per_frame_len);
+    if frame_count < declared_count {
+        warn!(
+            "Batch declares {} frames but payload holds at most {}",
+            declared_count, frame_count
+        );
+    }
+
+    // Capture time of each frame: batch creation minus the frame's age
+    let mut capture_times = Vec::new();
+    let mut offset = fixed_len;
+    if timestamped {
+        let ages_end = fixed_len + FRAME_AGE_LEN * declared_count;
+        if ages_end > payload.len() {
+            return frames; // Ages were cut short, so the frames can't be located
+        }
+        let mut created_ms = [0u8; BATCH_TIME_LEN];
+        created_ms.copy_from_slice(&payload[2..fixed_len]);
+        let created_ms = u64::from_be_bytes(created_ms);
+        capture_times = payload[fixed_len..ages_end]
+            .chunks_exact(FRAME_AGE_LEN)
+            .map(|age| created_ms.saturating_sub(u16::from_be_bytes([age[0], age[1]]) as u64))
+            .collect();
+        offset = ages_end;
+    }
 
-    for _ in 0..frame_count {
+    for index in 0..frame_count {
         if offset >= payload.len() {
             break;
         }
 
-        // Find the end of this frame (17 + data_len bytes with CRC32)
-        if let Some(frame) = CanFrameData::from_bytes(&payload[offset..]) {
-            let frame_size = 17 + frame.data.len(); // Updated for CRC32 format
+        // Find the end of this frame (13 + data_len bytes plus the CRC)
+        if let Some(mut frame) = CanFrameData::from_bytes(&payload[offset..], mode) {
+            let frame_size = frame.encoded_len(mode);
+            frame.captured_at_ms = capture_times.get(index).copied();
             frames.push(frame);
             offset += frame_size;
         } else {
@@ -1120,16 +2684,226 @@ mod tests {
     #[test]
     fn test_can_frame_serialization() {
         let frame = CanFrameData::new(0x123, &[0xAA, 0xBB, 0xCC]);
-        let bytes = frame.to_bytes();
-        let parsed = CanFrameData::from_bytes(&bytes).unwrap();
+        let bytes = frame.to_bytes(ChecksumMode::Crc32);
+        let parsed = CanFrameData::from_bytes(&bytes, ChecksumMode::Crc32).unwrap();
 
         assert_eq!(frame.id, parsed.id);
         assert_eq!(frame.data, parsed.data);
     }
 
+    #[test]
+    fn test_frame_format_survives_serialization() {
+        // A low extended ID must not come back as a standard frame (and vice versa)
+        let extended = CanFrameData::new_with_format(0x123, &[0x01], true);
+        let bytes = extended.to_bytes(ChecksumMode::Crc32);
+        let parsed = CanFrameData::from_bytes(&bytes, ChecksumMode::Crc32).unwrap();
+        assert_eq!(parsed.id, 0x123);
+        assert!(parsed.is_extended);
+
+        let standard = CanFrameData::new_with_format(0x7FF, &[0x02], false);
+        let bytes = standard.to_bytes(ChecksumMode::Crc32);
+        let parsed = CanFrameData::from_bytes(&bytes, ChecksumMode::Crc32).unwrap();
+        assert_eq!(parsed.id, 0x7FF);
+        assert!(!parsed.is_extended);
+    }
+
+    #[test]
+    fn test_individual_frame_uses_batch_framing() {
+        let frame = CanFrameData::new(0x321, &[9, 8, 7]);
+        let encoded = encode_batch(std::slice::from_ref(&frame), ChecksumMode::Crc32);
+
+        let parsed = parse_can_batch(&encoded);
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].id, frame.id);
+        assert_eq!(parsed[0].data, frame.data);
+
+        // A write cut short by a timeout must not parse as a valid frame
+        assert!(parse_can_batch(&encoded[..encoded.len() - 3]).is_empty());
+    }
+
+    #[test]
+    fn test_crc16_framing() {
+        let frames = [
+            CanFrameData::new(0x123, &[1, 2, 3]),
+            CanFrameData::new(0x0CF11E05, &[4; 8]),
+        ];
+        let crc32 = encode_batch(&frames, ChecksumMode::Crc32);
+        let crc16 = encode_batch(&frames, ChecksumMode::Crc16);
+        // 2 bytes saved per frame plus 2 on the batch CRC
+        assert_eq!(crc32.len() - crc16.len(), 2 * frames.len() + 2);
+
+        // The receiver picks the width from the header, whichever mode it runs itself
+        for batch in [&crc32, &crc16] {
+            let parsed = parse_can_batch(batch);
+            assert_eq!(parsed.len(), 2);
+            assert_eq!(parsed[1].id, 0x0CF11E05);
+            assert_eq!(parsed[1].data, vec![4; 8]);
+        }
+
+        let mut buffer = crc16.clone();
+        buffer.extend_from_slice(&crc32);
+        assert_eq!(drain_received_frames(&mut buffer).len(), 4);
+
+        let mut corrupted = crc16.clone();
+        corrupted[10] ^= 0x01;
+        assert!(parse_can_batch(&corrupted).is_empty());
+
+        let frame = &frames[0];
+        let bytes = frame.to_bytes(ChecksumMode::Crc16);
+        assert_eq!(bytes.len(), frame.encoded_len(ChecksumMode::Crc16));
+        assert!(CanFrameData::from_bytes(&bytes, ChecksumMode::Crc16).is_some());
+        assert!(CanFrameData::from_bytes(&bytes, ChecksumMode::Crc32).is_none());
+
+        // A lane set to CRC16 frames its batches that way
+        let mut batcher = ImprovedFrameBatcher::new(BatchConfig::default());
+        batcher.set_checksum_mode(ChecksumMode::Crc16);
+        assert!(batcher.add_frame(CanFrameData::new(0x123, &[1, 2, 3])));
+        let batch = batcher.create_batch();
+        assert_eq!(ChecksumMode::of_batch(&batch), Some(ChecksumMode::Crc16));
+    }
+
+    #[test]
+    fn test_batch_carries_capture_times() {
+        let mut old = CanFrameData::new(0x123, &[1]);
+        old.timestamp -= Duration::from_millis(250);
+        let fresh = CanFrameData::new(0x124, &[2]);
+
+        let before_ms = now_ms();
+        let parsed = parse_can_batch(&encode_batch(&[old, fresh], ChecksumMode::Crc16));
+        let after_ms = now_ms();
+
+        // Ages are whole milliseconds, hence the slack below the window
+        let captured: Vec<u64> = parsed.iter().map(|f| f.captured_at_ms.unwrap()).collect();
+        assert!((before_ms - 260..=after_ms - 250).contains(&captured[0]));
+        assert!((before_ms - 10..=after_ms).contains(&captured[1]));
+
+        // Locally created frames carry no capture time
+        assert!(CanFrameData::new(0x125, &[3]).captured_at_ms.is_none());
+    }
+
+    #[test]
+    fn test_absurd_frame_count_is_capped() {
+        let frame = CanFrameData::new(0x321, &[1, 2]);
+        let mut payload = vec![0x3F, 0xFF]; // Declares 16383 frames, no flags (CRC32, untimestamped)
+        payload.extend_from_slice(&frame.to_bytes(ChecksumMode::Crc32));
+
+        let mut batch = FRAME_START.to_vec();
+        batch.extend_from_slice(&payload);
+        batch.extend_from_slice(FRAME_END);
+        let mut hasher = Hasher::new();
+        hasher.update(&payload);
+        batch.extend_from_slice(&hasher.finalize().to_be_bytes());
+
+        let parsed = parse_can_batch(&batch);
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].id, 0x321);
+        assert!(parsed[0].captured_at_ms.is_none());
+    }
+
+    #[test]
+    fn test_drain_received_frames() {
+        let probe = CanFrameData::new(RTT_PROBE_ID, &42u64.to_be_bytes());
+        let batch = encode_batch(&[probe], ChecksumMode::Crc32);
+
+        // Noise, one complete batch, then the first half of another
+        let mut buffer = vec![0x00, 0xAA, 0x13];
+        buffer.extend_from_slice(&batch);
+        buffer.extend_from_slice(&batch[..10]);
+
+        let frames = drain_received_frames(&mut buffer);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].id, RTT_PROBE_ID);
+        assert!(frames[0].is_extended);
+        assert_eq!(buffer, &batch[..10]);
+
+        // The rest arrives on the next read
+        buffer.extend_from_slice(&batch[10..]);
+        assert_eq!(drain_received_frames(&mut buffer).len(), 1);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_sequence_gaps_count_lost_frames() {
+        // Batched frames are numbered per ID, so consecutive batches leave no gaps
+        let mut batcher = ImprovedFrameBatcher::new(BatchConfig::default());
+        let mut tracker = SequenceTracker::new();
+        let mut batches = Vec::new();
+        for _ in 0..4 {
+            assert!(batcher.add_frame(CanFrameData::new(0x123, &[0x01])));
+            batches.push(batcher.create_batch());
+            thread::sleep(Duration::from_millis(15)); // Past the default rate filter
+        }
+
+        // The link drops the third batch
+        batches.remove(2);
+        for frame in batches.iter().flat_map(|batch| parse_can_batch(batch)) {
+            tracker.record(&frame);
+        }
+        assert_eq!(tracker.missing, 1);
+        assert_eq!(tracker.packet_loss_estimate(), 0.25);
+    }
+
+    #[test]
+    fn test_link_simulator_loss_and_corruption() {
+        let frame = CanFrameData::new(0x100, &[1, 2, 3, 4]);
+        let batch = encode_batch(&[frame], ChecksumMode::Crc32);
+
+        let mut lossy = LinkSimulator::new(1.0, 0.0, 7);
+        assert!(lossy.apply(&batch).is_none());
+
+        // Heavy corruption must be caught by the batch CRC
+        let mut noisy = LinkSimulator::new(0.0, 0.5, 7);
+        let corrupted = noisy.apply(&batch).unwrap();
+        assert_ne!(corrupted, batch);
+        assert!(parse_can_batch(&corrupted).is_empty());
+
+        // Same seed, same impairments
+        let mut a = LinkSimulator::new(0.3, 0.05, 42);
+        let mut b = LinkSimulator::new(0.3, 0.05, 42);
+        for _ in 0..20 {
+            assert_eq!(a.apply(&batch), b.apply(&batch));
+        }
+    }
+
+    #[test]
+    fn test_priority_override_promotes_to_express_lane() {
+        assert_eq!(
+            CanFrameData::new(0x555, &[1]).priority,
+            MessagePriority::Medium
+        );
+
+        let manager = SerialManager::new();
+        manager.set_priority_overrides(HashMap::from([(0x555, MessagePriority::Critical)]));
+
+        let frame = manager.outgoing_frame(0x555, false, &[1]);
+        assert_eq!(frame.priority, MessagePriority::Critical);
+        assert!(Arc::ptr_eq(
+            manager.batcher_for(frame.priority),
+            &manager.rfd_express_batcher
+        ));
+    }
+
+    #[test]
+    fn test_baud_rate_change_forces_reconnect() {
+        let manager = SerialManager::new();
+        {
+            let mut status = manager.rfd_status.lock().unwrap();
+            status.connected = true;
+            status.port_name = Some("/dev/ttyUSB0".to_string());
+        }
+
+        // Same rate keeps the link, a new rate drops it for the scanner to reopen
+        manager.set_rfd_baud_rate(DEFAULT_RFD_BAUD_RATE);
+        assert!(manager.rfd_status.lock().unwrap().connected);
+
+        manager.set_rfd_baud_rate(115200);
+        assert!(!manager.rfd_status.lock().unwrap().connected);
+        assert_eq!(*manager.rfd_baud_rate.lock().unwrap(), 115200);
+    }
+
     #[test]
     fn test_enhanced_batching() {
-        let mut batcher = ImprovedFrameBatcher::new();
+        let mut batcher = ImprovedFrameBatcher::new(BatchConfig::default());
 
         // Add some frames
         let frame1 = CanFrameData::new(0x100, &[1, 2, 3, 4]);
@@ -1150,6 +2924,337 @@ mod tests {
         assert_eq!(parsed_frames[1].id, 0x200);
     }
 
+    #[test]
+    fn test_preserved_ids_are_not_coalesced() {
+        let mut batcher = ImprovedFrameBatcher::new(BatchConfig::default());
+        batcher.set_preserve_ids(HashSet::from([0x7A1]), DEFAULT_PRESERVED_PER_ID);
+
+        // Spaced past the per-ID rate filter's 10 ms default
+        for value in 1..=3 {
+            assert!(batcher.add_frame(CanFrameData::new(0x7A1, &[value])));
+            assert!(batcher.add_frame(CanFrameData::new(0x7A2, &[value])));
+            thread::sleep(Duration::from_millis(11));
+        }
+        assert_eq!(batcher.get_queue_size(), 4);
+
+        let parsed = parse_can_batch(&batcher.create_batch());
+        let preserved: Vec<u8> = parsed
+            .iter()
+            .filter(|frame| frame.id == 0x7A1)
+            .map(|frame| frame.data[0])
+            .collect();
+        assert_eq!(preserved, [1, 2, 3]);
+        let coalesced: Vec<&CanFrameData> =
+            parsed.iter().filter(|frame| frame.id == 0x7A2).collect();
+        assert_eq!(coalesced.len(), 1);
+        assert_eq!(coalesced[0].data, [3]);
+        assert!(batcher.is_empty());
+
+        // Past the per-ID depth the oldest queued frame is dropped, and counted
+        batcher.set_preserve_ids(HashSet::from([0x7A1]), 2);
+        for value in 4..=6 {
+            thread::sleep(Duration::from_millis(11));
+            assert!(batcher.add_frame(CanFrameData::new(0x7A1, &[value])));
+        }
+        assert_eq!(batcher.get_queue_size(), 2);
+        assert_eq!(batcher.preserved_dropped(), 1);
+    }
+
+    #[test]
+    fn test_express_batcher_limits() {
+        let mut express = ImprovedFrameBatcher::new(BatchConfig::express());
+
+        // Fill the express lane to its smaller size limit
+        for id in 0x700..0x700 + EXPRESS_MAX_BATCH_SIZE as u32 {
+            assert!(express.add_frame(CanFrameData::new(id, &[0x01])));
+        }
+        assert!(express.should_send());
+        assert!(!express.add_frame(CanFrameData::new(0x7FF, &[0x01]))); // Lane is full
+
+        let parsed_frames = parse_can_batch(&express.create_batch());
+        assert_eq!(parsed_frames.len(), EXPRESS_MAX_BATCH_SIZE);
+        assert!(express.is_empty());
+    }
+
+    #[test]
+    fn test_tx_stats_counters() {
+        let manager = SerialManager::new();
+        {
+            let mut batcher = manager.rfd_batcher.lock().unwrap();
+            assert!(batcher.add_frame(CanFrameData::new(0x124, &[0x01])));
+            assert!(batcher.add_frame(CanFrameData::new(0x124, &[0x02]))); // Rate-filtered
+            let batch = batcher.create_batch();
+            assert_eq!(batcher.bytes_sent(), batch.len() as u64);
+        }
+
+        let stats = manager.get_tx_stats();
+        assert_eq!(stats.batches_sent, 1);
+        assert_eq!(stats.frames_filtered, 1);
+        assert_eq!(manager.get_filtered_counts(), HashMap::from([(0x124, 1)]));
+        assert_eq!(stats.frames_per_sec, 0.0); // Only one sample so far
+    }
+
+    #[test]
+    fn test_batch_config_limits() {
+        assert!(BatchConfig::new(0, 100, 20, 20).is_err());
+        assert!(BatchConfig::new(8, 0, 20, 20).is_err());
+        assert!(BatchConfig::new(100_000, 100, 20, 20).is_err());
+        assert!(BatchConfig::new(8, 100, 50, 20).is_err()); // Ceiling below the timeout
+
+        // A retuned lane fills at its new size
+        let mut batcher = ImprovedFrameBatcher::new(BatchConfig::default());
+        batcher.set_config(BatchConfig::new(2, 1000, 20, 20).unwrap());
+        assert!(batcher.add_frame(CanFrameData::new(0x701, &[0x01])));
+        assert!(batcher.add_frame(CanFrameData::new(0x702, &[0x01])));
+        assert!(batcher.should_send());
+        assert!(!batcher.add_frame(CanFrameData::new(0x703, &[0x01])));
+    }
+
+    #[test]
+    fn test_batch_timeout_adapts_to_link() {
+        let mut batcher = ImprovedFrameBatcher::new(BatchConfig::new(8, 100, 20, 100).unwrap());
+        let mut status = ModemStatus::new();
+
+        // Each new failed write doubles the timeout, up to the ceiling
+        for expected in [40, 80, 100, 100] {
+            status.consecutive_failures += 1;
+            status.write_failures += 1;
+            batcher.adapt_timeout(&status);
+            assert_eq!(batcher.timeout_ms, expected);
+        }
+        batcher.adapt_timeout(&status); // No new writes, no change
+        assert_eq!(batcher.timeout_ms, 100);
+
+        // Successful writes ease it back down to the configured timeout
+        status.consecutive_failures = 0;
+        for _ in 0..20 {
+            status.last_success = Some(Instant::now());
+            batcher.adapt_timeout(&status);
+        }
+        assert_eq!(batcher.timeout_ms, 20);
+    }
+
+    #[test]
+    fn test_overflow_policy_sheds_when_port_busy() {
+        let manager = SerialManager::new();
+        manager
+            .set_batch_config(BatchConfig::new(2, 1000, 20, 20).unwrap())
+            .unwrap();
+        let busy = manager.rfd_connection.lock().unwrap();
+        let queue = |id| {
+            manager.queue_frame(
+                &manager.rfd_batcher,
+                &manager.rfd_connection,
+                &manager.rfd_status,
+                CanFrameData::new(id, &[0x01]),
+            )
+        };
+
+        // The busy port keeps the batch queued, so the oldest frame makes room
+        for id in [0x701, 0x702, 0x703] {
+            queue(id).unwrap();
+        }
+        drop(busy);
+        let ids: Vec<u32> = lock_lane(&manager.rfd_batcher)
+            .latest_frames
+            .keys()
+            .copied()
+            .collect();
+        assert_eq!(ids.len(), 2);
+        assert!(!ids.contains(&0x701));
+        assert_eq!(manager.get_tx_stats().frames_overflowed, 1);
+
+        // Everything queued outranks a Low frame, so the new frame is the one dropped
+        let mut batcher = ImprovedFrameBatcher::new(BatchConfig::new(1, 1000, 20, 20).unwrap());
+        batcher.set_overflow_policy(OverflowPolicy::DropLowestPriority);
+        let mut critical = CanFrameData::new(0x704, &[0x01]);
+        critical.priority = MessagePriority::Critical;
+        let mut low = CanFrameData::new(0x705, &[0x01]);
+        low.priority = MessagePriority::Low;
+        assert!(batcher.add_frame(critical.clone()));
+        assert!(!batcher.shed_for(&low));
+        assert!(batcher.shed_for(&critical));
+        assert!(batcher.is_empty());
+        assert_eq!(batcher.frames_overflowed(), 2);
+
+        assert_eq!(
+            OverflowPolicy::from_name("drop-lowest-priority"),
+            Ok(OverflowPolicy::DropLowestPriority)
+        );
+        assert!(OverflowPolicy::from_name("newest").is_err());
+    }
+
+    #[test]
+    fn test_can_fd_frames() {
+        let payload: Vec<u8> = (0..MAX_FRAME_DATA_LEN as u8).collect();
+        let frame = CanFrameData::new(0x18FF0001, &payload);
+        for mode in [ChecksumMode::Crc32, ChecksumMode::Crc16] {
+            let bytes = frame.to_bytes(mode);
+            assert_eq!(bytes.len(), mode.min_frame_len() + MAX_FRAME_DATA_LEN);
+            let parsed = CanFrameData::from_bytes(&bytes, mode).unwrap();
+            assert_eq!(parsed.data, payload);
+        }
+
+        // Byte accounting uses the full FD length: a 64-byte frame fills a default batch
+        let mut batcher = ImprovedFrameBatcher::new(BatchConfig::default());
+        assert!(batcher.add_frame(frame.clone()));
+        assert!(!batcher.add_frame(CanFrameData::new(0x18FF0002, &payload)));
+        let parsed = parse_can_batch(&batcher.create_batch());
+        assert_eq!(parsed[0].data, payload);
+
+        // A frame bigger than the whole byte limit still gets a batch to itself
+        let mut tight = ImprovedFrameBatcher::new(BatchConfig::new(8, 40, 20, 20).unwrap());
+        assert!(tight.add_frame(frame));
+        assert!(tight.should_send());
+        assert!(!tight.add_frame(CanFrameData::new(0x123, &[1])));
+    }
+
+    #[test]
+    fn test_drop_joins_owned_threads_only() {
+        let mut manager = SerialManager::new();
+        manager.start_batching().unwrap();
+        let running = Arc::clone(&manager.batch_running);
+
+        drop(manager.clone());
+        assert!(*running.lock().unwrap());
+        assert!(!manager.batch_thread.as_ref().unwrap().is_finished());
+
+        drop(manager);
+        assert!(!*running.lock().unwrap());
+    }
+
+    #[test]
+    fn test_shutdown_drains_lanes_and_stops_threads() {
+        let mut manager = SerialManager::new();
+        manager.start_batching().unwrap();
+        lock_lane(&manager.rfd_batcher).add_frame(CanFrameData::new(0x123, &[1, 2]));
+
+        manager.shutdown();
+        assert!(manager.batch_thread.is_none());
+        assert!(!*manager.batch_running.lock().unwrap());
+        assert!(lock_lane(&manager.rfd_batcher).is_empty());
+    }
+
+    #[test]
+    fn test_poisoned_lane_keeps_transmitting() {
+        let lane = Arc::new(Mutex::new(
+            ImprovedFrameBatcher::new(BatchConfig::default()),
+        ));
+        let poisoner = Arc::clone(&lane);
+        let _ = thread::spawn(move || {
+            let mut batcher = poisoner.lock().unwrap();
+            batcher.add_frame(CanFrameData::new(0x111, &[1]));
+            panic!("simulated panic while holding the batcher");
+        })
+        .join();
+        assert!(lane.is_poisoned());
+
+        // The half-finished queue is dropped, the lane itself keeps working
+        assert_eq!(lock_lane(&lane).get_queue_size(), 0);
+        assert!(!lane.is_poisoned());
+        assert!(lock_lane(&lane).add_frame(CanFrameData::new(0x112, &[2])));
+        let parsed = parse_can_batch(&lock_lane(&lane).create_batch());
+        assert_eq!(parsed[0].id, 0x112);
+    }
+
+    #[test]
+    fn test_connect_rfd_port_pins_the_port() {
+        let manager = SerialManager::new();
+        let result = manager.connect_rfd_port("/dev/nonexistent-rfd");
+        assert!(result.is_err());
+        assert!(!manager.rfd_status.lock().unwrap().connected);
+        assert!(matches!(result, Err(SerialError::OpenFailed { .. })));
+        assert_eq!(
+            manager.rfd_status.lock().unwrap().error_message,
+            result.err().map(|e| e.to_string())
+        );
+
+        // The scanner retries this port instead of probing others
+        assert_eq!(
+            manager.rfd_port.lock().unwrap().as_deref(),
+            Some("/dev/nonexistent-rfd")
+        );
+    }
+
+    #[test]
+    fn test_scan_only_known_usb_adapters() {
+        let usb = |vid, pid| {
+            SerialPortType::UsbPort(serialport::UsbPortInfo {
+                vid,
+                pid,
+                serial_number: None,
+                manufacturer: None,
+                product: None,
+            })
+        };
+        let known = SerialManager::is_known_modem_port;
+        assert!(known(&usb(0x0403, 0x6015)));
+        assert!(known(&usb(0x10C4, 0xEA60)));
+        assert!(!known(&usb(0x2341, 0x0043))); // Arduino Uno
+        assert!(!known(&SerialPortType::PciPort));
+        assert!(!known(&SerialPortType::Unknown));
+    }
+
+    #[test]
+    fn test_inject_requests_are_routed() {
+        let request = InjectRequest {
+            id: 0x123,
+            is_extended: true,
+            data: vec![0xDE, 0xAD],
+        };
+        // The RF frame a base station sends to request it
+        let mut payload = (request.id | EXTENDED_ID_FLAG).to_be_bytes().to_vec();
+        payload.extend_from_slice(&request.data);
+        let frame = CanFrameData::new_with_format(CAN_INJECT_ID, &payload, true);
+        let parsed = parse_can_batch(&encode_batch(&[frame], ChecksumMode::Crc32));
+        assert_eq!(InjectRequest::from_frame(&parsed[0]), Some(request.clone()));
+        assert_eq!(
+            InjectRequest::from_frame(&CanFrameData::new(0x123, &[1; 4])),
+            None
+        );
+
+        // Consumed (and dropped) while nothing accepts them, delivered once something does
+        let manager = SerialManager::new();
+        assert!(SerialManager::route_inject_request(
+            &manager.inject_sender,
+            &parsed[0]
+        ));
+        let requests = manager.accept_inject_requests();
+        assert!(SerialManager::route_inject_request(
+            &manager.inject_sender,
+            &parsed[0]
+        ));
+        assert_eq!(requests.try_recv(), Ok(request));
+
+        let other = CanFrameData::new(0x320, &[0; 8]);
+        assert!(!SerialManager::route_inject_request(
+            &manager.inject_sender,
+            &other
+        ));
+    }
+
+    #[test]
+    fn test_batch_size_matches_byte_accounting() {
+        let mut batcher = ImprovedFrameBatcher::new(BatchConfig::default());
+
+        // Fill with full 8-byte frames until the byte limit refuses one
+        let mut accepted = 0;
+        while batcher.add_frame(CanFrameData::new(0x100 + accepted as u32, &[0xAA; 8])) {
+            accepted += 1;
+        }
+        let predicted = batcher.get_total_bytes();
+        assert_eq!(
+            accepted,
+            MAX_BATCH_BYTES / (ChecksumMode::Crc32.min_frame_len() + 8)
+        );
+        assert!(predicted <= MAX_BATCH_BYTES);
+
+        // Markers, frame count, timestamps and batch CRC32 on top of the frames themselves
+        let timestamps = BATCH_TIME_LEN + FRAME_AGE_LEN * accepted;
+        let framing = FRAME_START.len() + 2 + timestamps + FRAME_END.len() + 4;
+        assert_eq!(batcher.create_batch().len(), predicted + framing);
+    }
+
     #[test]
     fn test_frame_filtering() {
         let mut filter = FrameFilter::new();
@@ -0,0 +1,237 @@
+// Running statistics for one session, written as session_summary_<timestamp>.json on clean shutdown
+use crate::gui_modules::FaultSeverity;
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+// Same guard as the odometer: don't integrate across stalls
+const MAX_INTEGRATION_STEP: Duration = Duration::from_secs(1);
+
+#[derive(Serialize, Clone, Copy)]
+pub struct ValueRange {
+    pub min: f64,
+    pub max: f64,
+}
+
+// Extend `range` to include `value` (None until the first sample arrives)
+fn record(range: &mut Option<ValueRange>, value: f64) {
+    match range {
+        Some(range) => {
+            range.min = range.min.min(value);
+            range.max = range.max.max(value);
+        }
+        None => {
+            *range = Some(ValueRange {
+                min: value,
+                max: value,
+            })
+        }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct FaultCounts {
+    pub critical: u32,
+    pub error: u32,
+    pub warning: u32,
+}
+
+// RF link counters read from the SerialManager when the summary is written
+#[derive(Serialize)]
+pub struct LinkSummary {
+    pub frames_received: u64,
+    pub frames_transmitted: u64,
+    pub batches_sent: u64,
+    pub write_failures: u64,
+    pub reconnects: u32,
+}
+
+// Pack energy integrated from power samples. The dashboard counter updates one on each BMS power
+// frame and is reset by the driver at the start of a run; the session summary keeps its own.
+pub struct EnergyMeter {
+    wh: f64,
+    last_update: Option<Instant>,
+}
+
+impl EnergyMeter {
+    pub fn new() -> Self {
+        Self {
+            wh: 0.0,
+            last_update: None,
+        }
+    }
+
+    // Add volts * amps over the time since the previous update (discharge counts as positive).
+    // Pass `fresh = false` while there is no battery data so the gap isn't integrated.
+    pub fn update(&mut self, volts: f64, amps: f64, fresh: bool) {
+        self.update_at(volts, amps, fresh, Instant::now());
+    }
+
+    fn update_at(&mut self, volts: f64, amps: f64, fresh: bool, now: Instant) {
+        if let Some(last) = self.last_update {
+            let elapsed = now.duration_since(last);
+            if fresh && elapsed <= MAX_INTEGRATION_STEP {
+                self.wh += volts * amps * elapsed.as_secs_f64() / 3600.0;
+            }
+        }
+        self.last_update = Some(now);
+    }
+
+    pub fn reset(&mut self) {
+        self.wh = 0.0;
+    }
+
+    pub fn wh(&self) -> f64 {
+        self.wh
+    }
+}
+
+#[derive(Serialize)]
+struct SessionSummary<'a> {
+    started: String,
+    ended: String,
+    duration_secs: f64,
+    distance_miles: f64,
+    energy_consumed_wh: f64,
+    peak_speed_mph: f64,
+    battery_voltage: Option<ValueRange>,
+    battery_current: Option<ValueRange>,
+    battery_charge: Option<ValueRange>,
+    battery_temp: Option<ValueRange>,
+    faults_raised: &'a FaultCounts,
+    rf_link: LinkSummary,
+}
+
+pub struct SessionStats {
+    started: DateTime<Local>,
+    started_at: Instant,
+    energy: EnergyMeter,
+    peak_speed_mph: f64,
+    battery_voltage: Option<ValueRange>,
+    battery_current: Option<ValueRange>,
+    battery_charge: Option<ValueRange>,
+    battery_temp: Option<ValueRange>,
+    faults: FaultCounts,
+}
+
+impl SessionStats {
+    pub fn new() -> Self {
+        Self {
+            started: Local::now(),
+            started_at: Instant::now(),
+            energy: EnergyMeter::new(),
+            peak_speed_mph: 0.0,
+            battery_voltage: None,
+            battery_current: None,
+            battery_charge: None,
+            battery_temp: None,
+            faults: FaultCounts::default(),
+        }
+    }
+
+    pub fn record_battery_voltage(&mut self, volts: f64) {
+        record(&mut self.battery_voltage, volts);
+    }
+
+    pub fn record_battery_current(&mut self, amps: f64) {
+        record(&mut self.battery_current, amps);
+    }
+
+    pub fn record_battery_charge(&mut self, percent: f64) {
+        record(&mut self.battery_charge, percent);
+    }
+
+    pub fn record_battery_temp(&mut self, temp: f64) {
+        record(&mut self.battery_temp, temp);
+    }
+
+    pub fn record_speed(&mut self, speed_mph: f64) {
+        self.peak_speed_mph = self.peak_speed_mph.max(speed_mph.abs());
+    }
+
+    // Count a fault when it becomes active (not on every frame that keeps it active)
+    pub fn record_fault(&mut self, severity: FaultSeverity) {
+        match severity {
+            FaultSeverity::Critical => self.faults.critical += 1,
+            FaultSeverity::Error => self.faults.error += 1,
+            FaultSeverity::Warning => self.faults.warning += 1,
+        }
+    }
+
+    // Integrate pack power into energy. Discharge counts as positive; pass `fresh = false`
+    // until battery data has arrived so the gap isn't integrated.
+    pub fn integrate_power(&mut self, volts: f64, amps: f64, fresh: bool) {
+        self.energy.update(volts, amps, fresh);
+    }
+
+    pub fn has_battery_data(&self) -> bool {
+        self.battery_voltage.is_some() && self.battery_current.is_some()
+    }
+
+    // Write the summary into `dir` (next to the CAN logs); returns the file written
+    pub fn write_summary(
+        &self,
+        dir: &Path,
+        distance_miles: f64,
+        link: LinkSummary,
+    ) -> Result<PathBuf, String> {
+        let ended = Local::now();
+        let summary = SessionSummary {
+            started: self.started.to_rfc3339(),
+            ended: ended.to_rfc3339(),
+            duration_secs: self.started_at.elapsed().as_secs_f64(),
+            distance_miles,
+            energy_consumed_wh: self.energy.wh(),
+            peak_speed_mph: self.peak_speed_mph,
+            battery_voltage: self.battery_voltage,
+            battery_current: self.battery_current,
+            battery_charge: self.battery_charge,
+            battery_temp: self.battery_temp,
+            faults_raised: &self.faults,
+            rf_link: link,
+        };
+
+        let path = dir.join(format!(
+            "session_summary_{}.json",
+            self.started.format("%Y%m%d_%H%M%S")
+        ));
+        let json = serde_json::to_string_pretty(&summary).map_err(|e| e.to_string())?;
+        fs::write(&path, json).map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_energy_meter_integrates_power_updates() {
+        let start = Instant::now();
+        let mut meter = EnergyMeter::new();
+
+        // 100 V at 36 A for 10 updates 100 ms apart: 3.6 kW for one second = 1 Wh
+        meter.update_at(100.0, 36.0, true, start);
+        for i in 1..=10 {
+            meter.update_at(100.0, 36.0, true, start + Duration::from_millis(100 * i));
+        }
+        assert!((meter.wh() - 1.0).abs() < 1e-9);
+
+        // A gap in BMS updates is skipped rather than integrated at the last power
+        meter.update_at(100.0, 36.0, true, start + Duration::from_secs(10));
+        assert!((meter.wh() - 1.0).abs() < 1e-9);
+
+        // Regen counts against the total
+        meter.update_at(100.0, -36.0, true, start + Duration::from_millis(10_500));
+        assert!((meter.wh() - 0.5).abs() < 1e-9);
+
+        // Samples without battery data move the clock on but add nothing
+        meter.update_at(100.0, 36.0, false, start + Duration::from_millis(10_600));
+        assert!((meter.wh() - 0.5).abs() < 1e-9);
+
+        meter.reset();
+        assert_eq!(meter.wh(), 0.0);
+    }
+}
@@ -0,0 +1,185 @@
+// LAN feed of raw CAN frames over UDP, for pit laptops on the same network.
+//
+// --udp-broadcast ADDR:PORT sends every frame the GUI receives; --udp-listen PORT takes frames
+// from such a feed instead of can0 and decodes them with the local DBC, like --replay.
+//
+// A packet is "TL", a format version byte and a frame count byte, then per frame the CAN ID as
+// a big-endian u32 (bit 31 set for extended IDs), the data length and the data. Frames are
+// packed until the next one would push the packet past MAX_PACKET_BYTES (well under a 1500 byte
+// Ethernet/WiFi MTU, so packets are never fragmented) or the GUI tick flushes them.
+use crate::can::build_frame;
+use log::warn;
+use socketcan::{CanFrame, EmbeddedFrame};
+use std::collections::VecDeque;
+use std::net::{SocketAddr, UdpSocket};
+
+const PACKET_MAGIC: &[u8; 2] = b"TL";
+const PACKET_VERSION: u8 = 1;
+const HEADER_BYTES: usize = 4; // Magic, version, frame count
+const MAX_PACKET_BYTES: usize = 1400;
+const EXTENDED_FLAG: u32 = 1 << 31;
+
+fn encode_frame(frame: &CanFrame, packet: &mut Vec<u8>) {
+    let id = match frame.id() {
+        socketcan::Id::Standard(std_id) => std_id.as_raw() as u32,
+        socketcan::Id::Extended(ext_id) => ext_id.as_raw() | EXTENDED_FLAG,
+    };
+    packet.extend_from_slice(&id.to_be_bytes());
+    packet.push(frame.data().len() as u8);
+    packet.extend_from_slice(frame.data());
+}
+
+fn decode_packet(packet: &[u8]) -> Result<Vec<CanFrame>, String> {
+    if packet.len() < HEADER_BYTES || &packet[..2] != PACKET_MAGIC {
+        return Err("not a telemetry packet".to_string());
+    }
+    if packet[2] != PACKET_VERSION {
+        return Err(format!("unsupported packet version {}", packet[2]));
+    }
+
+    let count = packet[3] as usize;
+    let mut frames = Vec::with_capacity(count);
+    let mut rest = &packet[HEADER_BYTES..];
+    for _ in 0..count {
+        if rest.len() < 5 {
+            return Err("truncated frame header".to_string());
+        }
+        let id = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]);
+        let len = rest[4] as usize;
+        let data = rest
+            .get(5..5 + len)
+            .ok_or_else(|| "truncated frame data".to_string())?;
+        let frame = build_frame(id & !EXTENDED_FLAG, id & EXTENDED_FLAG != 0, data)
+            .ok_or_else(|| format!("invalid frame 0x{:X}", id & !EXTENDED_FLAG))?;
+        frames.push(frame);
+        rest = &rest[5 + len..];
+    }
+    Ok(frames)
+}
+
+pub struct UdpBroadcaster {
+    socket: UdpSocket,
+    target: SocketAddr,
+    packet: Vec<u8>,
+    send_failures: u64,
+}
+
+impl UdpBroadcaster {
+    pub fn new(target: SocketAddr) -> Result<Self, String> {
+        let socket = UdpSocket::bind(("0.0.0.0", 0))
+            .map_err(|e| format!("Failed to open UDP socket: {}", e))?;
+        socket
+            .set_broadcast(true)
+            .and_then(|()| socket.set_nonblocking(true))
+            .map_err(|e| format!("Failed to configure UDP socket: {}", e))?;
+
+        Ok(Self {
+            socket,
+            target,
+            packet: Self::empty_packet(),
+            send_failures: 0,
+        })
+    }
+
+    fn empty_packet() -> Vec<u8> {
+        let mut packet = Vec::with_capacity(MAX_PACKET_BYTES);
+        packet.extend_from_slice(PACKET_MAGIC);
+        packet.extend_from_slice(&[PACKET_VERSION, 0]);
+        packet
+    }
+
+    // Add a frame to the pending packet, sending it first if the frame wouldn't fit
+    pub fn push(&mut self, frame: &CanFrame) {
+        let frame_bytes = 5 + frame.data().len();
+        if self.packet.len() + frame_bytes > MAX_PACKET_BYTES || self.packet[3] == u8::MAX {
+            self.flush();
+        }
+        encode_frame(frame, &mut self.packet);
+        self.packet[3] += 1;
+    }
+
+    // Send the pending frames, if any; a failed send drops them rather than blocking
+    pub fn flush(&mut self) {
+        if self.packet[3] == 0 {
+            return;
+        }
+        if let Err(e) = self.socket.send_to(&self.packet, self.target) {
+            if self.send_failures.is_multiple_of(100) {
+                warn!("UDP broadcast to {} failed: {}", self.target, e);
+            }
+            self.send_failures += 1;
+        }
+        self.packet = Self::empty_packet();
+    }
+}
+
+pub struct UdpReceiver {
+    socket: tokio::net::UdpSocket,
+    pending: VecDeque<CanFrame>,
+}
+
+impl UdpReceiver {
+    // Must be called from within the tokio runtime (the GUI subscription)
+    pub fn bind(port: u16) -> Result<Self, String> {
+        let socket = UdpSocket::bind(("0.0.0.0", port))
+            .map_err(|e| format!("Failed to bind UDP port {}: {}", port, e))?;
+        socket
+            .set_nonblocking(true)
+            .map_err(|e| format!("Failed to configure UDP socket: {}", e))?;
+        let socket = tokio::net::UdpSocket::from_std(socket)
+            .map_err(|e| format!("Failed to register UDP socket: {}", e))?;
+
+        Ok(Self {
+            socket,
+            pending: VecDeque::new(),
+        })
+    }
+
+    // Wait for the next frame; malformed packets are reported and skipped
+    pub async fn next_frame(&mut self) -> Result<CanFrame, String> {
+        let mut buf = [0u8; 2048];
+        loop {
+            if let Some(frame) = self.pending.pop_front() {
+                return Ok(frame);
+            }
+            let (len, peer) = self
+                .socket
+                .recv_from(&mut buf)
+                .await
+                .map_err(|e| format!("UDP receive failed: {}", e))?;
+            match decode_packet(&buf[..len]) {
+                Ok(frames) => self.pending.extend(frames),
+                Err(e) => warn!("Ignoring UDP packet from {}: {}", peer, e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_packet_round_trip() {
+        let frames = [
+            build_frame(0x320, false, &[1, 2, 3]).unwrap(),
+            build_frame(0x1806_E5F4, true, &[0xAA; 8]).unwrap(),
+            build_frame(0x7FF, false, &[]).unwrap(),
+        ];
+        let mut packet = UdpBroadcaster::empty_packet();
+        for frame in &frames {
+            encode_frame(frame, &mut packet);
+        }
+        packet[3] = frames.len() as u8;
+
+        let decoded = decode_packet(&packet).unwrap();
+        assert_eq!(decoded.len(), 3);
+        for (original, decoded) in frames.iter().zip(&decoded) {
+            assert_eq!(original.id(), decoded.id());
+            assert_eq!(original.data(), decoded.data());
+        }
+
+        assert!(decode_packet(&packet[..packet.len() - 1]).is_err());
+        assert!(decode_packet(b"XX\x01\x00").is_err());
+    }
+}
@@ -0,0 +1,208 @@
+// Optional WebSocket server (--ws-port) streaming live telemetry JSON to pit dashboards.
+//
+// Runs as tasks on the tokio runtime iced already drives, fed through a broadcast channel from
+// the CanFrameReceived handler, so slow or misbehaving clients can't stall the CAN subscription
+// or the RFD batcher. Clients that fall behind skip messages rather than buffering them.
+//
+// Every WebSocket text message is one JSON object tagged by "type":
+//
+//   {"type": "telemetry", "ts": 1700000000000, "can_id": 800,
+//    "message_name": "BMS_Power",
+//    "signals": [{"name": "Pack_Current", "raw": -123, "value": -12.3, "label": null,
+//                 "unit": "A", "out_of_range": false}, ...]}
+//
+//     One per decoded CAN frame (undecodable frames are not sent). `ts` is Unix milliseconds,
+//     `message_name` the GUI message name, or the DBC one for messages the GUI doesn't map
+//     (e.g. "MSGID_0X350"), `value` the scaled engineering value, `label` the DBC value-table text,
+//     `unit` the DBC unit ("" when it has none) and `out_of_range` whether the value fell
+//     outside the DBC [min|max] (`value` is then clipped if --clamp-signals is on).
+//
+//   {"type": "status", "ts": 1700000000000, "can_connected": true,
+//    "rfd_connected": true, "rfd_failures": 0, "rfd_queue": 3,
+//    "faults": [{"name": "...", "message": "BMS_DTC", "severity": "critical", "value": "..."}]}
+//
+//     Once per second: link health plus every active fault (severity is "critical",
+//     "error" or "warning").
+use crate::can::DecodedSignal;
+use crate::gui_modules::FaultSeverity;
+use futures::{SinkExt, StreamExt};
+use log::{error, info, warn};
+use serde::Serialize;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+// Messages buffered per client before a slow client starts skipping
+const CLIENT_BUFFER: usize = 256;
+
+#[derive(Serialize)]
+pub struct WsFault<'a> {
+    pub name: &'a str,
+    pub message: &'a str,
+    pub severity: &'static str,
+    pub value: &'a str,
+}
+
+pub fn severity_name(severity: FaultSeverity) -> &'static str {
+    match severity {
+        FaultSeverity::Critical => "critical",
+        FaultSeverity::Error => "error",
+        FaultSeverity::Warning => "warning",
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WsEvent<'a> {
+    Telemetry {
+        #[serde(rename = "ts")]
+        timestamp_ms: u64,
+        can_id: u32,
+        #[serde(rename = "message_name")]
+        message: &'a str,
+        signals: &'a [DecodedSignal],
+    },
+    Status {
+        #[serde(rename = "ts")]
+        timestamp_ms: u64,
+        can_connected: bool,
+        rfd_connected: bool,
+        rfd_failures: u32,
+        rfd_queue: usize,
+        faults: Vec<WsFault<'a>>,
+    },
+}
+
+// Handle the GUI publishes through; cheap to call when nobody is connected
+#[derive(Clone)]
+pub struct TelemetryBroadcaster {
+    sender: broadcast::Sender<String>,
+}
+
+impl TelemetryBroadcaster {
+    pub fn has_clients(&self) -> bool {
+        self.sender.receiver_count() > 0
+    }
+
+    pub fn publish(&self, event: &WsEvent) {
+        if !self.has_clients() {
+            return;
+        }
+        match serde_json::to_string(event) {
+            // Only fails when every client disconnected in the meantime
+            Ok(json) => {
+                let _ = self.sender.send(json);
+            }
+            Err(e) => error!("Failed to serialize WebSocket event: {}", e),
+        }
+    }
+}
+
+// Bind 0.0.0.0:`port` and serve clients on the current tokio runtime. Must be called from
+// within it (Application::new runs inside iced's executor).
+pub fn start_ws_server(port: u16) -> Result<TelemetryBroadcaster, String> {
+    let runtime = tokio::runtime::Handle::try_current()
+        .map_err(|e| format!("WebSocket server needs a tokio runtime: {}", e))?;
+
+    let listener = std::net::TcpListener::bind(("0.0.0.0", port))
+        .map_err(|e| format!("Failed to bind WebSocket port {}: {}", port, e))?;
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| format!("Failed to configure WebSocket listener: {}", e))?;
+    let listener = {
+        let _guard = runtime.enter();
+        TcpListener::from_std(listener)
+            .map_err(|e| format!("Failed to register WebSocket listener: {}", e))?
+    };
+
+    let (sender, _) = broadcast::channel(CLIENT_BUFFER);
+    let broadcaster = TelemetryBroadcaster {
+        sender: sender.clone(),
+    };
+
+    runtime.spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer)) => {
+                    info!("WebSocket client connected: {}", peer);
+                    tokio::spawn(serve_client(stream, sender.subscribe()));
+                }
+                Err(e) => warn!("WebSocket accept error: {}", e),
+            }
+        }
+    });
+
+    Ok(broadcaster)
+}
+
+async fn serve_client(stream: TcpStream, mut events: broadcast::Receiver<String>) {
+    let peer = stream
+        .peer_addr()
+        .map(|addr| addr.to_string())
+        .unwrap_or_else(|_| "unknown".to_string());
+    let socket = match tokio_tungstenite::accept_async(stream).await {
+        Ok(socket) => socket,
+        Err(e) => {
+            warn!("WebSocket handshake with {} failed: {}", peer, e);
+            return;
+        }
+    };
+    let (mut outgoing, mut incoming) = socket.split();
+
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(json) => {
+                    if outgoing.send(WsMessage::text(json)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("WebSocket client {} lagging, skipped {} messages", peer, skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            // Drain client frames so pings are answered and closes are noticed
+            message = incoming.next() => match message {
+                Some(Ok(WsMessage::Close(_))) | None | Some(Err(_)) => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+
+    info!("WebSocket client disconnected: {}", peer);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_telemetry_event_schema() {
+        let signals = [DecodedSignal {
+            name: "Pack_Current".to_string(),
+            raw: -123,
+            value: -12.3,
+            label: None,
+            unit: "A".to_string(),
+            out_of_range: false,
+        }];
+        let json = serde_json::to_value(WsEvent::Telemetry {
+            timestamp_ms: 1,
+            can_id: 0x320,
+            message: "BMS_Power",
+            signals: &signals,
+        })
+        .unwrap();
+
+        assert_eq!(json["type"], "telemetry");
+        assert_eq!(json["ts"], 1);
+        assert_eq!(json["can_id"], 0x320);
+        assert_eq!(json["message_name"], "BMS_Power");
+        assert_eq!(json["signals"][0]["name"], "Pack_Current");
+        assert_eq!(json["signals"][0]["raw"], -123);
+        assert!(json["signals"][0]["label"].is_null());
+        assert_eq!(json["signals"][0]["unit"], "A");
+        assert_eq!(json["signals"][0]["out_of_range"], false);
+    }
+}